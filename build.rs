@@ -12,6 +12,9 @@ struct CachedBuildData {
     procedural_intrinsic_mappings: Vec<(u32, u32)>,
     //use ordered hash map
     perk_formula_timestamps: BTreeMap<u64, u64>,
+    //offline builds never record new timestamps, unknown formulas get this instead
+    #[serde(skip)]
+    fixed_timestamp: Option<u64>,
 }
 impl CachedBuildData {
     fn has_data(&self) -> bool {
@@ -28,7 +31,9 @@ impl CachedBuildData {
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_secs();
-        if !self.perk_formula_timestamps.contains_key(&uuid) {
+        if let Some(fixed) = self.fixed_timestamp {
+            *self.perk_formula_timestamps.get(&uuid).unwrap_or(&fixed)
+        } else if !self.perk_formula_timestamps.contains_key(&uuid) {
             self.perk_formula_timestamps.insert(uuid, now);
             now
         } else {
@@ -336,6 +341,54 @@ impl UuidTimestamp for FiringData {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct MeleeFormula {
+    pub light_damage: f64,
+    pub light_delay: f64,
+    pub heavy_damage: f64,
+    pub heavy_delay: f64,
+    pub guard_damage: f64,
+    pub guard_delay: f64,
+    pub ammo_per_heavy: i32,
+    pub timestamp: u64,
+}
+impl From<&Map<String, Value>> for MeleeFormula {
+    fn from(_val: &Map<String, Value>) -> Self {
+        let get_f64 = |key: &str| {
+            _val.get(key)
+                .unwrap_or(&json_0_float())
+                .as_f64()
+                .unwrap_or_default()
+        };
+        MeleeFormula {
+            light_damage: get_f64("light_damage"),
+            light_delay: get_f64("light_delay"),
+            heavy_damage: get_f64("heavy_damage"),
+            heavy_delay: get_f64("heavy_delay"),
+            guard_damage: get_f64("guard_damage"),
+            guard_delay: get_f64("guard_delay"),
+            ammo_per_heavy: _val
+                .get("ammo_per_heavy")
+                .unwrap_or(&Value::Null)
+                .as_i64()
+                .unwrap_or_default() as i32,
+            timestamp: 0,
+        }
+    }
+}
+impl UuidTimestamp for MeleeFormula {
+    fn uuid(&self) -> f64 {
+        (self.light_damage * 613.7
+            + self.light_delay * 8231.3
+            + self.heavy_damage * 977.1
+            + self.heavy_delay * 6143.9
+            + self.guard_damage * 433.3
+            + self.guard_delay * 5521.7
+            + self.ammo_per_heavy as f64 * 12211.1)
+            * 10.0
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 struct DataPointers {
     h: usize,
@@ -344,6 +397,7 @@ struct DataPointers {
     s: usize,
     f: usize,
     a: usize,
+    m: usize,
 }
 
 fn write_variable(
@@ -363,9 +417,48 @@ fn write_variable(
     }
 }
 
+//offline builds only read checked in files and never touch the network or the source tree,
+//enabled with the `offline` feature or D2_CALC_OFFLINE=1
+fn is_offline() -> bool {
+    std::env::var("CARGO_FEATURE_OFFLINE").is_ok()
+        || std::env::var("D2_CALC_OFFLINE").is_ok_and(|val| val == "1" || val == "true")
+}
+
+//SOURCE_DATE_EPOCH pins the build time of offline builds, defaults to the unix epoch
+fn source_date_epoch() -> u64 {
+    match std::env::var("SOURCE_DATE_EPOCH") {
+        Ok(val) => val
+            .parse::<u64>()
+            .unwrap_or_else(|_| panic!("SOURCE_DATE_EPOCH must be unix seconds, got {}", val)),
+        Err(_) => 0,
+    }
+}
+
+fn read_offline_resource(name: &str) -> String {
+    let path = std::path::Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join("build_resources/offline")
+        .join(name);
+    std::fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!(
+            "offline build requires {} which could not be read: {}",
+            path.display(),
+            err
+        )
+    })
+}
+
 fn main() {
+    //any rerun-if line turns off rerunning on every file change, so the inputs are listed too
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=build_resources");
+    println!("cargo:rerun-if-env-changed=D2_CALC_OFFLINE");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    let offline = is_offline();
     let mut opts = built::Options::default();
     opts.set_dependencies(true);
+    if offline {
+        opts.set_time(false);
+    }
 
     let src = std::env::var("CARGO_MANIFEST_DIR").unwrap();
     let built_dst = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("built.rs");
@@ -373,21 +466,54 @@ fn main() {
 
     built::write_built_file_with_opts(&opts, src.as_ref(), &built_dst)
         .expect("Failed to acquire build-time information");
+    if offline {
+        let mut built_file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&built_dst)
+            .unwrap();
+        let built_time = chrono::DateTime::from_timestamp(source_date_epoch() as i64, 0)
+            .expect("SOURCE_DATE_EPOCH is out of range");
+        write_variable(
+            &mut built_file,
+            "BUILT_TIME_UTC",
+            "&str",
+            format!("{:?}", built_time.to_rfc2822()),
+            "The build time in RFC2822, UTC. Taken from SOURCE_DATE_EPOCH for offline builds",
+        );
+    }
 
     let mut formula_file = std::fs::File::create(formula_dst).unwrap();
 
     //write imports in file
     let res = writeln!(
         formula_file,
-        "use crate::types::rs_types::{{StatQuadraticFormula, RangeFormula, HandlingFormula, ReloadFormula, DamageMods, AmmoFormula, DataPointers, FiringData, WeaponPath}};");
+        "use crate::types::rs_types::{{StatQuadraticFormula, RangeFormula, HandlingFormula, ReloadFormula, DamageMods, AmmoFormula, DataPointers, FiringData, MeleeFormula, WeaponPath}};");
     if res.is_err() {
         panic!("cargo:warning=error writing imports");
     }
 
     let build_cache_path = std::path::Path::new("./build_resources/cached_build.ron");
     let mut cached_data: CachedBuildData;
+    if offline {
+        cached_data = ron::de::from_str(&std::fs::read_to_string(build_cache_path).unwrap_or_else(
+            |err| {
+                panic!(
+                    "offline build requires {} which could not be read: {}",
+                    build_cache_path.display(),
+                    err
+                )
+            },
+        ))
+        .unwrap_or_else(|err| {
+            panic!(
+                "offline build could not parse {}: {}",
+                build_cache_path.display(),
+                err
+            )
+        });
+        cached_data.fixed_timestamp = Some(source_date_epoch());
     //if "./build_resources/cached_build.ron" exists
-    if !build_cache_path.exists() {
+    } else if !build_cache_path.exists() {
         println!("cargo:warning=no cached build file found");
         cached_data = CachedBuildData::default();
     } else {
@@ -406,9 +532,16 @@ fn main() {
         }
     }
 
-    construct_enhance_perk_mapping(&mut formula_file, &mut cached_data);
+    if offline {
+        construct_enhance_perk_mapping_offline(&mut formula_file);
+    } else {
+        construct_enhance_perk_mapping(&mut formula_file, &mut cached_data);
+    }
     construct_weapon_formulas(&mut formula_file, &mut cached_data);
 
+    if offline {
+        return;
+    }
     cached_data.sort();
 
     //check if being run by rust-analyzer
@@ -453,6 +586,7 @@ fn construct_weapon_formulas(formula_file: &mut File, cached: &mut CachedBuildDa
         (12, "Sniper Rifle"),
         (24, "Submachine Gun"),
         (33, "Glaive"),
+        (18, "Sword"),
         (25, "Trace Rifle"),
         (17, "Sidearm"),
     ]);
@@ -492,6 +626,7 @@ fn construct_weapon_formulas(formula_file: &mut File, cached: &mut CachedBuildDa
     let mut ammo_data: Vec<AmmoFormula> = vec![AmmoFormula::default()];
     let mut firing_data: Vec<FiringData> = vec![FiringData::default()];
     let mut scalar_data: Vec<DamageMods> = vec![DamageMods::default()];
+    let mut melee_data: Vec<MeleeFormula> = vec![MeleeFormula::default()];
 
     let mut updated_weapon_defs: Vec<(WeaponPath, DataPointers)> = Vec::new();
     for (weapon_id, inner_values) in new_jdata.as_object().unwrap() {
@@ -693,6 +828,21 @@ fn construct_weapon_formulas(formula_file: &mut File, cached: &mut CachedBuildDa
                     firing_data.push(firing);
                 }
 
+                //only swords and glaives have a melee profile, everything else points at the default
+                if let Some(melee_val) = fam.get("melee").and_then(|val| val.as_object()) {
+                    let mut melee: MeleeFormula = melee_val.into();
+                    melee.light_delay *= 1.0 / 30.0;
+                    melee.heavy_delay *= 1.0 / 30.0;
+                    melee.guard_delay *= 1.0 / 30.0;
+                    if let Some(index) = find_uuid(&melee_data, melee.uuid()) {
+                        data.m = index;
+                    } else {
+                        data.m = melee_data.len();
+                        melee.timestamp = cached.get_timestamp(&melee);
+                        melee_data.push(melee);
+                    }
+                }
+
                 if err_list.len() > 0 {
                     return Err(err_list);
                 }
@@ -764,6 +914,13 @@ fn construct_weapon_formulas(formula_file: &mut File, cached: &mut CachedBuildDa
         format!("{:?}", ammo_data),
         "Array of ammo formulas",
     );
+    write_variable(
+        formula_file,
+        "MELEE_DATA",
+        &format!("[MeleeFormula; {}]", melee_data.len()),
+        format!("{:?}", melee_data),
+        "Array of sword and glaive melee formulas",
+    );
 }
 
 fn construct_enhance_perk_mapping(formula_file: &mut File, cached: &mut CachedBuildData) {
@@ -851,17 +1008,6 @@ fn construct_enhance_perk_mapping(formula_file: &mut File, cached: &mut CachedBu
                     .as_str()
                     .unwrap()
                     .to_owned();
-                let intrinsic_map: HashMap<u32, Vec<&str>> = HashMap::from([
-                    (901, vec!["High-Impact Frame"]),
-                    (902, vec!["VEIST Rapid-Fire", "Rapid-Fire Frame"]),
-                    (903, vec!["Adaptive Frame", "Adaptive Glaive"]),
-                    (904, vec!["Aggressive Frame", "Aggressive Glaive"]),
-                    (905, vec!["Lightweight Frame", "MIDA Synergy"]),
-                    (906, vec!["Precision Frame", "Häkke Precision Frame"]),
-                    (907, vec!["Double Fire"]),
-                    (908, vec!["Wave Frame"]),
-                    (911, vec!["Legacy PR-55 Frame"]),
-                ]);
                 let content_paths = manifest_json["Response"]["jsonWorldComponentContentPaths"]
                     ["en"]
                     .as_object()
@@ -873,34 +1019,11 @@ fn construct_enhance_perk_mapping(formula_file: &mut File, cached: &mut CachedBu
                         .unwrap()
                 ));
                 println!("cargo:warning=downloaded new manifest");
-                cached.procedural_intrinsic_mappings.clear();
                 let item_data_json: Value =
                     serde_json::from_str(&item_data_raw.unwrap().text().unwrap()).unwrap();
-                for (key, value) in item_data_json.as_object().unwrap() {
-                    let hash = key.parse::<u32>().unwrap();
-                    //does value have a key called itemTypeDisplayName?
-                    if !value
-                        .as_object()
-                        .unwrap()
-                        .contains_key("itemTypeDisplayName")
-                    {
-                        continue;
-                    }
-                    if !value["itemTypeDisplayName"]
-                        .as_str()
-                        .unwrap()
-                        .contains("Intrinsic")
-                    {
-                        continue;
-                    }
-                    let name = value["displayProperties"]["name"].as_str().unwrap();
-                    for (id, names) in intrinsic_map.iter() {
-                        if names.contains(&name) {
-                            perk_mappings.push((hash, *id));
-                            cached.procedural_intrinsic_mappings.push((hash, *id));
-                        }
-                    }
-                }
+                cached.procedural_intrinsic_mappings =
+                    intrinsic_mappings(item_data_json.as_object().unwrap());
+                perk_mappings.append(&mut cached.procedural_intrinsic_mappings.clone());
             }
         }
     } else {
@@ -915,3 +1038,73 @@ fn construct_enhance_perk_mapping(formula_file: &mut File, cached: &mut CachedBu
         "Mapping of enhanced perks and intrinsics to their base perk/intrinsic",
     );
 }
+
+//matches intrinsic items from the manifest to the frame ids used in weapon_formulas.json
+fn intrinsic_mappings(items: &Map<String, Value>) -> Vec<(u32, u32)> {
+    let intrinsic_map: HashMap<u32, Vec<&str>> = HashMap::from([
+        (901, vec!["High-Impact Frame"]),
+        (902, vec!["VEIST Rapid-Fire", "Rapid-Fire Frame"]),
+        (903, vec!["Adaptive Frame", "Adaptive Glaive"]),
+        (904, vec!["Aggressive Frame", "Aggressive Glaive"]),
+        (905, vec!["Lightweight Frame", "MIDA Synergy"]),
+        (906, vec!["Precision Frame", "Häkke Precision Frame"]),
+        (907, vec!["Double Fire"]),
+        (908, vec!["Wave Frame"]),
+        (911, vec!["Legacy PR-55 Frame"]),
+    ]);
+    let mut mappings = Vec::new();
+    for (key, value) in items {
+        let hash = key.parse::<u32>().unwrap();
+        //does value have a key called itemTypeDisplayName?
+        if !value
+            .as_object()
+            .unwrap()
+            .contains_key("itemTypeDisplayName")
+        {
+            continue;
+        }
+        if !value["itemTypeDisplayName"]
+            .as_str()
+            .unwrap()
+            .contains("Intrinsic")
+        {
+            continue;
+        }
+        let name = value["displayProperties"]["name"].as_str().unwrap();
+        for (id, names) in intrinsic_map.iter() {
+            if names.contains(&name) {
+                mappings.push((hash, *id));
+            }
+        }
+    }
+    mappings
+}
+
+//same as construct_enhance_perk_mapping but only reads the files in build_resources/offline
+fn construct_enhance_perk_mapping_offline(formula_file: &mut File) {
+    let dim_file = "trait-to-enhanced-trait.json";
+    let dct: HashMap<String, u32> = serde_json::from_str(&read_offline_resource(dim_file))
+        .unwrap_or_else(|err| panic!("offline build could not parse {}: {}", dim_file, err));
+    let mut perk_mappings: Vec<(u32, u32)> = dct
+        .into_iter()
+        .map(|(base, enhanced)| (enhanced, base.parse::<u32>().unwrap()))
+        .collect();
+
+    let manifest_file = "manifest_intrinsics.json";
+    let manifest: Value = serde_json::from_str(&read_offline_resource(manifest_file))
+        .unwrap_or_else(|err| panic!("offline build could not parse {}: {}", manifest_file, err));
+    let items = manifest["items"]
+        .as_object()
+        .unwrap_or_else(|| panic!("offline build expects an items object in {}", manifest_file));
+    perk_mappings.append(&mut intrinsic_mappings(items));
+
+    //hash map order is random, sorting keeps the output byte identical
+    perk_mappings.sort();
+    write_variable(
+        formula_file,
+        "ENHANCE_PERK_MAPPING",
+        &format!("[(u32, u32); {}]", perk_mappings.len()),
+        format!("{:?}", perk_mappings),
+        "Mapping of enhanced perks and intrinsics to their base perk/intrinsic",
+    );
+}
@@ -1,4 +1,213 @@
 type Hash = number;
+export interface PerkSpec {
+  hash: Hash;
+  value?: number;
+  enhanced?: boolean;
+  stat_buffs?: Map<Hash, number>;
+}
+
+export type Objective =
+  | "SustainedDps"
+  | { OptimalTtk: { resilience: number; overshield: number } }
+  | { AdsFalloff: { pvp: boolean } }
+  | { Weighted: Array<{ objective: Objective; weight: number }> };
+
+export interface OptimizerInput {
+  columns: Array<Array<PerkSpec>>;
+  masterworks?: Array<Map<Hash, number>>;
+  objective: Objective;
+  top_n?: number;
+}
+
+export interface RollScore {
+  perks: Array<PerkSpec>;
+  masterwork: Map<Hash, number>;
+  score: number;
+  values: Array<number>;
+}
+
+export interface StartingMagState {
+  mag?: number;
+  reserves?: number;
+  perk_values?: Map<Hash, number>;
+  kill_interval?: number;
+}
+
+export interface DurationDpsResponse {
+  time_damage_data: Array<[number, number]>;
+  total_damage: number;
+  total_shots: number;
+  duration: number;
+  dps: number;
+  mag_left: number;
+  reserves_left: number;
+}
+
+export interface AddClearWave {
+  count?: number;
+  retarget_time?: number;
+}
+
+export interface AddClearResponse {
+  dps: {
+    dps_per_mag: Array<number>;
+    time_damage_data: Array<[number, number]>;
+    total_damage: number;
+    total_time: number;
+    total_shots: number;
+  };
+  kill_times: Array<number>;
+  time_to_clear?: number;
+}
+
+export interface AbilityDamageResponse {
+  impact: number;
+  secondary: number;
+  total: number;
+  hits: number;
+  damage_mult: number;
+}
+
+export interface AbilityDpsResponse {
+  total_damage: number;
+  total_time: number;
+  dps: number;
+}
+
+export type PatchDiffCategory = "Range" | "Handling" | "Reload" | "Ammo" | "Firing" | "PveScalars";
+
+export interface ValueChange {
+  category: PatchDiffCategory;
+  field: string;
+  old: number;
+  new: number;
+}
+
+export interface PatchDiff {
+  weapon_hash: Hash;
+  old_patch: string;
+  new_patch: string;
+  changes: Array<ValueChange>;
+}
+
+export interface PveKillData {
+  precision_ratio: number;
+  precision_shots: number;
+  body_shots: number;
+  ammo_used: number;
+  reloads: number;
+  time_taken: number;
+  killed: boolean;
+  phase_times: Array<number | undefined>;
+  stuns: number;
+}
+
+export interface PveTtkResponse {
+  enemy: string;
+  health: number;
+  adjusted_health: number;
+  kills: Array<PveKillData>;
+  melee: PveKillData | undefined;
+}
+
+export interface PvpTarget {
+  resilience?: number;
+  overshield_type?: "NONE" | "PERSISTENT" | "DECAYING";
+  overshield?: number;
+  overshield_decay?: number;
+  healing?: "NONE" | "RESTORATION" | "AMPLIFIED" | "DEVOUR";
+  recovery?: number;
+  recovery_delay?: number;
+  frost_armor?: number;
+  woven_mail?: boolean;
+  concussive_dampener?: boolean;
+}
+
+export interface PvpShotMix {
+  headshots: number;
+  bodyshots: number;
+  time_taken: number;
+  reloads: number;
+  killed: boolean;
+  regen_reset: boolean;
+}
+
+export interface PvpTtkResponse {
+  health: number;
+  mixes: Array<PvpShotMix>;
+  melee: PvpShotMix | undefined;
+}
+
+export interface Engagement {
+  distance?: number;
+  ads?: boolean;
+}
+
+export interface TtkDistancePoint {
+  distance: number;
+  optimal_shots: number;
+  optimal_ttk: number | undefined;
+  body_shots: number;
+  body_ttk: number | undefined;
+}
+
+export interface TtkCurveResponse {
+  points: Array<TtkDistancePoint>;
+  breakpoints: Array<number>;
+}
+
+export interface AccuracyModel {
+  hit_rate?: number;
+  precision_ratio?: number;
+  spread?: number;
+  distance?: number;
+  range_penalty?: number;
+}
+
+export interface MonteCarloSettings {
+  trials?: number;
+  seed?: number;
+}
+
+export interface Percentiles {
+  mean: number;
+  p10: number;
+  p50: number;
+  p90: number;
+}
+
+export interface AccuracyDpsResponse {
+  perfect_dps: number;
+  dps: Percentiles;
+  hit_chance: number;
+}
+
+export interface AccuracyTtkResponse {
+  perfect_ttk: number;
+  ttk: Percentiles;
+  kill_rate: number;
+  hit_chance: number;
+}
+
+export type PlayerBuff =
+  | "Radiant"
+  | "WellOfRadiance"
+  | "EmpoweringRift"
+  | "WardOfDawn"
+  | "BannerShield"
+  | "NobleRounds"
+  | "Weaken";
+
+export interface PlayerState {
+  subclass?: "ARC" | "VOID" | "SOLAR" | "STASIS" | "KINETIC" | "STRAND" | "UNKNOWN";
+  aspects?: Array<Hash>;
+  fragments?: Array<Hash>;
+  resilience?: number;
+  mobility?: number;
+  recovery?: number;
+  buffs?: Array<PlayerBuff>;
+}
+
 /**
 */
 export function start(): void;
@@ -11,10 +220,55 @@ export function getMetadata(): MetaData;
 */
 export function stringifyWeapon(): string;
 /**
-*Returns the weapon as a JSON structure, snake case fields
-* @returns {any}
+*Returns the current weapon and encounter as a build spec JSON string
+* @returns {string}
+*/
+export function exportBuild(): string;
+/**
+* @param {string} _build_json
+*/
+export function importBuild(_build_json: string): void;
+/**
+*Hot swaps the weapon formulas with ones parsed from a weapon_formulas.json string
+* @param {string} _formula_json
+*/
+export function setFormulaDatabase(_formula_json: string): void;
+/**
+* @param {Uint8Array} _bytes
+*/
+export function setFormulaDatabaseBytes(_bytes: Uint8Array): void;
+/**
+*Goes back to the formulas compiled into the api
+*/
+export function resetFormulaDatabase(): void;
+/**
+*Registers a season or patch from a weapon_formulas.json string, an existing id is replaced
+* @param {string} _id
+* @param {string} _formula_json
 */
-export function weaponJSON(): any;
+export function addFormulaPatch(_id: string, _formula_json: string): void;
+/**
+* @returns {Array<string>}
+*/
+export function getFormulaPatches(): Array<string>;
+/**
+* @param {string} _id
+*/
+export function useFormulaPatch(_id: string): void;
+/**
+*Values of the current weapon that changed between two patches
+* @param {string} _old_patch
+* @param {string} _new_patch
+* @param {boolean} _pvp
+* @returns {PatchDiff}
+*/
+export function diffFormulaPatches(_old_patch: string, _new_patch: string, _pvp: boolean): PatchDiff;
+/**
+*Ranks perk and masterwork combinations for the current weapon and encounter
+* @param {OptimizerInput} _input
+* @returns {Array<RollScore>}
+*/
+export function optimizePerks(_input: OptimizerInput): Array<RollScore>;
 /**
 * @param {Hash} _hash
 * @param {number} _weapon_type_id
@@ -81,6 +335,22 @@ export function getWeaponAmmoSizes(_dynamic_traits: boolean, _pvp: boolean): Amm
 */
 export function getWeaponTtk(_overhsield: number): Array<ResillienceTtkSummary>;
 /**
+*Time to kill the current enemy in the current activity
+* @returns {PveTtkResponse}
+*/
+export function getWeaponPveTtk(): PveTtkResponse;
+/**
+*Shots to kill a guardian for every headshot and bodyshot mix
+* @param {PvpTarget} _target
+* @returns {PvpTtkResponse}
+*/
+export function getWeaponPvpTtk(_target: PvpTarget): PvpTtkResponse;
+/**
+*Shots to kill a guardian with the same resilience and recovery as the player
+* @returns {PvpTtkResponse}
+*/
+export function getWeaponMirrorTtk(): PvpTtkResponse;
+/**
 * @param {boolean} _use_rpl
 * @returns {DpsResponse}
 */
@@ -93,18 +363,62 @@ export function getWeaponDps(_use_rpl: boolean): DpsResponse;
 */
 export function getWeaponFiringData(_dynamic_traits: boolean, _pvp: boolean, _use_rpl: boolean): FiringResponse;
 /**
+* @param {number} _hash
+*/
+export function setAbility(_hash: number): void;
+/**
+*Buffs and debuffs on the current weapon are applied to the ability as well
+* @param {boolean} _pvp
+* @returns {AbilityDamageResponse}
+*/
+export function getAbilityDamage(_pvp: boolean): AbilityDamageResponse;
+/**
+* @param {boolean} _pvp
+* @returns {AbilityDpsResponse}
+*/
+export function getAbilityDps(_pvp: boolean): AbilityDpsResponse;
+/**
+*The custom deltas and values are only read for CUSTOM, which uses the override cap
 * @param {number} _rpl
+* @param {number} _player_pl
 * @param {number} _override_cap
 * @param {number} _difficulty
 * @param {number} _enemy_type
+* @param {Float64Array | undefined} _custom_deltas
+* @param {Float64Array | undefined} _custom_values
+*/
+export function setEncounter(_rpl: number, _player_pl: number, _override_cap: number, _difficulty: number, _enemy_type: number, _custom_deltas?: Float64Array, _custom_values?: Float64Array): void;
+/**
+*Replaces the players build, buffs here apply without adding them as perks
+* @param {PlayerState} _state
 */
-export function setEncounter(_rpl: number, _override_cap: number, _difficulty: number, _enemy_type: number): void;
+export function setPlayerState(_state: PlayerState): void;
+/**
+* @returns {PlayerState}
+*/
+export function getPlayerState(): PlayerState;
+/**
+*Pairs of power delta and damage multiplier from -99 up to the delta cap, for charting
+* @returns {Array<[number, number]>}
+*/
+export function getPlDeltaCurve(): Array<[number, number]>;
+/**
+*Loads the enemy archetypes from a json list, every entry has to cite a source
+* @param {string} _catalogue_json
+*/
+export function setEnemyCatalogue(_catalogue_json: string): void;
+/**
+*Replaces the enemy with an archetype from the enemy catalogue
+* @param {number} _id
+*/
+export function setEnemyArchetype(_id: number): void;
 /**
 */
 export enum DifficultyOptions {
   NORMAL,
   RAID,
   MASTER,
+  CUSTOM,
 }
 /**
 */
@@ -119,6 +433,62 @@ export enum EnemyType {
   CHAMPION,
 }
 /**
+* Independent calculation session, does not share state with the global functions
+*/
+export class Calculator {
+  free(): void;
+  constructor();
+  stringifyWeapon(): string;
+  exportBuild(): string;
+  importBuild(_build_json: string): void;
+  setFormulaDatabase(_formula_json: string): void;
+  setFormulaDatabaseBytes(_bytes: Uint8Array): void;
+  resetFormulaDatabase(): void;
+  addFormulaPatch(_id: string, _formula_json: string): void;
+  getFormulaPatches(): Array<string>;
+  useFormulaPatch(_id: string): void;
+  diffFormulaPatches(_old_patch: string, _new_patch: string, _pvp: boolean): PatchDiff;
+  optimizePerks(_input: OptimizerInput): Array<RollScore>;
+  setWeapon(_hash: Hash, _weapon_type_id: number, _intrinsic_hash: Hash, _ammo_type_id: number, _damage_type_id: number): void;
+  getStats(): Map<Hash, Stat>;
+  setStats(_stats: Map<Hash, number>): void;
+  addTrait(_stats: Map<Hash, number>, _value: number, _hash: number): void;
+  removeTrait(_hash: Hash): void;
+  getTraitHashes(): Array<Hash>;
+  setTraitValue(perk_hash: Hash, new_value: number): void;
+  getWeaponRangeFalloff(_dynamic_traits: boolean, _pvp: boolean): RangeResponse;
+  getWeaponHandlingTimes(_dynamic_traits: boolean, _pvp: boolean): HandlingResponse;
+  getWeaponReloadTimes(_dynamic_traits: boolean, _pvp: boolean): ReloadResponse;
+  getWeaponAmmoSizes(_dynamic_traits: boolean, _pvp: boolean): AmmoResponse;
+  getWeaponTtk(_overshield: number): Array<ResillienceTtkSummary>;
+  getWeaponPveTtk(): PveTtkResponse;
+  getWeaponPvpTtk(_target: PvpTarget): PvpTtkResponse;
+  getWeaponMirrorTtk(): PvpTtkResponse;
+  getWeaponTtkAtDistance(_overshield: number, _engagement: Engagement): Array<ResillienceTtkSummary>;
+  getWeaponPvpTtkAtDistance(_target: PvpTarget, _engagement: Engagement): PvpTtkResponse;
+  getWeaponTtkCurve(_target: PvpTarget, _ads: boolean): TtkCurveResponse;
+  getWeaponDps(_use_rpl: boolean): DpsResponse;
+  getWeaponDpsAtDistance(_engagement: Engagement, _use_rpl: boolean): DpsResponse;
+  getWeaponDpsForDuration(_seconds: number, _starting_mag_state: StartingMagState | undefined, _use_rpl: boolean): DurationDpsResponse;
+  getWeaponAddClear(_wave: AddClearWave, _use_rpl: boolean): AddClearResponse;
+  getWeaponAccuracyDps(_model: AccuracyModel, _settings: MonteCarloSettings | undefined, _use_rpl: boolean): AccuracyDpsResponse;
+  getWeaponAccuracyTtk(_target: PvpTarget, _model: AccuracyModel, _settings: MonteCarloSettings | undefined): AccuracyTtkResponse;
+  getWeaponFiringData(_dynamic_traits: boolean, _pvp: boolean): FiringResponse;
+  getWeaponFlinch(_dynamic_traits: boolean, _pvp: boolean, _resilience: number): number;
+  getMiscData(_dynamic_traits: boolean, _pvp: boolean): Map<string, number>;
+  getModifierResponseSummary(_dynamic_traits: boolean, _pvp: boolean): any;
+  setAbility(_hash: number): void;
+  getAbilityDamage(_pvp: boolean): AbilityDamageResponse;
+  getAbilityDps(_pvp: boolean): AbilityDpsResponse;
+  setEncounter(_rpl: number, _player_pl: number, _override_cap: number, _difficulty: DifficultyOptions, _enemy_type: EnemyType, _custom_deltas?: Float64Array, _custom_values?: Float64Array): void;
+  setPlayerState(_state: PlayerState): void;
+  getPlayerState(): PlayerState;
+  getPlDeltaCurve(): Array<[number, number]>;
+  setEnemyCatalogue(_catalogue_json: string): void;
+  setEnemyArchetype(_id: number): void;
+  setLoggingLevel(_level: number): void;
+}
+/**
 */
 export class AmmoResponse {
   /**
@@ -50,6 +50,43 @@ class WeaponInterface:
         def over_time_span(self, _start: float, _end: float) -> tuple[float, float]: ...
         def __repr__(self) -> str: ...
 
+    class DurationDpsResponse:
+        @property
+        def time_damage_data(self) -> list[tuple[float, float]]: ...
+        @property
+        def total_damage(self) -> float: ...
+        @property
+        def total_shots(self) -> int: ...
+        @property
+        def duration(self) -> float: ...
+        @property
+        def dps(self) -> float: ...
+        @property
+        def mag_left(self) -> int: ...
+        @property
+        def reserves_left(self) -> int: ...
+        def __repr__(self) -> str: ...
+
+    class AddClearResponse:
+        @property
+        def dps(self) -> WeaponInterface.DpsResponse: ...
+        @property
+        def kill_times(self) -> list[float]: ...
+        @property
+        def time_to_clear(self) -> float | None: ...
+        def __repr__(self) -> str: ...
+
+    class PatchDiff:
+        @property
+        def weapon_hash(self) -> int: ...
+        @property
+        def old_patch(self) -> str: ...
+        @property
+        def new_patch(self) -> str: ...
+        @property
+        def changes(self) -> list[tuple[str, str, float, float]]: ...
+        def __repr__(self) -> str: ...
+
     class FiringResponse:
         @property
         def pvp_impact_damage(self) -> float: ...
@@ -80,8 +117,6 @@ class WeaponInterface:
         def bodyshots(self) -> int: ...
         @property
         def time_taken(self) -> float: ...
-        @property
-        def achievable_range(self) -> float: ...
         def __repr__(self) -> str: ...
 
     class BodyKillData:
@@ -100,6 +135,166 @@ class WeaponInterface:
         def optimal_ttk(self) -> WeaponInterface.OptimalKillData: ...
         def __repr__(self) -> str: ...
 
+    class PveKillData:
+        @property
+        def precision_ratio(self) -> float: ...
+        @property
+        def precision_shots(self) -> int: ...
+        @property
+        def body_shots(self) -> int: ...
+        @property
+        def ammo_used(self) -> int: ...
+        @property
+        def reloads(self) -> int: ...
+        @property
+        def time_taken(self) -> float: ...
+        @property
+        def killed(self) -> bool: ...
+        @property
+        def phase_times(self) -> list[float | None]: ...
+        @property
+        def stuns(self) -> int: ...
+        def __repr__(self) -> str: ...
+
+    class PveTtkResponse:
+        @property
+        def enemy(self) -> str: ...
+        @property
+        def health(self) -> float: ...
+        @property
+        def adjusted_health(self) -> float: ...
+        @property
+        def kills(self) -> list[WeaponInterface.PveKillData]: ...
+        @property
+        def melee(self) -> WeaponInterface.PveKillData | None: ...
+        def __repr__(self) -> str: ...
+
+    class OvershieldType(Enum):
+        NONE = 0,
+        PERSISTENT = 1,
+        DECAYING = 2,
+
+    class PvpHealing(Enum):
+        NONE = 0,
+        RESTORATION = 1,
+        AMPLIFIED = 2,
+        DEVOUR = 3,
+
+    class PvpTarget:
+        def __init__(self, _resilience: int, _overshield_type: WeaponInterface.OvershieldType | None = None, _overshield: float | None = None, _healing: WeaponInterface.PvpHealing | None = None, _recovery: int | None = None): ...
+        @staticmethod
+        def default() -> WeaponInterface.PvpTarget: ...
+        @property
+        def resilience(self) -> int: ...
+        @property
+        def overshield_type(self) -> WeaponInterface.OvershieldType: ...
+        @property
+        def overshield(self) -> float: ...
+        @property
+        def overshield_decay(self) -> float: ...
+        @property
+        def healing(self) -> WeaponInterface.PvpHealing: ...
+        @property
+        def recovery(self) -> int: ...
+        @property
+        def recovery_delay(self) -> float: ...
+        @property
+        def frost_armor(self) -> int: ...
+        @property
+        def woven_mail(self) -> bool: ...
+        @property
+        def concussive_dampener(self) -> bool: ...
+        def __repr__(self) -> str: ...
+
+    class PvpShotMix:
+        @property
+        def headshots(self) -> int: ...
+        @property
+        def bodyshots(self) -> int: ...
+        @property
+        def time_taken(self) -> float: ...
+        @property
+        def reloads(self) -> int: ...
+        @property
+        def killed(self) -> bool: ...
+        @property
+        def regen_reset(self) -> bool: ...
+        def __repr__(self) -> str: ...
+
+    class PvpTtkResponse:
+        @property
+        def health(self) -> float: ...
+        @property
+        def mixes(self) -> list[WeaponInterface.PvpShotMix]: ...
+        @property
+        def melee(self) -> WeaponInterface.PvpShotMix | None: ...
+        def __repr__(self) -> str: ...
+
+    class TtkDistancePoint:
+        @property
+        def distance(self) -> float: ...
+        @property
+        def optimal_shots(self) -> int: ...
+        @property
+        def optimal_ttk(self) -> float | None: ...
+        @property
+        def body_shots(self) -> int: ...
+        @property
+        def body_ttk(self) -> float | None: ...
+        def __repr__(self) -> str: ...
+
+    class TtkCurveResponse:
+        @property
+        def points(self) -> list[WeaponInterface.TtkDistancePoint]: ...
+        @property
+        def breakpoints(self) -> list[float]: ...
+        def __repr__(self) -> str: ...
+
+    class AccuracyModel:
+        def __init__(self, _hit_rate: float, _precision_ratio: float, _spread: float | None = None, _distance: float | None = None, _range_penalty: float | None = None): ...
+        @property
+        def hit_rate(self) -> float: ...
+        @property
+        def precision_ratio(self) -> float: ...
+        @property
+        def spread(self) -> float: ...
+        @property
+        def distance(self) -> float: ...
+        @property
+        def range_penalty(self) -> float: ...
+        def __repr__(self) -> str: ...
+
+    class Percentiles:
+        @property
+        def mean(self) -> float: ...
+        @property
+        def p10(self) -> float: ...
+        @property
+        def p50(self) -> float: ...
+        @property
+        def p90(self) -> float: ...
+        def __repr__(self) -> str: ...
+
+    class AccuracyDpsResponse:
+        @property
+        def perfect_dps(self) -> float: ...
+        @property
+        def dps(self) -> WeaponInterface.Percentiles: ...
+        @property
+        def hit_chance(self) -> float: ...
+        def __repr__(self) -> str: ...
+
+    class AccuracyTtkResponse:
+        @property
+        def perfect_ttk(self) -> float: ...
+        @property
+        def ttk(self) -> WeaponInterface.Percentiles: ...
+        @property
+        def kill_rate(self) -> float: ...
+        @property
+        def hit_chance(self) -> float: ...
+        def __repr__(self) -> str: ...
+
     @staticmethod
     def set_weapon(_hash: int, _weapon_type_id: int, _intrinsic_hash: int, _ammo_type_id: int, _damage_type_id: int) -> None: ...
     @staticmethod
@@ -117,19 +312,56 @@ class WeaponInterface:
     @staticmethod
     def get_dps(_do_rpl_mult: bool) -> WeaponInterface.DpsResponse: ...
     @staticmethod
+    def get_dps_at_distance(_distance: float, _ads: bool, _do_rpl_mult: bool) -> WeaponInterface.DpsResponse: ...
+    @staticmethod
+    def get_dps_for_duration(_seconds: float, _do_rpl_mult: bool, _mag: int | None = None, _reserves: int | None = None, _perk_values: dict[int, int] | None = None, _kill_interval: float | None = None) -> WeaponInterface.DurationDpsResponse: ...
+    @staticmethod
+    def get_add_clear(_count: int, _retarget_time: float, _do_rpl_mult: bool) -> WeaponInterface.AddClearResponse: ...
+    @staticmethod
     def get_ttk(_overshield: float) -> list[ResillienceSummary]: ...
     @staticmethod
+    def get_pve_ttk() -> WeaponInterface.PveTtkResponse: ...
+    @staticmethod
+    def get_pvp_ttk(_target: WeaponInterface.PvpTarget) -> WeaponInterface.PvpTtkResponse: ...
+    @staticmethod
+    def get_mirror_ttk() -> WeaponInterface.PvpTtkResponse: ...
+    @staticmethod
+    def get_ttk_at_distance(_overshield: float, _distance: float, _ads: bool) -> list[ResillienceSummary]: ...
+    @staticmethod
+    def get_pvp_ttk_at_distance(_target: WeaponInterface.PvpTarget, _distance: float, _ads: bool) -> WeaponInterface.PvpTtkResponse: ...
+    @staticmethod
+    def get_ttk_curve(_target: WeaponInterface.PvpTarget, _ads: bool) -> WeaponInterface.TtkCurveResponse: ...
+    @staticmethod
+    def get_accuracy_dps(_model: WeaponInterface.AccuracyModel, _do_rpl_mult: bool, _trials: int | None = None, _seed: int | None = None) -> WeaponInterface.AccuracyDpsResponse: ...
+    @staticmethod
+    def get_accuracy_ttk(_target: WeaponInterface.PvpTarget, _model: WeaponInterface.AccuracyModel, _trials: int | None = None, _seed: int | None = None) -> WeaponInterface.AccuracyTtkResponse: ...
+    @staticmethod
     def set_stats(_stats: dict[int, int]) -> None: ...
     @staticmethod
     def get_firing_data(_use_traits: bool, _use_rpl: bool) -> WeaponInterface.FiringResponse: ...
     @staticmethod
     def reverse_pve_calc(_damage: float, _combatant_mult = 1.0, _pve_mult = 1.0) -> float: ...
+    @staticmethod
+    def set_formula_database(_formula_json: str) -> None: ...
+    @staticmethod
+    def set_formula_database_bytes(_bytes: bytes) -> None: ...
+    @staticmethod
+    def reset_formula_database() -> None: ...
+    @staticmethod
+    def add_formula_patch(_id: str, _formula_json: str) -> None: ...
+    @staticmethod
+    def get_formula_patches() -> list[str]: ...
+    @staticmethod
+    def use_formula_patch(_id: str) -> None: ...
+    @staticmethod
+    def diff_formula_patches(_old_patch: str, _new_patch: str, _pvp: bool) -> WeaponInterface.PatchDiff: ...
 
 class ActivityInterface:
     class DifficultyOptions(Enum):
         NORMAL = 1,
         RAID = 2,
         MASTER = 3,
+        CUSTOM = 4,
 
     class PlayerClass(Enum):
         UNKNOW = 0,
@@ -138,12 +370,35 @@ class ActivityInterface:
         Warlock = 3,
 
     class Activity:
-        def __init__(self, _name: str, _difficulty: ActivityInterface.DifficultyOptions, _rpl: int, _cap: int): ...
+        def __init__(self, _name: str, _difficulty: ActivityInterface.DifficultyOptions, _rpl: int, _cap: int, _custom_table: list[tuple[float, float]] | None = None): ...
         def __new__(cls: type[Self]) -> Self: ...
         def __repr__(self) -> str: ...
 
+    class PlayerBuff(Enum):
+        Radiant = 0,
+        WellOfRadiance = 1,
+        EmpoweringRift = 2,
+        WardOfDawn = 3,
+        BannerShield = 4,
+        NobleRounds = 5,
+        Weaken = 6,
+
     class Player:
         def __init__(self, _power_level: int, _class: ActivityInterface.PlayerClass): ...
+        @property
+        def subclass(self) -> int: ...
+        @property
+        def aspects(self) -> list[int]: ...
+        @property
+        def fragments(self) -> list[int]: ...
+        @property
+        def resilience(self) -> int: ...
+        @property
+        def mobility(self) -> int: ...
+        @property
+        def recovery(self) -> int: ...
+        @property
+        def buffs(self) -> list[ActivityInterface.PlayerBuff]: ...
         def __new__(cls: type[Self]) -> Self: ...
         def __repr__(self) -> str: ...
 
@@ -155,6 +410,8 @@ class ActivityInterface:
     def get_activity() -> ActivityInterface.Activity: ...
     @staticmethod
     def get_player() -> ActivityInterface.Player: ...
+    @staticmethod
+    def get_pl_delta_curve() -> list[tuple[int, float]]: ...
 
 class EnemyInterface:
     class EnemyType(Enum):
@@ -178,3 +435,79 @@ class EnemyInterface:
     def set_enemy(_enemy: Enemy) -> None: ...
     @staticmethod
     def set_enemy_type(_type: EnemyType) -> None: ...
+    @staticmethod
+    def set_enemy_catalogue(_catalogue_json: str) -> None: ...
+    @staticmethod
+    def set_enemy_archetype(_id: int) -> None: ...
+
+class AbilityInterface:
+    class AbilityDamageResponse:
+        impact: float
+        secondary: float
+        total: float
+        hits: int
+        damage_mult: float
+        def __repr__(self) -> str: ...
+
+    class AbilityDpsResponse:
+        total_damage: float
+        total_time: float
+        dps: float
+        def __repr__(self) -> str: ...
+
+    @staticmethod
+    def set_ability(_hash: int) -> None: ...
+    @staticmethod
+    def get_ability_damage(_pvp: bool) -> AbilityInterface.AbilityDamageResponse: ...
+    @staticmethod
+    def get_ability_dps(_pvp: bool) -> AbilityInterface.AbilityDpsResponse: ...
+
+class Calculator:
+    """Independent calculation session, does not share state with the interfaces above"""
+    def __init__(self): ...
+    def set_weapon(self, _hash: int, _weapon_type_id: int, _intrinsic_hash: int, _ammo_type_id: int, _damage_type_id: int) -> None: ...
+    def get_hash(self) -> int: ...
+    def stringify_weapon(self) -> str: ...
+    def export_build(self) -> str: ...
+    def import_build(self, _build_json: str) -> None: ...
+    def set_formula_database(self, _formula_json: str) -> None: ...
+    def set_formula_database_bytes(self, _bytes: bytes) -> None: ...
+    def reset_formula_database(self) -> None: ...
+    def add_formula_patch(self, _id: str, _formula_json: str) -> None: ...
+    def get_formula_patches(self) -> list[str]: ...
+    def use_formula_patch(self, _id: str) -> None: ...
+    def diff_formula_patches(self, _old_patch: str, _new_patch: str, _pvp: bool) -> WeaponInterface.PatchDiff: ...
+    def get_range_falloff(self, _use_traits: bool, _pvp: bool) -> WeaponInterface.RangeResponse: ...
+    def get_handling_times(self, _use_traits: bool, _pvp: bool) -> WeaponInterface.HandlingResponse: ...
+    def add_trait(self, _trait: WeaponInterface.Trait) -> None: ...
+    def remove_trait(self, _trait: int) -> None: ...
+    def get_dps(self, _do_rpl_mult: bool) -> WeaponInterface.DpsResponse: ...
+    def get_dps_at_distance(self, _distance: float, _ads: bool, _do_rpl_mult: bool) -> WeaponInterface.DpsResponse: ...
+    def get_dps_for_duration(self, _seconds: float, _do_rpl_mult: bool, _mag: int | None = None, _reserves: int | None = None, _perk_values: dict[int, int] | None = None, _kill_interval: float | None = None) -> WeaponInterface.DurationDpsResponse: ...
+    def get_add_clear(self, _count: int, _retarget_time: float, _do_rpl_mult: bool) -> WeaponInterface.AddClearResponse: ...
+    def get_ttk(self, _overshield: float) -> list[WeaponInterface.ResillienceSummary]: ...
+    def get_pve_ttk(self) -> WeaponInterface.PveTtkResponse: ...
+    def get_pvp_ttk(self, _target: WeaponInterface.PvpTarget) -> WeaponInterface.PvpTtkResponse: ...
+    def get_mirror_ttk(self) -> WeaponInterface.PvpTtkResponse: ...
+    def get_ttk_at_distance(self, _overshield: float, _distance: float, _ads: bool) -> list[WeaponInterface.ResillienceSummary]: ...
+    def get_pvp_ttk_at_distance(self, _target: WeaponInterface.PvpTarget, _distance: float, _ads: bool) -> WeaponInterface.PvpTtkResponse: ...
+    def get_ttk_curve(self, _target: WeaponInterface.PvpTarget, _ads: bool) -> WeaponInterface.TtkCurveResponse: ...
+    def get_accuracy_dps(self, _model: WeaponInterface.AccuracyModel, _do_rpl_mult: bool, _trials: int | None = None, _seed: int | None = None) -> WeaponInterface.AccuracyDpsResponse: ...
+    def get_accuracy_ttk(self, _target: WeaponInterface.PvpTarget, _model: WeaponInterface.AccuracyModel, _trials: int | None = None, _seed: int | None = None) -> WeaponInterface.AccuracyTtkResponse: ...
+    def set_stats(self, _stats: dict[int, int]) -> None: ...
+    def get_firing_data(self, _use_traits: bool, _pvp: bool) -> WeaponInterface.FiringResponse: ...
+    def reverse_pve_calc(self, _damage: float, _combatant_mult = 1.0, _pve_mult = 1.0) -> float: ...
+    def set_activity(self, _activity: ActivityInterface.Activity) -> None: ...
+    def get_activity(self) -> ActivityInterface.Activity: ...
+    def set_player(self, _player: ActivityInterface.Player) -> None: ...
+    def get_player(self) -> ActivityInterface.Player: ...
+    def get_pl_delta_curve(self) -> list[tuple[int, float]]: ...
+    def set_enemy(self, _enemy: EnemyInterface.Enemy) -> None: ...
+    def get_enemy(self) -> EnemyInterface.Enemy: ...
+    def set_enemy_type(self, _type: EnemyInterface.EnemyType) -> None: ...
+    def set_enemy_catalogue(self, _catalogue_json: str) -> None: ...
+    def set_enemy_archetype(self, _id: int) -> None: ...
+    def set_ability(self, _hash: int) -> None: ...
+    def get_ability_damage(self, _pvp: bool) -> AbilityInterface.AbilityDamageResponse: ...
+    def get_ability_dps(self, _pvp: bool) -> AbilityInterface.AbilityDpsResponse: ...
+    def set_logging_level(self, _level: int) -> None: ...
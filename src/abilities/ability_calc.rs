@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{
+    activity::{Activity, PlayerState},
+    d2_enums::{AmmoType, Seconds, WeaponType},
+    enemies::Enemy,
+    perks::{
+        buff_perks::{GLOBAL_BUFFS, SURGE_BUFFS},
+        get_dmg_modifier,
+        lib::CalculationInput,
+        simulation_state::SimulationState,
+        Perk, Perks,
+    },
+    types::rs_types::FiringData,
+};
+
+use super::Ability;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct AbilityDamageResponse {
+    pub impact: f64,
+    ///Sum of every secondary hit
+    pub secondary: f64,
+    pub total: f64,
+    pub hits: u32,
+    ///Combined buff, debuff and activity multiplier already applied to the damage
+    pub damage_mult: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct AbilityDpsResponse {
+    pub total_damage: f64,
+    pub total_time: Seconds,
+    pub dps: f64,
+}
+
+///Multiplier from the buffs and debuffs in `_perks` that also apply to abilities,
+///empowering, surge and debuff stacking is handled the same way as for weapons
+pub fn calc_ability_buff(
+    _ability: &Ability,
+    _perks: &[Perk],
+    _enemy: &Enemy,
+    _player: &PlayerState,
+    _pvp: bool,
+) -> f64 {
+    let surges_apply = _player.subclass_is(_ability.damage_type);
+    let global_perks: Vec<Perk> = _perks
+        .iter()
+        .filter(|perk| {
+            let perk_enum: Perks = perk.hash.into();
+            GLOBAL_BUFFS.contains(&perk_enum) || (surges_apply && SURGE_BUFFS.contains(&perk_enum))
+        })
+        .cloned()
+        .collect();
+    let firing_data = FiringData::default();
+    let stats = HashMap::new();
+    let perk_value_map = HashMap::new();
+    let mut calc_input = CalculationInput::construct_static(
+        0,
+        &firing_data,
+        &stats,
+        &perk_value_map,
+        &WeaponType::UNKNOWN,
+        &AmmoType::UNKNOWN,
+        &_ability.damage_type,
+        1.0,
+        _player,
+    );
+    calc_input.enemy_type = &_enemy.type_;
+    let mut sim_state = SimulationState::default();
+    get_dmg_modifier(global_perks, &calc_input, _pvp, &mut sim_state).impact_dmg_scale
+}
+
+///Damage of a single cast, pve damage is scaled by the activity power level
+pub fn calc_ability_damage(
+    _ability: &Ability,
+    _perks: &[Perk],
+    _enemy: &Enemy,
+    _activity: &Activity,
+    _pvp: bool,
+) -> AbilityDamageResponse {
+    let profile = &_ability.damage_profile;
+    let mut damage_mult =
+        calc_ability_buff(_ability, _perks, _enemy, &_activity.player.state, _pvp);
+    if !_pvp {
+        damage_mult *= _activity.get_pl_delta() * _activity.get_rpl_mult();
+    }
+
+    let impact = profile.impact * profile.crit_mult.max(1.0) * damage_mult;
+    let secondary = (0..profile.sec_hit_count)
+        .map(|hit| profile.secondary * (1.0 + profile.lin_hit_scalar * hit as f64))
+        .sum::<f64>()
+        * damage_mult;
+    let hits = profile.sec_hit_count + if profile.impact > 0.0 { 1 } else { 0 };
+
+    AbilityDamageResponse {
+        impact,
+        secondary,
+        total: impact + secondary,
+        hits,
+        damage_mult,
+    }
+}
+
+///Damage of a single cast spread over the time it takes every hit to land
+pub fn calc_ability_dps(
+    _ability: &Ability,
+    _perks: &[Perk],
+    _enemy: &Enemy,
+    _activity: &Activity,
+    _pvp: bool,
+) -> AbilityDpsResponse {
+    let damage = calc_ability_damage(_ability, _perks, _enemy, _activity, _pvp);
+    let total_time = _ability.damage_profile.duration;
+    AbilityDpsResponse {
+        total_damage: damage.total,
+        total_time,
+        //instant abilities are treated as landing within a single frame
+        dps: damage.total / total_time.max(1.0 / 30.0),
+    }
+}
@@ -0,0 +1,170 @@
+use crate::d2_enums::{DamageType, Seconds};
+
+use super::{Ability, AbilityDamageProfile, AbilityType};
+
+//ids are internal until abilities are pulled from the manifest,
+//grenades are 100+, melees 200+, class abilities 300+ and supers 400+
+struct AbilityData {
+    hash: u32,
+    name: &'static str,
+    ability_type: AbilityType,
+    damage_type: DamageType,
+    profile: AbilityDamageProfile,
+}
+
+const fn profile(
+    _impact: f64,
+    _secondary: f64,
+    _sec_hit_count: u32,
+    _lin_hit_scalar: f64,
+    _duration: Seconds,
+) -> AbilityDamageProfile {
+    AbilityDamageProfile {
+        impact: _impact,
+        secondary: _secondary,
+        sec_hit_count: _sec_hit_count,
+        lin_hit_scalar: _lin_hit_scalar,
+        crit_mult: 1.0,
+        duration: _duration,
+    }
+}
+
+//pve damage against a same power combatant
+const ABILITIES: [AbilityData; 16] = [
+    AbilityData {
+        hash: 100,
+        name: "Vortex Grenade",
+        ability_type: AbilityType::GRENADE,
+        damage_type: DamageType::VOID,
+        profile: profile(240.0, 95.0, 12, 0.0, 3.6),
+    },
+    AbilityData {
+        hash: 101,
+        name: "Pulse Grenade",
+        ability_type: AbilityType::GRENADE,
+        damage_type: DamageType::ARC,
+        profile: profile(120.0, 160.0, 6, 0.0, 3.0),
+    },
+    AbilityData {
+        hash: 102,
+        name: "Incendiary Grenade",
+        ability_type: AbilityType::GRENADE,
+        damage_type: DamageType::SOLAR,
+        profile: profile(640.0, 0.0, 0, 0.0, 0.0),
+    },
+    AbilityData {
+        hash: 103,
+        name: "Magnetic Grenade",
+        ability_type: AbilityType::GRENADE,
+        damage_type: DamageType::VOID,
+        profile: profile(300.0, 420.0, 1, 0.0, 1.0),
+    },
+    AbilityData {
+        hash: 200,
+        name: "Ballistic Slam",
+        ability_type: AbilityType::MELEE,
+        damage_type: DamageType::ARC,
+        profile: profile(850.0, 0.0, 0, 0.0, 0.0),
+    },
+    AbilityData {
+        hash: 201,
+        name: "Knife Trick",
+        ability_type: AbilityType::MELEE,
+        damage_type: DamageType::SOLAR,
+        profile: profile(0.0, 230.0, 3, 0.0, 0.3),
+    },
+    AbilityData {
+        hash: 202,
+        name: "Incinerator Snap",
+        ability_type: AbilityType::MELEE,
+        damage_type: DamageType::SOLAR,
+        profile: profile(420.0, 0.0, 0, 0.0, 0.0),
+    },
+    AbilityData {
+        hash: 300,
+        name: "Barricade",
+        ability_type: AbilityType::CLASS,
+        damage_type: DamageType::UNKNOWN,
+        profile: profile(0.0, 0.0, 0, 0.0, 0.0),
+    },
+    AbilityData {
+        hash: 301,
+        name: "Empowering Rift",
+        ability_type: AbilityType::CLASS,
+        damage_type: DamageType::UNKNOWN,
+        profile: profile(0.0, 0.0, 0, 0.0, 0.0),
+    },
+    AbilityData {
+        hash: 400,
+        name: "Thundercrash",
+        ability_type: AbilityType::SUPER,
+        damage_type: DamageType::ARC,
+        profile: profile(40000.0, 0.0, 0, 0.0, 2.5),
+    },
+    AbilityData {
+        hash: 401,
+        name: "Golden Gun: Marksman",
+        ability_type: AbilityType::SUPER,
+        damage_type: DamageType::SOLAR,
+        profile: profile(0.0, 11000.0, 6, 0.0, 6.5),
+    },
+    AbilityData {
+        hash: 402,
+        name: "Blade Barrage",
+        ability_type: AbilityType::SUPER,
+        damage_type: DamageType::SOLAR,
+        profile: profile(0.0, 2750.0, 18, 0.0, 2.2),
+    },
+    AbilityData {
+        hash: 403,
+        name: "Nova Bomb: Cataclysm",
+        ability_type: AbilityType::SUPER,
+        damage_type: DamageType::VOID,
+        profile: profile(35000.0, 0.0, 0, 0.0, 2.0),
+    },
+    AbilityData {
+        hash: 404,
+        name: "Chaos Reach",
+        ability_type: AbilityType::SUPER,
+        damage_type: DamageType::ARC,
+        profile: profile(0.0, 850.0, 44, 0.01, 6.0),
+    },
+    AbilityData {
+        hash: 405,
+        name: "Silence and Squall",
+        ability_type: AbilityType::SUPER,
+        damage_type: DamageType::STASIS,
+        profile: profile(9500.0, 1000.0, 15, 0.0, 12.0),
+    },
+    AbilityData {
+        hash: 406,
+        name: "Needlestorm",
+        ability_type: AbilityType::SUPER,
+        damage_type: DamageType::STRAND,
+        profile: profile(0.0, 11500.0, 3, 0.0, 4.0),
+    },
+];
+
+impl From<&AbilityData> for Ability {
+    fn from(_data: &AbilityData) -> Self {
+        Ability {
+            name: _data.name.to_string(),
+            hash: _data.hash,
+            ability_type: _data.ability_type,
+            damage_type: _data.damage_type,
+            damage_profile: _data.profile,
+            is_initialized: true,
+        }
+    }
+}
+
+pub fn get_ability(_hash: u32) -> Option<Ability> {
+    ABILITIES
+        .iter()
+        .find(|data| data.hash == _hash)
+        .map(Ability::from)
+}
+
+pub fn list_abilities() -> Vec<Ability> {
+    ABILITIES.iter().map(Ability::from).collect()
+}
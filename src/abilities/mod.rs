@@ -1,4 +1,11 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+use serde::Serialize;
+
+use crate::d2_enums::{DamageType, Seconds};
+
+pub mod ability_calc;
+pub mod ability_data;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 pub enum AbilityType {
     GRENADE,
     MELEE,
@@ -17,20 +24,24 @@ impl Default for AbilityType {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
 pub struct AbilityDamageProfile {
-    impact: f64,
-    secondary: f64,
-    sec_hit_count: u32,
-    lin_hit_scalar: f64,
-    crit_mult: f64, // if 1.0, no crit
+    pub impact: f64,
+    pub secondary: f64,
+    pub sec_hit_count: u32,
+    ///Each secondary hit deals this much more than the last, 0.0 for flat ticks
+    pub lin_hit_scalar: f64,
+    pub crit_mult: f64, // if 1.0, no crit
+    ///Time between the cast and the last hit landing
+    pub duration: Seconds,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct Ability {
     pub name: String,
     pub hash: u32,
     pub ability_type: AbilityType,
+    pub damage_type: DamageType,
     pub damage_profile: AbilityDamageProfile,
     pub is_initialized: bool,
 }
@@ -13,40 +13,53 @@ struct LinearTable {
     table: Vec<TableKey>,
 }
 impl LinearTable {
+    ///Interpolates between the keys either side of `time`, holding the end values past
+    ///either end so curves with positive keys keep scaling up to their last key
     fn evaluate(&self, time: f64) -> f64 {
-        if time > 0.0 {
-            return self.table[self.table.len() - 1].value;
+        let first = self.table[0];
+        let last = self.table[self.table.len() - 1];
+        if time <= first.time {
+            return first.value;
         }
-        let mut index = 0;
-        for i in 0..self.table.len() {
-            if self.table[i].time > time {
-                index = i;
-                break;
-            }
-        }
-        if index == 0 {
-            return self.table[0].value;
-        }
-        if index == self.table.len() {
-            return self.table[self.table.len() - 1].value;
+        if time >= last.time {
+            return last.value;
         }
+        //the table is sorted so the key before the first one past `time` is at or below it
+        let index = self
+            .table
+            .iter()
+            .position(|key| key.time > time)
+            .unwrap_or(self.table.len() - 1);
         let a = self.table[index - 1];
         let b = self.table[index];
         let t = (time - a.time) / (b.time - a.time);
-        return a.value + (b.value - a.value) * t;
+        a.value + (b.value - a.value) * t
+    }
+    fn from_vecs(_times: &[f64], _values: &[f64]) -> LinearTable {
+        let pairs: Vec<(f64, f64)> = _times
+            .iter()
+            .copied()
+            .zip(_values.iter().copied())
+            .collect();
+        LinearTable::from_pairs(&pairs)
     }
-    fn from_vecs(_times: [f64; 11], _values: [f64; 11]) -> LinearTable {
-        let mut table = Vec::new();
-        let times = _times.clone().to_vec();
-        let values = _values.clone().to_vec();
-        for i in 0..times.len() {
+    fn from_pairs(_pairs: &[(f64, f64)]) -> LinearTable {
+        let mut table: Vec<TableKey> = _pairs
+            .iter()
+            .map(|(time, value)| TableKey {
+                time: *time,
+                value: *value,
+            })
+            .collect();
+        //an empty table would panic on evaluate, treat it as no scaling
+        if table.is_empty() {
             table.push(TableKey {
-                time: times[i],
-                value: values[i],
+                time: 0.0,
+                value: 1.0,
             });
         }
-        table.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap());
-        return LinearTable { table };
+        table.sort_by(|a, b| a.time.total_cmp(&b.time));
+        LinearTable { table }
     }
 }
 
@@ -80,11 +93,14 @@ const RAID_TIMES: [f64; 11] = [
 
 const WEAPON_DELTA_EXPONENT: f64 = 1.006736;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DifficultyOptions {
-    NORMAL = 1,
-    RAID = 2,
-    MASTER = 3,
+    NORMAL,
+    RAID,
+    MASTER,
+    ///Pairs of power delta and damage multiplier, followed by the delta cap,
+    ///for activities without a built in curve such as grandmasters or contest mode
+    Custom(Vec<(f64, f64)>, i32),
 }
 impl Default for DifficultyOptions {
     fn default() -> Self {
@@ -92,22 +108,49 @@ impl Default for DifficultyOptions {
     }
 }
 impl DifficultyOptions {
+    ///Builds a custom difficulty from matching lists of power deltas and damage multipliers
+    pub fn custom(_deltas: &[f64], _values: &[f64], _cap: i32) -> Result<Self, String> {
+        if _deltas.is_empty() || _deltas.len() != _values.len() {
+            return Err(format!(
+                "Custom difficulty needs the same number of deltas and values, got {} and {}",
+                _deltas.len(),
+                _values.len()
+            ));
+        }
+        if _deltas.iter().chain(_values.iter()).any(|x| !x.is_finite()) {
+            return Err("Custom difficulty deltas and values must be finite".to_string());
+        }
+        Ok(DifficultyOptions::Custom(
+            _deltas
+                .iter()
+                .copied()
+                .zip(_values.iter().copied())
+                .collect(),
+            _cap,
+        ))
+    }
+
     pub fn get_difficulty_data(&self) -> DifficultyData {
         match self {
             DifficultyOptions::NORMAL => DifficultyData {
                 name: "Normal".to_string(),
                 cap: 50,
-                table: LinearTable::from_vecs(NORMAL_TIMES, NORMAL_VALUES),
+                table: LinearTable::from_vecs(&NORMAL_TIMES, &NORMAL_VALUES),
             },
             DifficultyOptions::MASTER => DifficultyData {
                 name: "Master".to_string(),
                 cap: 20,
-                table: LinearTable::from_vecs(MASTER_TIMES, MASTER_VALUES),
+                table: LinearTable::from_vecs(&MASTER_TIMES, &MASTER_VALUES),
             },
             DifficultyOptions::RAID => DifficultyData {
                 name: "Raid & Dungeon".to_string(),
                 cap: 20,
-                table: LinearTable::from_vecs(RAID_TIMES, RAID_VALUES),
+                table: LinearTable::from_vecs(&RAID_TIMES, &RAID_VALUES),
+            },
+            DifficultyOptions::Custom(pairs, cap) => DifficultyData {
+                name: "Custom".to_string(),
+                cap: *cap,
+                table: LinearTable::from_pairs(pairs),
             },
         }
     }
@@ -127,26 +170,39 @@ pub(super) fn rpl_mult(_rpl: f64) -> f64 {
     return (1.0 + ((1.0 / 30.0) * _rpl)) / (1.0 + 1.0 / 3.0);
 }
 
-pub(super) fn gpl_delta(_activity: &Activity) -> f64 {
-    let difficulty_data = _activity.difficulty.get_difficulty_data();
-    let curve = difficulty_data.table;
-    let rpl = _activity.rpl;
-    let cap = if _activity.cap < difficulty_data.cap {
+fn difficulty_cap(_activity: &Activity, _difficulty_data: &DifficultyData) -> i32 {
+    if _activity.cap < _difficulty_data.cap {
         _activity.cap
     } else {
-        difficulty_data.cap
-    };
-    let mut delta = _activity.player.pl as i32 - rpl as i32;
-    if delta < -99 {
+        _difficulty_data.cap
+    }
+}
+
+fn delta_mult(_curve: &LinearTable, _delta: i32) -> f64 {
+    if _delta < -99 {
         return 0.0;
-    } else if delta > cap {
-        delta = cap;
     }
-    let wep_delta_mult = WEAPON_DELTA_EXPONENT.powi(delta);
-    let gear_delta_mult = curve.evaluate(delta as f64);
+    let wep_delta_mult = WEAPON_DELTA_EXPONENT.powi(_delta);
+    let gear_delta_mult = _curve.evaluate(_delta as f64);
     wep_delta_mult * gear_delta_mult
 }
 
+pub(super) fn gpl_delta(_activity: &Activity) -> f64 {
+    let difficulty_data = _activity.difficulty.get_difficulty_data();
+    let cap = difficulty_cap(_activity, &difficulty_data);
+    let delta = _activity.player.pl as i32 - _activity.rpl as i32;
+    delta_mult(&difficulty_data.table, delta.min(cap))
+}
+
+///Power delta multiplier for every delta from -99 up to the effective cap
+pub(super) fn gpl_delta_curve(_activity: &Activity) -> Vec<(i32, f64)> {
+    let difficulty_data = _activity.difficulty.get_difficulty_data();
+    let cap = difficulty_cap(_activity, &difficulty_data);
+    (-99..=cap.max(-99))
+        .map(|delta| (delta, delta_mult(&difficulty_data.table, delta)))
+        .collect()
+}
+
 // add_remove_pve_bonuses(
 //     _rpl: f64,
 //     _pl: u32,
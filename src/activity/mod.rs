@@ -1,8 +1,17 @@
-use self::damage_calc::{gpl_delta, rpl_mult, DifficultyOptions};
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    d2_enums::DamageType,
+    perks::{Perk, Perks},
+};
+
+use self::damage_calc::{gpl_delta, gpl_delta_curve, rpl_mult, DifficultyOptions};
 
 pub mod damage_calc;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PlayerClass {
     Unknown = 0,
     Titan = 1,
@@ -15,13 +24,97 @@ impl Default for PlayerClass {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+///Buffs on the player and debuffs they apply to the target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PlayerBuff {
+    Radiant,
+    WellOfRadiance,
+    EmpoweringRift,
+    WardOfDawn,
+    BannerShield,
+    NobleRounds,
+    Weaken,
+}
+impl PlayerBuff {
+    ///The perk that models this buff
+    pub fn perk(&self) -> Perks {
+        match self {
+            PlayerBuff::Radiant => Perks::Radiant,
+            PlayerBuff::WellOfRadiance => Perks::WellOfRadiance,
+            PlayerBuff::EmpoweringRift => Perks::EmpRift,
+            PlayerBuff::WardOfDawn => Perks::WardOfDawn,
+            PlayerBuff::BannerShield => Perks::BannerShield,
+            PlayerBuff::NobleRounds => Perks::NobleRounds,
+            PlayerBuff::Weaken => Perks::Weaken,
+        }
+    }
+}
+
+///The players build, read by perks through `CalculationInput`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayerState {
+    ///Element of the equipped subclass, UNKNOWN if it wasn't given
+    pub subclass: DamageType,
+    ///Manifest hashes of the equipped aspects and fragments
+    pub aspects: Vec<u32>,
+    pub fragments: Vec<u32>,
+    ///Armor stats from 0 to 100
+    pub resilience: i32,
+    pub mobility: i32,
+    pub recovery: i32,
+    pub buffs: Vec<PlayerBuff>,
+}
+impl PlayerState {
+    pub fn has_buff(&self, _buff: PlayerBuff) -> bool {
+        self.buffs.contains(&_buff)
+    }
+    ///True if the subclass is `_element` or no subclass was given
+    pub fn subclass_is(&self, _element: DamageType) -> bool {
+        self.subclass == _element || self.subclass == DamageType::UNKNOWN
+    }
+    ///True if `_aspect` is equipped or no aspects were given
+    pub fn has_aspect(&self, _aspect: Perks) -> bool {
+        self.aspects.is_empty() || self.aspects.contains(&(_aspect as u32))
+    }
+    ///True if `_fragment` is equipped or no fragments were given
+    pub fn has_fragment(&self, _fragment: Perks) -> bool {
+        self.fragments.is_empty() || self.fragments.contains(&(_fragment as u32))
+    }
+    ///Resilience tier from 0 to 10
+    pub fn resilience_tier(&self) -> usize {
+        (self.resilience.clamp(0, 100) / 10) as usize
+    }
+    ///Recovery tier from 0 to 10
+    pub fn recovery_tier(&self) -> usize {
+        (self.recovery.clamp(0, 100) / 10) as usize
+    }
+    ///Active buffs as perks so they go through the same modifiers as buffs added to a weapon
+    pub fn buff_perks(&self) -> Vec<Perk> {
+        self.buffs
+            .iter()
+            .map(|buff| Perk {
+                stat_buffs: HashMap::new(),
+                enhanced: false,
+                value: 1,
+                hash: buff.perk() as u32,
+                raw_hash: buff.perk() as u32,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Player {
     pub pl: u32,
     pub class: PlayerClass,
+    ///Set through `Calculator::set_player` so the weapon sees the same build
+    #[serde(default)]
+    pub state: PlayerState,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Activity {
     pub name: String,
     pub difficulty: DifficultyOptions,
@@ -40,6 +133,7 @@ impl Default for Activity {
             player: Player {
                 pl: expansion_base + 210,
                 class: PlayerClass::default(),
+                state: PlayerState::default(),
             },
         }
     }
@@ -48,6 +142,10 @@ impl Activity {
     pub fn get_pl_delta(&self) -> f64 {
         gpl_delta(&self)
     }
+    ///Pairs of power delta and damage multiplier from -99 up to the delta cap, for charting
+    pub fn get_pl_delta_curve(&self) -> Vec<(i32, f64)> {
+        gpl_delta_curve(self)
+    }
     pub fn get_rpl_mult(&self) -> f64 {
         rpl_mult(self.rpl as f64)
     }
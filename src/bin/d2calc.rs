@@ -0,0 +1,302 @@
+//! Command line front end for the calculator
+//!
+//! d2calc --type 13 --intrinsic 69420 --stat 4188031367=50 --stat 943549884=50
+//! d2calc --input build.json --json
+
+use std::process::exit;
+
+use d2_calculation_api::{
+    build_spec::{BuildSpec, PerkSpec, WeaponSpec},
+    calculator::Calculator,
+    types::rs_types::{
+        AmmoResponse, DpsResponse, FiringResponse, HandlingResponse, RangeResponse, ReloadResponse,
+    },
+    weapons::ttk_calc::ResillienceSummary,
+};
+use serde::Serialize;
+
+const USAGE: &str = "\
+Usage: d2calc [OPTIONS]
+
+Weapon:
+  --input <FILE>           read a build spec from a .json or .ron file
+  --hash <HASH>            weapon hash (default 0)
+  --type <ID>              weapon type id
+  --intrinsic <HASH>       intrinsic frame hash
+  --ammo <ID>              ammo type id (default 1)
+  --damage <HASH>          damage type hash (default kinetic)
+  --stat <HASH=VALUE>      set a stat, can be repeated
+  --perk <HASH[=VALUE]>    add a perk with an optional value, can be repeated
+
+Output:
+  --pvp                    use pvp values
+  --static                 ignore perk effects, base stats only
+  --overshield <AMOUNT>    overshield for ttk (default 0)
+  --json                   print json instead of a table
+  --export <json|ron>      print the build spec instead of calculating
+  -h, --help               print this message";
+
+#[derive(Debug, Clone)]
+struct CliOptions {
+    build: BuildSpec,
+    pvp: bool,
+    dynamic_traits: bool,
+    overshield: f64,
+    json: bool,
+    export: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct CliOutput {
+    range: RangeResponse,
+    handling: HandlingResponse,
+    reload: ReloadResponse,
+    ammo: AmmoResponse,
+    firing: FiringResponse,
+    ttk: Vec<ResillienceSummary>,
+    dps: DpsResponse,
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1).collect()) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            exit(2);
+        }
+    };
+    if let Some(format) = &options.export {
+        let spec = match format.as_str() {
+            "json" => options.build.to_json(),
+            "ron" => options.build.to_ron(),
+            _ => Err(format!("unknown export format {}", format)),
+        };
+        match spec {
+            Ok(spec) => println!("{}", spec),
+            Err(err) => {
+                eprintln!("error: {}", err);
+                exit(1);
+            }
+        }
+        return;
+    }
+    let output = match run(&options) {
+        Ok(output) => output,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            exit(1);
+        }
+    };
+    if options.json {
+        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+    } else {
+        print_table(&output);
+    }
+}
+
+fn parse_args(_args: Vec<String>) -> Result<CliOptions, String> {
+    let mut options = CliOptions {
+        build: BuildSpec::new(WeaponSpec {
+            hash: 0,
+            weapon_type: 0,
+            intrinsic_hash: 0,
+            ammo_type: 1,
+            damage_type: 3373582085,
+            stats: Default::default(),
+            perks: Vec::new(),
+        }),
+        pvp: false,
+        dynamic_traits: true,
+        overshield: 0.0,
+        json: false,
+        export: None,
+    };
+    let mut flags = options.build.weapon.clone();
+    let mut has_flags = false;
+    let mut file_input = None;
+    let mut args = _args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for {}", arg));
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                exit(0);
+            }
+            "--pvp" => options.pvp = true,
+            "--static" => options.dynamic_traits = false,
+            "--json" => options.json = true,
+            "--export" => options.export = Some(value()?),
+            "--overshield" => options.overshield = parse_num(&value()?)?,
+            "--input" => file_input = Some(read_input(&value()?)?),
+            "--hash" => flags.hash = parse_num(&value()?)?,
+            "--type" => flags.weapon_type = parse_num(&value()?)?,
+            "--intrinsic" => flags.intrinsic_hash = parse_num(&value()?)?,
+            "--ammo" => flags.ammo_type = parse_num(&value()?)?,
+            "--damage" => flags.damage_type = parse_num(&value()?)?,
+            "--stat" => {
+                let (hash, val) = split_pair(&value()?)?;
+                flags
+                    .stats
+                    .insert(hash, val.ok_or("stat needs a value")? as i32);
+            }
+            "--perk" => {
+                let (hash, val) = split_pair(&value()?)?;
+                flags.perks.push(PerkSpec {
+                    hash,
+                    value: val.unwrap_or(0),
+                    enhanced: false,
+                    stat_buffs: Default::default(),
+                });
+            }
+            _ => return Err(format!("unknown argument {}", arg)),
+        }
+        has_flags |= !matches!(
+            arg.as_str(),
+            "--pvp" | "--static" | "--json" | "--overshield" | "--input" | "--export"
+        );
+    }
+    match (file_input, has_flags) {
+        (Some(_), true) => return Err("--input can't be combined with weapon flags".to_string()),
+        (Some(build), false) => options.build = build,
+        (None, _) => options.build.weapon = flags,
+    };
+    if options.build.weapon.weapon_type == 0 {
+        return Err("a weapon type is required".to_string());
+    }
+    Ok(options)
+}
+
+fn parse_num<T: std::str::FromStr>(_value: &str) -> Result<T, String> {
+    _value
+        .parse()
+        .map_err(|_| format!("invalid number {}", _value))
+}
+
+fn split_pair(_value: &str) -> Result<(u32, Option<u32>), String> {
+    match _value.split_once('=') {
+        Some((hash, val)) => Ok((parse_num(hash)?, Some(parse_num(val)?))),
+        None => Ok((parse_num(_value)?, None)),
+    }
+}
+
+fn read_input(_path: &str) -> Result<BuildSpec, String> {
+    let text = std::fs::read_to_string(_path)
+        .map_err(|err| format!("could not read {}: {}", _path, err))?;
+    if _path.ends_with(".ron") {
+        BuildSpec::from_ron(&text)
+    } else {
+        BuildSpec::from_json(&text)
+    }
+    .map_err(|err| format!("{}: {}", _path, err))
+}
+
+fn run(_options: &CliOptions) -> Result<CliOutput, String> {
+    let mut calc = Calculator::new();
+    calc.load_build(&_options.build)?;
+
+    let traits = _options.dynamic_traits;
+    let pvp = _options.pvp;
+    Ok(CliOutput {
+        range: calc.get_weapon_range(traits, pvp),
+        handling: calc.get_weapon_handling(traits, pvp),
+        reload: calc.get_weapon_reload(traits, pvp),
+        ammo: calc.get_weapon_ammo(traits, pvp),
+        firing: calc.get_weapon_firing_data(traits, pvp),
+        ttk: calc.get_weapon_ttk(_options.overshield),
+        dps: calc.get_weapon_dps(false),
+    })
+}
+
+fn print_table(_output: &CliOutput) {
+    let range = &_output.range;
+    print_section(
+        "Range",
+        &[
+            ("hip falloff start", range.hip_falloff_start),
+            ("hip falloff end", range.hip_falloff_end),
+            ("ads falloff start", range.ads_falloff_start),
+            ("ads falloff end", range.ads_falloff_end),
+            ("floor percent", range.floor_percent),
+        ],
+    );
+    let handling = &_output.handling;
+    print_section(
+        "Handling",
+        &[
+            ("ready time", handling.ready_time),
+            ("stow time", handling.stow_time),
+            ("ads time", handling.ads_time),
+        ],
+    );
+    let reload = &_output.reload;
+    print_section(
+        "Reload",
+        &[
+            ("reload time", reload.reload_time),
+            ("ammo time", reload.ammo_time),
+        ],
+    );
+    let ammo = &_output.ammo;
+    print_section(
+        "Ammo",
+        &[
+            ("mag size", ammo.mag_size as f64),
+            ("reserve size", ammo.reserve_size as f64),
+        ],
+    );
+    let firing = &_output.firing;
+    print_section(
+        "Firing",
+        &[
+            ("pvp impact damage", firing.pvp_impact_damage),
+            ("pvp explosion damage", firing.pvp_explosion_damage),
+            ("pvp crit mult", firing.pvp_crit_mult),
+            ("pve impact damage", firing.pve_impact_damage),
+            ("pve explosion damage", firing.pve_explosion_damage),
+            ("pve crit mult", firing.pve_crit_mult),
+            ("burst delay", firing.burst_delay),
+            ("inner burst delay", firing.inner_burst_delay),
+            ("burst size", firing.burst_size as f64),
+            ("rpm", firing.rpm),
+        ],
+    );
+
+    println!("TTK");
+    println!(
+        "  {:>10} {:>10} {:>10} {:>10} {:>10}",
+        "resilience", "optimal", "headshots", "body", "bodyshots"
+    );
+    for summary in &_output.ttk {
+        println!(
+            "  {:>10} {:>10.3} {:>10} {:>10.3} {:>10}",
+            summary.value,
+            summary.optimal_ttk.time_taken,
+            summary.optimal_ttk.headshots,
+            summary.body_ttk.time_taken,
+            summary.body_ttk.bodyshots
+        );
+    }
+    println!();
+
+    let dps = &_output.dps;
+    let mut dps_rows = vec![
+        ("total damage", dps.total_damage),
+        ("total time", dps.total_time),
+        ("total shots", dps.total_shots as f64),
+    ];
+    if dps.total_time > 0.0 {
+        dps_rows.push(("average dps", dps.total_damage / dps.total_time));
+    }
+    print_section("DPS", &dps_rows);
+    for (i, mag_dps) in dps.dps_per_mag.iter().enumerate() {
+        println!("  {:<22}{:>12.3}", format!("mag {}", i + 1), mag_dps);
+    }
+}
+
+fn print_section(_title: &str, _rows: &[(&str, f64)]) {
+    println!("{}", _title);
+    for (name, value) in _rows {
+        println!("  {:<22}{:>12.3}", name, value);
+    }
+    println!();
+}
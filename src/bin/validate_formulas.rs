@@ -0,0 +1,62 @@
+//! Checks weapon_formulas.json for problems the build script would skip over
+//!
+//! validate_formulas build_resources/weapon_formulas.json
+//! validate_formulas patch.json --json --deny-warnings
+
+use std::process::exit;
+
+use d2_calculation_api::formula_validator::{validate_formulas, Severity};
+
+const USAGE: &str = "\
+Usage: validate_formulas [OPTIONS] [FILE]
+
+  FILE                     formula json to check (default build_resources/weapon_formulas.json)
+  --json                   print the report as json
+  --deny-warnings          exit with an error on warnings too
+  -h, --help               print this message";
+
+fn main() {
+    let mut path = "build_resources/weapon_formulas.json".to_string();
+    let mut json = false;
+    let mut deny_warnings = false;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                exit(0);
+            }
+            "--json" => json = true,
+            "--deny-warnings" => deny_warnings = true,
+            _ if arg.starts_with('-') => {
+                eprintln!("error: unknown argument {}\n\n{}", arg, USAGE);
+                exit(2);
+            }
+            _ => path = arg,
+        }
+    }
+
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(err) => {
+            eprintln!("error: could not read {}: {}", path, err);
+            exit(2);
+        }
+    };
+    let report = validate_formulas(&text);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        for issue in &report.issues {
+            println!("{}", issue);
+        }
+        println!(
+            "{}: {} errors, {} warnings",
+            path,
+            report.count(Severity::Error),
+            report.count(Severity::Warning)
+        );
+    }
+    if report.has_errors() || (deny_warnings && !report.issues.is_empty()) {
+        exit(1);
+    }
+}
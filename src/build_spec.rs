@@ -0,0 +1,182 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    activity::Activity,
+    enemies::Enemy,
+    formula_database::FormulaDatabase,
+    perks::{enhanced_check, Perk, Perks},
+    weapons::{Stat, Weapon},
+};
+
+///Bumped whenever the layout of [`BuildSpec`] changes in a breaking way
+pub const BUILD_SPEC_VERSION: u32 = 1;
+
+///A complete, shareable description of a weapon build and the encounter it is used in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildSpec {
+    pub version: u32,
+    pub weapon: WeaponSpec,
+    #[serde(default)]
+    pub activity: Activity,
+    #[serde(default)]
+    pub enemy: Enemy,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeaponSpec {
+    #[serde(default)]
+    pub hash: u32,
+    pub weapon_type: u8,
+    pub intrinsic_hash: u32,
+    #[serde(default = "default_ammo_type")]
+    pub ammo_type: u32,
+    #[serde(default = "default_damage_type")]
+    pub damage_type: u32,
+    #[serde(default)]
+    pub stats: BTreeMap<u32, i32>,
+    #[serde(default)]
+    pub perks: Vec<PerkSpec>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PerkSpec {
+    pub hash: u32,
+    #[serde(default)]
+    pub value: u32,
+    #[serde(default)]
+    pub enhanced: bool,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub stat_buffs: BTreeMap<u32, i32>,
+}
+
+fn default_ammo_type() -> u32 {
+    1
+}
+fn default_damage_type() -> u32 {
+    3373582085
+}
+
+impl BuildSpec {
+    pub fn new(_weapon: WeaponSpec) -> BuildSpec {
+        BuildSpec {
+            version: BUILD_SPEC_VERSION,
+            weapon: _weapon,
+            activity: Activity::default(),
+            enemy: Enemy::default(),
+        }
+    }
+
+    pub fn from_weapon(_weapon: &Weapon, _activity: &Activity, _enemy: &Enemy) -> BuildSpec {
+        BuildSpec {
+            version: BUILD_SPEC_VERSION,
+            weapon: WeaponSpec::from(_weapon),
+            activity: _activity.clone(),
+            enemy: _enemy.clone(),
+        }
+    }
+
+    ///Builds the weapon, activity and enemy described by this spec
+    pub fn build(&self, _db: &FormulaDatabase) -> Result<(Weapon, Activity, Enemy), String> {
+        Ok((
+            self.to_weapon(_db)?,
+            self.activity.clone(),
+            self.enemy.clone(),
+        ))
+    }
+
+    pub fn to_weapon(&self, _db: &FormulaDatabase) -> Result<Weapon, String> {
+        if self.version > BUILD_SPEC_VERSION {
+            return Err(format!(
+                "Build spec version {} is newer than supported version {}",
+                self.version, BUILD_SPEC_VERSION
+            ));
+        }
+        self.weapon.to_weapon(_db)
+    }
+
+    pub fn from_json(_json: &str) -> Result<BuildSpec, String> {
+        serde_json::from_str(_json).map_err(|err| format!("Invalid build json: {}", err))
+    }
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|err| format!("Could not write json: {}", err))
+    }
+    pub fn from_ron(_ron: &str) -> Result<BuildSpec, String> {
+        ron::from_str(_ron).map_err(|err| format!("Invalid build ron: {}", err))
+    }
+    pub fn to_ron(&self) -> Result<String, String> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| format!("Could not write ron: {}", err))
+    }
+}
+
+impl WeaponSpec {
+    pub fn to_weapon(&self, _db: &FormulaDatabase) -> Result<Weapon, String> {
+        let mut weapon = Weapon::generate_weapon(
+            _db,
+            self.hash,
+            self.weapon_type,
+            self.intrinsic_hash,
+            self.ammo_type,
+            self.damage_type,
+        )?;
+        for perk in &self.perks {
+            let data = enhanced_check(perk.hash);
+            weapon.perks.insert(
+                data.0,
+                Perk {
+                    stat_buffs: perk.stat_buffs.clone().into_iter().collect(),
+                    enhanced: perk.enhanced || data.1,
+                    value: perk.value,
+                    hash: data.0,
+                    raw_hash: perk.hash,
+                },
+            );
+        }
+        let stats: HashMap<u32, Stat> = self
+            .stats
+            .iter()
+            .map(|(hash, value)| (*hash, Stat::from(*value)))
+            .collect();
+        weapon.set_stats(stats);
+        Ok(weapon)
+    }
+}
+
+impl From<&Weapon> for WeaponSpec {
+    fn from(_weapon: &Weapon) -> Self {
+        let mut perks: Vec<PerkSpec> = _weapon
+            .perks
+            .values()
+            //the intrinsic and built in perks are added back by the weapon constructor
+            .filter(|perk| {
+                perk.hash != _weapon.intrinsic_hash && perk.hash != Perks::BuiltIn as u32
+            })
+            .map(|perk| PerkSpec {
+                hash: perk.raw_hash,
+                value: perk.value,
+                enhanced: perk.enhanced,
+                stat_buffs: perk.stat_buffs.clone().into_iter().collect(),
+            })
+            .collect();
+        perks.sort_by_key(|perk| perk.hash);
+        WeaponSpec {
+            hash: _weapon.hash,
+            weapon_type: _weapon.weapon_type as u8,
+            intrinsic_hash: _weapon
+                .perks
+                .get(&_weapon.intrinsic_hash)
+                .map_or(_weapon.intrinsic_hash, |perk| perk.raw_hash),
+            ammo_type: _weapon.ammo_type as u32,
+            damage_type: _weapon.damage_type.into(),
+            //part values come from perk stat buffs and get rebuilt on load
+            stats: _weapon
+                .stats
+                .iter()
+                .map(|(hash, stat)| (*hash, stat.base_value))
+                .collect(),
+            perks,
+        }
+    }
+}
@@ -0,0 +1,519 @@
+use std::collections::HashMap;
+
+use crate::{
+    abilities::{
+        ability_calc::{
+            calc_ability_damage, calc_ability_dps, AbilityDamageResponse, AbilityDpsResponse,
+        },
+        ability_data::get_ability,
+        Ability,
+    },
+    activity::{damage_calc::DifficultyOptions, Activity, Player},
+    build_spec::BuildSpec,
+    d2_enums::{BungieHash, Seconds},
+    enemies::{enemy_data::EnemyCatalogue, Enemy, EnemyType},
+    formula_database::{FormulaDatabase, PatchHistory},
+    logging::{extern_log, LogLevel},
+    perks::{enhanced_check, lib::ModifierResponseSummary, Perk},
+    types::rs_types::{
+        AmmoResponse, DpsResponse, FiringResponse, HandlingResponse, RangeResponse, ReloadResponse,
+    },
+    weapons::{
+        accuracy_calc::{
+            AccuracyDpsResponse, AccuracyModel, AccuracyTtkResponse, MonteCarloSettings,
+        },
+        dps_calc::{AddClearResponse, AddClearWave, DurationDpsResponse, StartingMagState},
+        falloff_calc::{Engagement, TtkCurveResponse},
+        optimizer::{optimize_rolls, OptimizerInput, RollScore},
+        patch_calc::{diff_patches, PatchDiff},
+        pve_ttk_calc::PveTtkResponse,
+        pvp_ttk_calc::{PvpTarget, PvpTtkResponse},
+        rotation_calc::{simulate_rotation, FiringPlan, Loadout, RotationResponse},
+        sword_calc::{GuardResponse, SwingProfile, SwingType},
+        ttk_calc::ResillienceSummary,
+        Stat, Weapon,
+    },
+};
+
+///A self contained calculation session.
+///Every instance owns its own weapon, encounter and log level so multiple
+///weapons can be calculated side by side without touching global state.
+#[derive(Debug, Clone, Default)]
+pub struct Calculator {
+    pub weapon: Weapon,
+    pub activity: Activity,
+    pub ability: Ability,
+    pub enemy: Enemy,
+    pub log_level: LogLevel,
+    ///Formulas new weapons are generated from, defaults to the compiled in data
+    pub formula_db: FormulaDatabase,
+    ///Past balance patches that weapons can be evaluated under
+    pub patches: PatchHistory,
+    ///Archetypes `set_enemy_archetype` picks from, empty until one is loaded
+    pub enemy_catalogue: EnemyCatalogue,
+}
+impl Calculator {
+    pub fn new() -> Calculator {
+        Self::default()
+    }
+
+    ///Logs a message if it is within this calculators log level
+    pub fn log(&self, _msg: &str, _level: LogLevel) {
+        extern_log(_msg, _level, self.log_level);
+    }
+
+    //---------------WEAPONS---------------//
+
+    ///Replaces the current weapon, on failure the weapon is reset to default
+    pub fn set_weapon(
+        &mut self,
+        _hash: u32,
+        _weapon_type_id: u8,
+        _intrinsic_hash: u32,
+        _ammo_type_id: u32,
+        _damage_type_id: u32,
+    ) -> Result<(), String> {
+        let new_weapon = Weapon::generate_weapon(
+            &self.formula_db,
+            _hash,
+            _weapon_type_id,
+            _intrinsic_hash,
+            _ammo_type_id,
+            _damage_type_id,
+        );
+        match new_weapon {
+            Ok(weapon) => {
+                self.weapon = weapon;
+                self.weapon
+                    .set_player_state(self.activity.player.state.clone());
+                Ok(())
+            }
+            Err(err) => {
+                self.log(
+                    &format!(
+                        "Could not find weapon data for type: {}, intrinsic: {}, Err: {:?}",
+                        _weapon_type_id, _intrinsic_hash, err
+                    ),
+                    LogLevel::Error,
+                );
+                self.weapon = Weapon::default();
+                Err(err)
+            }
+        }
+    }
+
+    ///Swaps in a new formula database, the current weapon is rebuilt from it
+    ///and keeps its perks and stats. If the new database has no data for the
+    ///current weapon it is left as is
+    pub fn set_formula_database(&mut self, _db: FormulaDatabase) {
+        self.formula_db = _db;
+        if self.weapon.hash == 0 {
+            return;
+        }
+        match self.weapon.rebuild(&self.formula_db) {
+            Ok(weapon) => self.weapon = weapon,
+            Err(err) => self.log(
+                &format!("Could not rebuild weapon with new formulas, Err: {:?}", err),
+                LogLevel::Warning,
+            ),
+        }
+    }
+
+    ///Registers a season or patch, an existing id is replaced
+    pub fn add_formula_patch(&mut self, _id: &str, _db: FormulaDatabase) {
+        self.patches.add_patch(_id, _db);
+    }
+
+    ///Evaluates the current weapon and every weapon set after this under the given patch
+    pub fn use_formula_patch(&mut self, _id: &str) -> Result<(), String> {
+        let db = self.patches.get_patch(_id)?.clone();
+        self.set_formula_database(db);
+        Ok(())
+    }
+
+    ///Values of the current weapon that changed between two patches
+    pub fn diff_formula_patches(
+        &self,
+        _old_patch: &str,
+        _new_patch: &str,
+        _pvp: bool,
+    ) -> Result<PatchDiff, String> {
+        diff_patches(&self.weapon, &self.patches, _old_patch, _new_patch, _pvp)
+    }
+
+    pub fn get_stats(&self) -> HashMap<u32, Stat> {
+        self.weapon.stats.clone()
+    }
+
+    pub fn set_stats(&mut self, _stats: HashMap<u32, i32>) {
+        let mut stats = HashMap::new();
+        for (key, value) in _stats {
+            stats.insert(key, Stat::from(value));
+        }
+        self.weapon.set_stats(stats);
+    }
+
+    pub fn add_perk(&mut self, _perk: Perk) {
+        self.weapon.add_perk(_perk);
+    }
+
+    pub fn remove_perk(&mut self, _perk_hash: u32) {
+        self.weapon.remove_perk(_perk_hash);
+    }
+
+    pub fn get_perk_hashes(&self) -> Vec<u32> {
+        self.weapon.list_perk_ids()
+    }
+
+    ///Accepts both enhanced and base perk hashes
+    pub fn change_perk_value(&mut self, _perk_hash: u32, _new_value: u32) {
+        let data = enhanced_check(_perk_hash);
+        self.weapon.change_perk_val(data.0, _new_value);
+    }
+
+    pub fn get_weapon_range(&self, _dynamic_traits: bool, _pvp: bool) -> RangeResponse {
+        let calc_input = _dynamic_traits.then(|| self.weapon.static_calc_input());
+        self.weapon.calc_range_falloff(calc_input, None, _pvp)
+    }
+
+    pub fn get_weapon_handling(&self, _dynamic_traits: bool, _pvp: bool) -> HandlingResponse {
+        let calc_input = _dynamic_traits.then(|| self.weapon.static_calc_input());
+        self.weapon.calc_handling_times(calc_input, None, _pvp)
+    }
+
+    pub fn get_weapon_reload(&self, _dynamic_traits: bool, _pvp: bool) -> ReloadResponse {
+        let calc_input = _dynamic_traits.then(|| self.weapon.static_calc_input());
+        self.weapon.calc_reload_time(calc_input, None, _pvp)
+    }
+
+    pub fn get_weapon_ammo(&self, _dynamic_traits: bool, _pvp: bool) -> AmmoResponse {
+        let calc_input = _dynamic_traits.then(|| self.weapon.static_calc_input());
+        self.weapon.calc_ammo_sizes(calc_input, None, _pvp)
+    }
+
+    pub fn get_weapon_ttk(&self, _overshield: f64) -> Vec<ResillienceSummary> {
+        self.weapon.calc_ttk(_overshield)
+    }
+
+    pub fn get_weapon_ttk_at_distance(
+        &self,
+        _overshield: f64,
+        _engagement: &Engagement,
+    ) -> Vec<ResillienceSummary> {
+        self.weapon.calc_ttk_at_distance(_overshield, _engagement)
+    }
+
+    ///Time to kill the current enemy in the current activity
+    pub fn get_weapon_pve_ttk(&self) -> PveTtkResponse {
+        self.weapon.calc_pve_ttk(&self.enemy, &self.activity)
+    }
+
+    ///Shots to kill a guardian for every headshot and bodyshot mix
+    pub fn get_weapon_pvp_ttk(&self, _target: &PvpTarget) -> PvpTtkResponse {
+        self.weapon.calc_pvp_ttk(_target)
+    }
+
+    ///Shots to kill a guardian with the same resilience and recovery as the player
+    pub fn get_weapon_mirror_ttk(&self) -> PvpTtkResponse {
+        self.weapon.calc_mirror_ttk()
+    }
+
+    ///Shots to kill a guardian for every mix with damage falloff at the engagement distance
+    pub fn get_weapon_pvp_ttk_at_distance(
+        &self,
+        _target: &PvpTarget,
+        _engagement: &Engagement,
+    ) -> PvpTtkResponse {
+        self.weapon.calc_pvp_ttk_at_distance(_target, _engagement)
+    }
+
+    ///Pvp time to kill every meter from 0 to 80m and where shots to kill goes up
+    pub fn get_weapon_ttk_curve(&self, _target: &PvpTarget, _ads: bool) -> TtkCurveResponse {
+        self.weapon.calc_ttk_curve(_target, _ads)
+    }
+
+    pub fn get_weapon_dps(&self, _use_rpl: bool) -> DpsResponse {
+        let mut dps_response = self
+            .weapon
+            .calc_dps(self.enemy.clone(), self.activity.get_pl_delta());
+        if _use_rpl {
+            dps_response.apply_rpl(self.activity.get_rpl_mult())
+        }
+        dps_response
+    }
+
+    ///Dps against the current enemy with damage falloff at the engagement distance
+    pub fn get_weapon_dps_at_distance(
+        &self,
+        _engagement: &Engagement,
+        _use_rpl: bool,
+    ) -> DpsResponse {
+        let mut dps_response = self.weapon.calc_dps_at_distance(
+            self.enemy.clone(),
+            self.activity.get_pl_delta(),
+            _engagement,
+        );
+        if _use_rpl {
+            dps_response.apply_rpl(self.activity.get_rpl_mult())
+        }
+        dps_response
+    }
+
+    ///Dps over a fixed length damage phase instead of the whole reserve
+    pub fn get_weapon_dps_for_duration(
+        &self,
+        _seconds: Seconds,
+        _starting_mag_state: &StartingMagState,
+        _use_rpl: bool,
+    ) -> DurationDpsResponse {
+        let mut response = self.weapon.calc_dps_for_duration(
+            self.enemy.clone(),
+            self.activity.get_pl_delta(),
+            _seconds,
+            _starting_mag_state,
+        );
+        if _use_rpl {
+            response.apply_rpl(self.activity.get_rpl_mult())
+        }
+        response
+    }
+
+    ///Clears a wave of the current enemy, kills trigger on kill perks
+    pub fn get_weapon_add_clear(&self, _wave: &AddClearWave, _use_rpl: bool) -> AddClearResponse {
+        let mut response =
+            self.weapon
+                .calc_add_clear(self.enemy.clone(), self.activity.get_pl_delta(), _wave);
+        if _use_rpl {
+            response.apply_rpl(self.activity.get_rpl_mult())
+        }
+        response
+    }
+
+    ///Dps against the current enemy with every shot rolled from the accuracy model
+    pub fn get_weapon_accuracy_dps(
+        &self,
+        _model: &AccuracyModel,
+        _settings: &MonteCarloSettings,
+        _use_rpl: bool,
+    ) -> AccuracyDpsResponse {
+        let mut response = self.weapon.calc_accuracy_dps(
+            &self.enemy,
+            self.activity.get_pl_delta(),
+            _model,
+            _settings,
+        );
+        if _use_rpl {
+            response.apply_rpl(self.activity.get_rpl_mult())
+        }
+        response
+    }
+
+    ///Time to kill a guardian with every shot rolled from the accuracy model
+    pub fn get_weapon_accuracy_ttk(
+        &self,
+        _target: &PvpTarget,
+        _model: &AccuracyModel,
+        _settings: &MonteCarloSettings,
+    ) -> AccuracyTtkResponse {
+        self.weapon.calc_accuracy_ttk(_target, _model, _settings)
+    }
+
+    pub fn get_swing_profiles(&self) -> Vec<SwingProfile> {
+        self.weapon.calc_swing_profiles()
+    }
+
+    ///Dps of a glaive or sword repeating one swing against the current enemy
+    pub fn get_weapon_melee_dps(
+        &self,
+        _swing: SwingType,
+        _seconds: Seconds,
+        _use_rpl: bool,
+    ) -> DpsResponse {
+        let mut response =
+            self.weapon
+                .calc_melee_dps(&self.enemy, self.activity.get_pl_delta(), _swing, _seconds);
+        if _use_rpl {
+            response.apply_rpl(self.activity.get_rpl_mult())
+        }
+        response
+    }
+
+    pub fn get_guard_stats(&self) -> GuardResponse {
+        self.weapon.calc_guard_stats()
+    }
+
+    ///Ranks perk and masterwork combinations for the current weapon and encounter
+    pub fn optimize_perks(&self, _input: &OptimizerInput) -> Vec<RollScore> {
+        optimize_rolls(
+            &self.weapon,
+            &self.enemy,
+            self.activity.get_pl_delta(),
+            _input,
+        )
+    }
+
+    ///Runs a damage phase swapping between the loadout weapons against the current enemy,
+    ///the calculators own weapon is not part of the rotation unless it is in the loadout
+    pub fn simulate_rotation(
+        &self,
+        _loadout: &Loadout,
+        _plan: &FiringPlan,
+        _duration: Seconds,
+    ) -> RotationResponse {
+        simulate_rotation(
+            _loadout,
+            &self.enemy,
+            self.activity.get_pl_delta(),
+            _plan,
+            _duration,
+        )
+    }
+
+    ///Pve damage in the response has the encounter bonuses applied
+    pub fn get_weapon_firing_data(&self, _dynamic_traits: bool, _pvp: bool) -> FiringResponse {
+        let calc_input = _dynamic_traits.then(|| self.weapon.static_calc_input());
+        let mut response = self.weapon.calc_firing_data(calc_input, None, _pvp);
+        response.apply_pve_bonuses(
+            self.activity.get_rpl_mult(),
+            self.activity.get_pl_delta(),
+            self.weapon.damage_mods.pve,
+            self.weapon.damage_mods.get_mod(&self.enemy.type_),
+        );
+        response
+    }
+
+    pub fn get_weapon_flinch(&self, _dynamic_traits: bool, _pvp: bool, _resilience: u8) -> f64 {
+        let calc_input = _dynamic_traits.then(|| self.weapon.static_calc_input());
+        self.weapon
+            .calc_flinch_resist(calc_input, _resilience as i32, _pvp, None)
+    }
+
+    pub fn get_misc_data(&self, _dynamic_traits: bool, _pvp: bool) -> HashMap<String, f64> {
+        let calc_input = _dynamic_traits.then(|| self.weapon.static_calc_input());
+        self.weapon.get_misc_stats(calc_input, _pvp)
+    }
+
+    pub fn get_modifier_response(
+        &self,
+        _dynamic_traits: bool,
+        _pvp: bool,
+    ) -> HashMap<BungieHash, ModifierResponseSummary> {
+        let calc_input = _dynamic_traits.then(|| self.weapon.static_calc_input());
+        self.weapon.get_modifier_summary(calc_input, _pvp, None)
+    }
+
+    ///Replaces the weapon, activity and enemy with the ones described by the build
+    pub fn load_build(&mut self, _build: &BuildSpec) -> Result<(), String> {
+        let (weapon, activity, enemy) = _build.build(&self.formula_db)?;
+        self.weapon = weapon;
+        self.activity = activity;
+        self.enemy = enemy;
+        self.weapon
+            .set_player_state(self.activity.player.state.clone());
+        Ok(())
+    }
+
+    pub fn export_build(&self) -> BuildSpec {
+        BuildSpec::from_weapon(&self.weapon, &self.activity, &self.enemy)
+    }
+
+    //---------------ABILITIES---------------//
+
+    ///Replaces the current ability, on failure the ability is reset to default
+    pub fn set_ability(&mut self, _hash: u32) -> Result<(), String> {
+        match get_ability(_hash) {
+            Some(ability) => {
+                self.ability = ability;
+                Ok(())
+            }
+            None => {
+                self.ability = Ability::default();
+                Err(format!("Could not find ability data for hash: {}", _hash))
+            }
+        }
+    }
+
+    ///Buffs and debuffs on the current weapon are applied to the ability as well
+    pub fn get_ability_damage(&self, _pvp: bool) -> AbilityDamageResponse {
+        calc_ability_damage(
+            &self.ability,
+            &self.weapon.list_perks(),
+            &self.enemy,
+            &self.activity,
+            _pvp,
+        )
+    }
+
+    pub fn get_ability_dps(&self, _pvp: bool) -> AbilityDpsResponse {
+        calc_ability_dps(
+            &self.ability,
+            &self.weapon.list_perks(),
+            &self.enemy,
+            &self.activity,
+            _pvp,
+        )
+    }
+
+    //---------------ENCOUNTER---------------//
+
+    ///Replaces the player and hands their build to the weapon
+    pub fn set_player(&mut self, _player: Player) {
+        self.weapon.set_player_state(_player.state.clone());
+        self.activity.player = _player;
+    }
+
+    pub fn set_encounter(
+        &mut self,
+        _reccomended_pl: u32,
+        _player_pl: u32,
+        _override_cap: i32,
+        _difficulty: DifficultyOptions,
+        _enemy_type: EnemyType,
+    ) {
+        self.activity.rpl = _reccomended_pl;
+        self.activity.cap = _override_cap;
+        self.activity.difficulty = _difficulty;
+        self.activity.player.pl = _player_pl;
+        self.enemy.type_ = _enemy_type;
+    }
+
+    ///Damage multiplier for every power delta the activity allows, for charting
+    pub fn get_pl_delta_curve(&self) -> Vec<(i32, f64)> {
+        self.activity.get_pl_delta_curve()
+    }
+
+    pub fn set_enemy_catalogue(&mut self, _catalogue: EnemyCatalogue) {
+        self.enemy_catalogue = _catalogue;
+    }
+
+    ///Replaces the enemy with an archetype from the enemy catalogue,
+    ///on failure the enemy is left unchanged
+    pub fn set_enemy_archetype(&mut self, _id: u32) -> Result<(), String> {
+        match self.enemy_catalogue.get_enemy(_id) {
+            Some(enemy) => {
+                self.enemy = enemy;
+                Ok(())
+            }
+            None => Err(format!("Could not find enemy data for id: {}", _id)),
+        }
+    }
+
+    ///Strips the activity scaling from a damage number observed in game
+    pub fn reverse_pve_calc(
+        &self,
+        _damage: f64,
+        _combatant_mult: Option<f64>,
+        _pve_mult: Option<f64>,
+    ) -> f64 {
+        let combatant_mult = _combatant_mult.unwrap_or(1.0);
+        let pve_mult = _pve_mult.unwrap_or(1.0);
+        if self.activity.name == "Default" {
+            self.log(
+                "Activity is default and can return bad values",
+                LogLevel::Warning,
+            )
+        }
+        crate::activity::damage_calc::remove_pve_bonuses(_damage, combatant_mult, &self.activity)
+            / pve_mult
+    }
+}
@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 pub enum AmmoType {
@@ -213,7 +213,7 @@ impl StatHashes {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub enum DamageType {
     ARC,
     VOID,
@@ -221,6 +221,7 @@ pub enum DamageType {
     STASIS,
     KINETIC,
     STRAND,
+    #[default]
     UNKNOWN,
 }
 
@@ -237,6 +238,19 @@ impl From<u32> for DamageType {
         }
     }
 }
+impl From<DamageType> for u32 {
+    fn from(_value: DamageType) -> u32 {
+        match _value {
+            DamageType::ARC => 2303181850,
+            DamageType::VOID => 3454344768,
+            DamageType::SOLAR => 1847026933,
+            DamageType::STASIS => 151347233,
+            DamageType::KINETIC => 3373582085,
+            DamageType::STRAND => 3949783978,
+            DamageType::UNKNOWN => 0,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 pub enum DamageSource {
@@ -246,6 +260,17 @@ pub enum DamageSource {
     ENVIRONMENTAL,
     UNKNOWN,
 }
+impl From<&WeaponType> for DamageSource {
+    fn from(_value: &WeaponType) -> DamageSource {
+        match _value {
+            WeaponType::SNIPER => DamageSource::SNIPER,
+            //glaive shots are projectiles, only their swings count as melee
+            WeaponType::SWORD => DamageSource::MELEE,
+            WeaponType::GRENADELAUNCHER | WeaponType::ROCKET => DamageSource::EXPLOSION,
+            _ => DamageSource::UNKNOWN,
+        }
+    }
+}
 
 pub type Seconds = f64;
 pub type MetersPerSecond = f64;
@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+
+use crate::d2_enums::Seconds;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChampionType {
+    #[default]
+    BARRIER,
+    OVERLOAD,
+    UNSTOPPABLE,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChampionProfile {
+    pub champion_type: ChampionType,
+    ///Resistance to everything while the champion isn't stunned
+    pub unstunned_resistance: f64,
+    ///Health of the immune shield a barrier champion raises, in the same units as health
+    pub barrier_health: f64,
+    ///Fraction of health at which the barrier goes up
+    pub barrier_threshold: f64,
+    ///Fraction of max health an overload champion regains every second while not stunned
+    pub regen_per_second: f64,
+    ///Time without taking damage before regeneration starts
+    pub regen_delay: Seconds,
+    pub stun_duration: Seconds,
+    ///Extra damage taken during the window that opens with a stun
+    pub stun_vulnerability: f64,
+    pub vulnerable_duration: Seconds,
+    ///Time after a stun before the champion can be stunned again
+    pub stun_cooldown: Seconds,
+}
+impl Default for ChampionProfile {
+    fn default() -> Self {
+        ChampionProfile {
+            champion_type: ChampionType::default(),
+            unstunned_resistance: 0.0,
+            barrier_health: 0.0,
+            barrier_threshold: 0.5,
+            regen_per_second: 0.0,
+            regen_delay: 2.0,
+            stun_duration: 3.0,
+            stun_vulnerability: 0.0,
+            vulnerable_duration: 0.0,
+            stun_cooldown: 10.0,
+        }
+    }
+}
+
+///Stun, barrier and regeneration state of a champion over a fight.
+///Without the matching champion mod a champion is never stunned and
+///a raised barrier makes it immune.
+#[derive(Debug, Clone)]
+pub struct ChampionState {
+    profile: ChampionProfile,
+    has_mod: bool,
+    stunned_until: Seconds,
+    vulnerable_until: Seconds,
+    next_stun: Seconds,
+    last_damage: Seconds,
+    barrier_left: f64,
+    barrier_raised: bool,
+    pub stuns: u32,
+}
+impl ChampionState {
+    pub fn new(_profile: ChampionProfile, _has_mod: bool) -> Self {
+        ChampionState {
+            profile: _profile,
+            has_mod: _has_mod,
+            stunned_until: f64::NEG_INFINITY,
+            vulnerable_until: f64::NEG_INFINITY,
+            next_stun: 0.0,
+            last_damage: 0.0,
+            barrier_left: 0.0,
+            barrier_raised: false,
+            stuns: 0,
+        }
+    }
+
+    pub fn is_stunned(&self, _time: Seconds) -> bool {
+        _time < self.stunned_until
+    }
+
+    ///True while a barrier the weapon can't break is up
+    pub fn is_immune(&self) -> bool {
+        self.barrier_left > 0.0 && !self.has_mod
+    }
+
+    fn stun(&mut self, _time: Seconds) {
+        self.stunned_until = _time + self.profile.stun_duration;
+        self.vulnerable_until = _time + self.profile.vulnerable_duration;
+        self.next_stun = _time + self.profile.stun_cooldown;
+        self.stuns += 1;
+    }
+
+    ///Damage scale for a hit landing at `_time`, overload and unstoppable champions are
+    ///stunned by any modded hit once the stun is off cooldown
+    pub fn damage_scale(&mut self, _time: Seconds) -> f64 {
+        if self.has_mod
+            && self.profile.champion_type != ChampionType::BARRIER
+            && _time >= self.next_stun
+        {
+            self.stun(_time);
+        }
+        let mut scale = 1.0;
+        if !self.is_stunned(_time) {
+            scale *= 1.0 - self.profile.unstunned_resistance;
+        }
+        if _time < self.vulnerable_until {
+            scale *= 1.0 + self.profile.stun_vulnerability;
+        }
+        scale
+    }
+
+    ///Health an overload champion regained since it was last damaged
+    pub fn regen(&self, _time: Seconds, _max_health: f64) -> f64 {
+        if self.profile.champion_type != ChampionType::OVERLOAD || self.is_stunned(_time) {
+            return 0.0;
+        }
+        let regen_start = (self.last_damage + self.profile.regen_delay).max(self.stunned_until);
+        (_time - regen_start).max(0.0) * self.profile.regen_per_second * _max_health
+    }
+
+    ///Splits a hit between a barrier and the champions health, returns the damage that
+    ///reaches health. Breaking the barrier stuns but the rest of the hit is lost.
+    pub fn absorb(&mut self, _time: Seconds, _damage: f64, _health_fraction: f64) -> f64 {
+        self.last_damage = _time;
+        if self.profile.champion_type == ChampionType::BARRIER
+            && !self.barrier_raised
+            && _health_fraction <= self.profile.barrier_threshold
+        {
+            self.barrier_raised = true;
+            self.barrier_left = self.profile.barrier_health;
+        }
+        if self.barrier_left <= 0.0 {
+            return _damage;
+        }
+        if self.has_mod {
+            self.barrier_left -= _damage;
+            if self.barrier_left <= 0.0 {
+                self.barrier_left = 0.0;
+                self.stun(_time);
+            }
+        }
+        0.0
+    }
+}
@@ -0,0 +1,60 @@
+use std::collections::{btree_map::Entry, BTreeMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::Enemy;
+
+///One archetype in an enemy catalogue json
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EnemyEntry {
+    pub id: u32,
+    ///Where the health and resist values were measured, entries without one are rejected
+    pub source: String,
+    #[serde(flatten)]
+    pub enemy: Enemy,
+}
+
+///Enemy archetypes loaded at runtime, there is no built in data since there are no
+///published health values to ship with the api
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EnemyCatalogue {
+    entries: BTreeMap<u32, EnemyEntry>,
+}
+impl EnemyCatalogue {
+    ///Parses a json list of `EnemyEntry`, ids have to be unique and every entry has to
+    ///cite a source
+    pub fn from_json(_json: &str) -> Result<EnemyCatalogue, String> {
+        let parsed: Vec<EnemyEntry> = serde_json::from_str(_json)
+            .map_err(|err| format!("Invalid enemy catalogue json: {}", err))?;
+        let mut catalogue = EnemyCatalogue::default();
+        let mut errors: Vec<String> = Vec::new();
+        for entry in parsed {
+            let label = format!("{} :> {}", entry.id, entry.enemy.name);
+            if entry.source.trim().is_empty() {
+                errors.push(format!("{}: no source given", label));
+            } else if entry.enemy.health <= 0.0 {
+                errors.push(format!("{}: health must be above 0", label));
+            } else if let Entry::Vacant(slot) = catalogue.entries.entry(entry.id) {
+                slot.insert(entry);
+            } else {
+                errors.push(format!("{}: duplicate id", label));
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
+        Ok(catalogue)
+    }
+
+    pub fn get_enemy(&self, _id: u32) -> Option<Enemy> {
+        self.entries.get(&_id).map(|entry| entry.enemy.clone())
+    }
+
+    ///Pairs of catalogue id and enemy
+    pub fn list_enemies(&self) -> Vec<(u32, Enemy)> {
+        self.entries
+            .iter()
+            .map(|(id, entry)| (*id, entry.enemy.clone()))
+            .collect()
+    }
+}
@@ -1,6 +1,13 @@
+use serde::{Deserialize, Serialize};
+
 use crate::activity::Activity;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+use self::champion::ChampionProfile;
+
+pub mod champion;
+pub mod enemy_data;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EnemyType {
     MINOR,
     ELITE,
@@ -17,16 +24,40 @@ impl Default for EnemyType {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+///Damage resistance that kicks in once the enemy drops to `health_fraction` of its health
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResistPhase {
+    pub health_fraction: f64,
+    pub damage_resistance: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Enemy {
+    pub name: String,
+    ///Health at matching power, in the same units as weapon damage before the rpl multiplier
     pub health: f64,
     pub damage: f64,
     pub damage_resistance: f64,
     pub type_: EnemyType,
     pub tier: u8,
+    pub resist_phases: Vec<ResistPhase>,
+    pub champion: Option<ChampionProfile>,
 }
 impl Enemy {
-    pub fn get_adjusted_health(&self, _activity: Activity) -> f64 {
-        self.health * (1.0 - self.damage_resistance)
+    ///Damage a weapon has to deal before power delta and resistances are applied,
+    ///resist phases are left out since they depend on how much health is left
+    pub fn get_adjusted_health(&self, _activity: &Activity) -> f64 {
+        self.health / (_activity.get_pl_delta() * (1.0 - self.damage_resistance))
+    }
+
+    ///Resistance of the deepest phase the enemy has reached at this fraction of its health
+    pub fn get_damage_resistance(&self, _health_fraction: f64) -> f64 {
+        self.resist_phases
+            .iter()
+            .filter(|phase| _health_fraction <= phase.health_fraction)
+            .min_by(|a, b| a.health_fraction.total_cmp(&b.health_fraction))
+            .map_or(self.damage_resistance, |phase| phase.damage_resistance)
     }
 }
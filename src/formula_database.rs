@@ -0,0 +1,407 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{
+    database,
+    types::rs_types::{
+        AmmoFormula, DamageMods, DataPointers, FiringData, HandlingFormula, MeleeFormula,
+        RangeFormula, ReloadFormula, StatQuadraticFormula, WeaponPath,
+    },
+};
+
+///Every formula table weapons are generated from.
+///The default is the data compiled in from weapon_formulas.json,
+///a balance patch can be loaded at runtime with `from_json` or `from_bytes`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FormulaDatabase {
+    pub(crate) data_pointers: HashMap<WeaponPath, DataPointers>,
+    pub(crate) range_data: Vec<RangeFormula>,
+    pub(crate) handling_data: Vec<HandlingFormula>,
+    pub(crate) reload_data: Vec<ReloadFormula>,
+    pub(crate) scalar_data: Vec<DamageMods>,
+    pub(crate) firing_data: Vec<FiringData>,
+    pub(crate) ammo_data: Vec<AmmoFormula>,
+    pub(crate) melee_data: Vec<MeleeFormula>,
+}
+impl Default for FormulaDatabase {
+    fn default() -> Self {
+        FormulaDatabase {
+            data_pointers: HashMap::from(database::DATA_POINTERS),
+            range_data: database::RANGE_DATA.to_vec(),
+            handling_data: database::HANDLING_DATA.to_vec(),
+            reload_data: database::RELOAD_DATA.to_vec(),
+            scalar_data: database::SCALAR_DATA.to_vec(),
+            firing_data: database::FIRING_DATA.to_vec(),
+            ammo_data: database::AMMO_DATA.to_vec(),
+            melee_data: database::MELEE_DATA.to_vec(),
+        }
+    }
+}
+
+impl FormulaDatabase {
+    ///Parses the same schema as build_resources/weapon_formulas.json.
+    ///Formulas that are unchanged from the compiled in data keep their timestamps,
+    ///new ones have a timestamp of 0
+    pub fn from_json(_json: &str) -> Result<FormulaDatabase, String> {
+        let jdata: Value =
+            serde_json::from_str(_json).map_err(|err| format!("Invalid formula json: {}", err))?;
+        let index = jdata
+            .get("INDEX")
+            .and_then(Value::as_object)
+            .ok_or("Formula json has no INDEX object")?;
+
+        //index 0 of every table is the fallback for weapons without that formula
+        let mut db = FormulaDatabase {
+            data_pointers: HashMap::new(),
+            range_data: vec![RangeFormula::default()],
+            handling_data: vec![HandlingFormula::default()],
+            reload_data: vec![ReloadFormula::default()],
+            scalar_data: vec![DamageMods {
+                pve: 0.0,
+                minor: 0.0,
+                elite: 0.0,
+                miniboss: 0.0,
+                champion: 0.0,
+                boss: 0.0,
+                vehicle: 0.0,
+                timestamp: 0,
+            }],
+            firing_data: vec![FiringData::default()],
+            ammo_data: vec![AmmoFormula::default()],
+            melee_data: vec![MeleeFormula::default()],
+        };
+        let mut errors: Vec<String> = Vec::new();
+        for (weapon_id, section_name) in index {
+            let section_name = section_name.as_str().unwrap_or_default();
+            let (weapon_type, section) = match (
+                weapon_id.parse::<u32>(),
+                jdata.get(section_name).and_then(Value::as_object),
+            ) {
+                (Ok(weapon_type), Some(section)) => (weapon_type, section),
+                _ => {
+                    errors.push(format!(
+                        "INDEX entry {} :> {} has no matching section",
+                        weapon_id, section_name
+                    ));
+                    continue;
+                }
+            };
+            for (intrinsic, weapon_def) in section {
+                //category, magazine and sub family tables share the section with the weapons
+                let intrinsic_hash = match intrinsic.parse::<u32>() {
+                    Ok(hash) => hash,
+                    Err(_) => continue,
+                };
+                match db.add_weapon(section, weapon_def) {
+                    Ok(pointers) => {
+                        db.data_pointers
+                            .insert(WeaponPath(weapon_type, intrinsic_hash), pointers);
+                    }
+                    Err(err) => errors.push(format!("{} :> {}: {}", section_name, intrinsic, err)),
+                }
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
+        db.copy_timestamps(&FormulaDatabase::default());
+        Ok(db)
+    }
+
+    ///Compact binary form of an already resolved database
+    pub fn from_bytes(_bytes: &[u8]) -> Result<FormulaDatabase, String> {
+        let db: FormulaDatabase = bincode::deserialize(_bytes)
+            .map_err(|err| format!("Invalid formula database bytes: {}", err))?;
+        db.check_pointers()?;
+        Ok(db)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        bincode::serialize(self).map_err(|err| format!("Could not write formula database: {}", err))
+    }
+
+    ///Weapon type id and intrinsic hash of every weapon in the database
+    pub fn weapon_paths(&self) -> Vec<(u32, u32)> {
+        let mut paths: Vec<(u32, u32)> = self
+            .data_pointers
+            .keys()
+            .map(|path| (path.0, path.1))
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    fn check_pointers(&self) -> Result<(), String> {
+        for (path, pointers) in &self.data_pointers {
+            if pointers.r >= self.range_data.len()
+                || pointers.h >= self.handling_data.len()
+                || pointers.rl >= self.reload_data.len()
+                || pointers.s >= self.scalar_data.len()
+                || pointers.f >= self.firing_data.len()
+                || pointers.a >= self.ammo_data.len()
+                || pointers.m >= self.melee_data.len()
+            {
+                return Err(format!(
+                    "Data pointers for type: {}, intrinsic: {} are out of range",
+                    path.0, path.1
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn add_weapon(
+        &mut self,
+        _section: &Map<String, Value>,
+        _weapon_def: &Value,
+    ) -> Result<DataPointers, String> {
+        let profile = |group: &str| -> Result<&Map<String, Value>, String> {
+            let name = _weapon_def
+                .get(group)
+                .and_then(Value::as_str)
+                .ok_or_else(|| format!("{} not set", group))?;
+            _section
+                .get(group)
+                .and_then(|profiles| profiles.get(name))
+                .and_then(Value::as_object)
+                .ok_or_else(|| format!("{} {} not found", group, name))
+        };
+        let cat = profile("cat")?;
+        let mag = profile("magProf")?;
+        let fam = profile("subFam")?;
+        let cat_entry = |key: &str| -> Result<&Map<String, Value>, String> {
+            cat.get(key)
+                .and_then(Value::as_object)
+                .ok_or_else(|| format!("{} not found in category", key))
+        };
+
+        let range = cat_entry("range")?;
+        let range = RangeFormula {
+            start: StatQuadraticFormula {
+                evpp: 0.0,
+                vpp: get_f64(range, "vpp_start", 0.0),
+                offset: get_f64(range, "offset_start", 0.0),
+            },
+            end: StatQuadraticFormula {
+                evpp: 0.0,
+                vpp: get_f64(range, "vpp_end", 0.0),
+                offset: get_f64(range, "offset_end", 0.0),
+            },
+            floor_percent: get_f64(range, "floor_percent", 0.0),
+            fusion: get_bool(range, "fusion"),
+            timestamp: 0,
+        };
+
+        let handling = cat_entry("handling")?;
+        let handling = HandlingFormula {
+            ready: quadratic(handling.get("ready")),
+            stow: quadratic(handling.get("stow")),
+            ads: quadratic(handling.get("ads")),
+            timestamp: 0,
+        };
+
+        let reload = cat_entry("reload")?;
+        let reload = ReloadFormula {
+            reload_data: quadratic(cat.get("reload")),
+            ammo_percent: get_f64(reload, "ammo_percent", 0.0),
+            timestamp: 0,
+        };
+
+        let scalars = cat_entry("combatant_scalars")?;
+        let scalar = DamageMods {
+            pve: _weapon_def
+                .get("pve_mult")
+                .and_then(Value::as_f64)
+                .unwrap_or(1.0),
+            minor: get_f64(scalars, "minor", 1.0),
+            elite: get_f64(scalars, "elite", 1.0),
+            miniboss: get_f64(scalars, "miniboss", 1.0),
+            champion: get_f64(scalars, "champion", 1.0),
+            boss: get_f64(scalars, "boss", 1.0),
+            vehicle: get_f64(scalars, "vehicle", 1.0),
+            timestamp: 0,
+        };
+
+        let ammo = AmmoFormula {
+            mag: quadratic(mag.get("mag")),
+            round_to: mag
+                .get("round_to")
+                .and_then(Value::as_i64)
+                .unwrap_or_default() as i32,
+            reserve_id: mag
+                .get("reserve_id")
+                .and_then(Value::as_u64)
+                .unwrap_or_default() as u32,
+            timestamp: 0,
+        };
+
+        //delays are stored in frames
+        let firing = FiringData {
+            damage: get_f64(fam, "damage", 0.0),
+            crit_mult: 1.5 + get_f64(fam, "crit_mult", 0.0) / 51.0,
+            burst_delay: get_f64(fam, "burst_delay", 0.0) * (1.0 / 30.0),
+            inner_burst_delay: get_f64(fam, "inner_burst_delay", 0.0) * (1.0 / 30.0),
+            burst_size: fam
+                .get("burst_size")
+                .and_then(Value::as_i64)
+                .unwrap_or_default() as i32,
+            one_ammo: get_bool(fam, "one_ammo"),
+            charge: get_bool(fam, "charge"),
+            timestamp: 0,
+        };
+
+        let melee = fam
+            .get("melee")
+            .and_then(Value::as_object)
+            .map(|melee| MeleeFormula {
+                light_damage: get_f64(melee, "light_damage", 0.0),
+                light_delay: get_f64(melee, "light_delay", 0.0) * (1.0 / 30.0),
+                heavy_damage: get_f64(melee, "heavy_damage", 0.0),
+                heavy_delay: get_f64(melee, "heavy_delay", 0.0) * (1.0 / 30.0),
+                guard_damage: get_f64(melee, "guard_damage", 0.0),
+                guard_delay: get_f64(melee, "guard_delay", 0.0) * (1.0 / 30.0),
+                ammo_per_heavy: melee
+                    .get("ammo_per_heavy")
+                    .and_then(Value::as_i64)
+                    .unwrap_or_default() as i32,
+                timestamp: 0,
+            });
+
+        Ok(DataPointers {
+            h: intern(&mut self.handling_data, handling),
+            r: intern(&mut self.range_data, range),
+            rl: intern(&mut self.reload_data, reload),
+            s: intern(&mut self.scalar_data, scalar),
+            f: intern(&mut self.firing_data, firing),
+            a: intern(&mut self.ammo_data, ammo),
+            //only swords and glaives have a melee profile, everything else points at the default
+            m: melee.map_or(0, |melee| intern(&mut self.melee_data, melee)),
+        })
+    }
+
+    fn copy_timestamps(&mut self, _compiled: &FormulaDatabase) {
+        copy_timestamps(&mut self.range_data, &_compiled.range_data);
+        copy_timestamps(&mut self.handling_data, &_compiled.handling_data);
+        copy_timestamps(&mut self.reload_data, &_compiled.reload_data);
+        copy_timestamps(&mut self.scalar_data, &_compiled.scalar_data);
+        copy_timestamps(&mut self.firing_data, &_compiled.firing_data);
+        copy_timestamps(&mut self.ammo_data, &_compiled.ammo_data);
+        copy_timestamps(&mut self.melee_data, &_compiled.melee_data);
+    }
+}
+
+///Name the compiled in formulas are registered under in a [`PatchHistory`]
+pub const CURRENT_PATCH: &str = "current";
+
+///Formula sets for past seasons and patches, in the order they were added.
+///Always starts out with the compiled in data as [`CURRENT_PATCH`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchHistory {
+    patches: Vec<(String, FormulaDatabase)>,
+}
+impl Default for PatchHistory {
+    fn default() -> Self {
+        PatchHistory {
+            patches: vec![(CURRENT_PATCH.to_string(), FormulaDatabase::default())],
+        }
+    }
+}
+impl PatchHistory {
+    ///Adding an id that already exists replaces its formulas
+    pub fn add_patch(&mut self, _id: &str, _db: FormulaDatabase) {
+        match self.patches.iter_mut().find(|(id, _)| id == _id) {
+            Some((_, db)) => *db = _db,
+            None => self.patches.push((_id.to_string(), _db)),
+        }
+    }
+
+    pub fn remove_patch(&mut self, _id: &str) -> Result<(), String> {
+        if _id == CURRENT_PATCH {
+            return Err(format!("The {} patch can't be removed", CURRENT_PATCH));
+        }
+        let before = self.patches.len();
+        self.patches.retain(|(id, _)| id != _id);
+        if self.patches.len() == before {
+            return Err(format!("Unknown patch: {}", _id));
+        }
+        Ok(())
+    }
+
+    pub fn get_patch(&self, _id: &str) -> Result<&FormulaDatabase, String> {
+        self.patches
+            .iter()
+            .find(|(id, _)| id == _id)
+            .map(|(_, db)| db)
+            .ok_or_else(|| format!("Unknown patch: {}", _id))
+    }
+
+    pub fn patch_ids(&self) -> Vec<String> {
+        self.patches.iter().map(|(id, _)| id.clone()).collect()
+    }
+}
+
+trait Timestamped: Clone + PartialEq {
+    fn timestamp(&mut self) -> &mut u64;
+}
+macro_rules! impl_timestamped {
+    ($($formula:ty),*) => {
+        $(impl Timestamped for $formula {
+            fn timestamp(&mut self) -> &mut u64 {
+                &mut self.timestamp
+            }
+        })*
+    };
+}
+impl_timestamped!(
+    RangeFormula,
+    HandlingFormula,
+    ReloadFormula,
+    DamageMods,
+    FiringData,
+    AmmoFormula,
+    MeleeFormula
+);
+
+fn copy_timestamps<T: Timestamped>(_table: &mut [T], _compiled: &[T]) {
+    for formula in _table.iter_mut() {
+        let found = _compiled.iter().find_map(|compiled| {
+            let mut unstamped = compiled.clone();
+            let timestamp = *unstamped.timestamp();
+            *unstamped.timestamp() = 0;
+            (unstamped == *formula).then_some(timestamp)
+        });
+        if let Some(timestamp) = found {
+            *formula.timestamp() = timestamp;
+        }
+    }
+}
+
+fn intern<T: PartialEq>(_table: &mut Vec<T>, _formula: T) -> usize {
+    match _table.iter().position(|formula| *formula == _formula) {
+        Some(index) => index,
+        None => {
+            _table.push(_formula);
+            _table.len() - 1
+        }
+    }
+}
+
+fn get_f64(_map: &Map<String, Value>, _key: &str, _default: f64) -> f64 {
+    _map.get(_key).and_then(Value::as_f64).unwrap_or(_default)
+}
+
+fn get_bool(_map: &Map<String, Value>, _key: &str) -> bool {
+    _map.get(_key).and_then(Value::as_bool).unwrap_or(false)
+}
+
+fn quadratic(_val: Option<&Value>) -> StatQuadraticFormula {
+    let empty = Map::new();
+    let map = _val.and_then(Value::as_object).unwrap_or(&empty);
+    StatQuadraticFormula {
+        evpp: get_f64(map, "evpp", 0.0),
+        vpp: get_f64(map, "vpp", 0.0),
+        offset: get_f64(map, "offset", 0.0),
+    }
+}
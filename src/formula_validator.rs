@@ -0,0 +1,649 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use serde::{
+    de::{DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    Deserializer, Serialize,
+};
+use serde_json::{Map, Value};
+
+//highest stat value formulas are evaluated at
+const MAX_STAT: f64 = 100.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum Severity {
+    ///The data can't be turned into correct formulas
+    Error,
+    ///The data builds but is probably wrong
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum IssueKind {
+    InvalidJson,
+    MissingSection,
+    MissingCategory,
+    MissingSubFamily,
+    MissingMagProfile,
+    ///A field the build would silently replace with a default
+    MissingField,
+    InvalidType,
+    ///The same key appears twice in one object, only the last one is used
+    DuplicateKey,
+    ///The same intrinsic hash is used by more than one weapon type
+    DuplicateHash,
+    NonMonotonic,
+    ImpossibleValue,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    pub kind: IssueKind,
+    ///JSON path of the offending value, e.g. `$['Pulse Rifle'].subFam.Aggressive.burst_delay`
+    pub path: String,
+    pub message: String,
+}
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(
+            f,
+            "{}[{:?}] {}: {}",
+            severity, self.kind, self.path, self.message
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+impl ValidationReport {
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity == Severity::Error)
+    }
+
+    pub fn count(&self, _severity: Severity) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == _severity)
+            .count()
+    }
+
+    fn push(&mut self, _severity: Severity, _kind: IssueKind, _path: &str, _message: String) {
+        self.issues.push(ValidationIssue {
+            severity: _severity,
+            kind: _kind,
+            path: _path.to_string(),
+            message: _message,
+        });
+    }
+}
+
+///Checks a weapon_formulas.json string for everything the build script would
+///otherwise skip over or fill in with defaults
+pub fn validate_formulas(_json: &str) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let mut duplicate_keys = Vec::new();
+    let scan = KeyScan {
+        path: "$".to_string(),
+        duplicates: &mut duplicate_keys,
+    };
+    if let Err(err) = scan.deserialize(&mut serde_json::Deserializer::from_str(_json)) {
+        report.push(
+            Severity::Error,
+            IssueKind::InvalidJson,
+            "$",
+            format!("invalid json: {}", err),
+        );
+        return report;
+    }
+    for path in duplicate_keys {
+        report.push(
+            Severity::Error,
+            IssueKind::DuplicateKey,
+            &path,
+            "key is defined more than once, earlier definitions are ignored".to_string(),
+        );
+    }
+
+    let jdata: Value = serde_json::from_str(_json).unwrap_or_default();
+    let index = match jdata.get("INDEX").and_then(Value::as_object) {
+        Some(index) => index,
+        None => {
+            report.push(
+                Severity::Error,
+                IssueKind::MissingSection,
+                "$.INDEX",
+                "no INDEX object mapping weapon type ids to sections".to_string(),
+            );
+            return report;
+        }
+    };
+
+    let mut hash_owners: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (weapon_id, section_name) in index {
+        let id_path = json_path("$.INDEX", weapon_id);
+        if weapon_id.parse::<u32>().is_err() {
+            report.push(
+                Severity::Error,
+                IssueKind::InvalidType,
+                &id_path,
+                "weapon type id is not a number".to_string(),
+            );
+        }
+        let section_name = match section_name.as_str() {
+            Some(name) => name,
+            None => {
+                report.push(
+                    Severity::Error,
+                    IssueKind::InvalidType,
+                    &id_path,
+                    "section name is not a string".to_string(),
+                );
+                continue;
+            }
+        };
+        let section_path = json_path("$", section_name);
+        let section = match jdata.get(section_name).and_then(Value::as_object) {
+            Some(section) => section,
+            None => {
+                report.push(
+                    Severity::Error,
+                    IssueKind::MissingSection,
+                    &section_path,
+                    format!(
+                        "section {} referenced by INDEX does not exist",
+                        section_name
+                    ),
+                );
+                continue;
+            }
+        };
+        validate_section(&mut report, section, &section_path);
+        for key in section.keys().filter(|key| key.parse::<u32>().is_ok()) {
+            hash_owners.entry(key).or_default().push(section_name);
+        }
+    }
+
+    let mut shared: Vec<(&str, Vec<&str>)> = hash_owners
+        .into_iter()
+        .filter(|(_, owners)| owners.len() > 1)
+        .collect();
+    shared.sort();
+    for (hash, owners) in shared {
+        report.push(
+            Severity::Warning,
+            IssueKind::DuplicateHash,
+            &json_path(&json_path("$", owners[0]), hash),
+            format!("intrinsic hash is also used by {}", owners[1..].join(", ")),
+        );
+    }
+    report
+}
+
+fn validate_section(_report: &mut ValidationReport, _section: &Map<String, Value>, _path: &str) {
+    let groups = [
+        ("cat", IssueKind::MissingCategory),
+        ("subFam", IssueKind::MissingSubFamily),
+        ("magProf", IssueKind::MissingMagProfile),
+    ];
+    for (group, kind) in groups {
+        if !_section.get(group).is_some_and(Value::is_object) {
+            _report.push(
+                Severity::Error,
+                kind,
+                &json_path(_path, group),
+                format!("section has no {} table", group),
+            );
+        }
+    }
+    let profiles = |group: &str| {
+        _section
+            .get(group)
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    for (name, cat) in profiles("cat") {
+        validate_category(_report, &cat, &json_path(&json_path(_path, "cat"), &name));
+    }
+    for (name, fam) in profiles("subFam") {
+        validate_sub_family(
+            _report,
+            &fam,
+            &json_path(&json_path(_path, "subFam"), &name),
+        );
+    }
+    for (name, mag) in profiles("magProf") {
+        validate_mag_profile(
+            _report,
+            &mag,
+            &json_path(&json_path(_path, "magProf"), &name),
+        );
+    }
+
+    for (hash, weapon_def) in _section {
+        if hash.parse::<u32>().is_err() {
+            continue;
+        }
+        let weapon_path = json_path(_path, hash);
+        for (group, kind) in groups {
+            let field_path = json_path(&weapon_path, group);
+            match weapon_def.get(group) {
+                Some(Value::String(name)) => {
+                    if !_section
+                        .get(group)
+                        .is_some_and(|table| table.get(name).is_some())
+                    {
+                        _report.push(
+                            Severity::Error,
+                            kind,
+                            &field_path,
+                            format!("{} {} does not exist", group, name),
+                        );
+                    }
+                }
+                Some(Value::Null) | None => _report.push(
+                    Severity::Error,
+                    kind,
+                    &field_path,
+                    format!("weapon has no {}", group),
+                ),
+                Some(_) => _report.push(
+                    Severity::Error,
+                    IssueKind::InvalidType,
+                    &field_path,
+                    format!("{} must be a string", group),
+                ),
+            }
+        }
+        if let Some(pve_mult) = number(_report, weapon_def, &weapon_path, "pve_mult", false) {
+            if pve_mult <= 0.0 {
+                _report.push(
+                    Severity::Error,
+                    IssueKind::ImpossibleValue,
+                    &json_path(&weapon_path, "pve_mult"),
+                    format!("pve multiplier must be positive, got {}", pve_mult),
+                );
+            }
+        }
+    }
+}
+
+fn validate_category(_report: &mut ValidationReport, _cat: &Value, _path: &str) {
+    if let Some(range) = object(_report, _cat, _path, "range") {
+        let range_path = json_path(_path, "range");
+        for key in ["vpp_start", "offset_start", "vpp_end", "offset_end"] {
+            number(_report, range, &range_path, key, true);
+        }
+        for key in ["vpp_start", "vpp_end"] {
+            if let Some(vpp) = number(_report, range, &range_path, key, false) {
+                if vpp < 0.0 {
+                    _report.push(
+                        Severity::Warning,
+                        IssueKind::NonMonotonic,
+                        &json_path(&range_path, key),
+                        "range goes down as the stat goes up".to_string(),
+                    );
+                }
+            }
+        }
+        if let Some(floor) = number(_report, range, &range_path, "floor_percent", true) {
+            if !(0.0..=1.0).contains(&floor) {
+                _report.push(
+                    Severity::Error,
+                    IssueKind::ImpossibleValue,
+                    &json_path(&range_path, "floor_percent"),
+                    format!("floor percent must be between 0 and 1, got {}", floor),
+                );
+            }
+        }
+    }
+
+    if let Some(reload) = object(_report, _cat, _path, "reload") {
+        let reload_path = json_path(_path, "reload");
+        quadratic(_report, reload, &reload_path, Slope::Falling, "reload time");
+        if let Some(ammo_percent) = number(_report, reload, &reload_path, "ammo_percent", false) {
+            if !(0.0..=1.0).contains(&ammo_percent) {
+                _report.push(
+                    Severity::Error,
+                    IssueKind::ImpossibleValue,
+                    &json_path(&reload_path, "ammo_percent"),
+                    format!("ammo percent must be between 0 and 1, got {}", ammo_percent),
+                );
+            }
+        }
+    }
+
+    if let Some(handling) = object(_report, _cat, _path, "handling") {
+        let handling_path = json_path(_path, "handling");
+        for (key, name) in [
+            ("ready", "ready time"),
+            ("stow", "stow time"),
+            ("ads", "ads time"),
+        ] {
+            if let Some(formula) = object(_report, handling, &handling_path, key) {
+                quadratic(
+                    _report,
+                    formula,
+                    &json_path(&handling_path, key),
+                    Slope::Falling,
+                    name,
+                );
+            }
+        }
+    }
+
+    if let Some(scalars) = object(_report, _cat, _path, "combatant_scalars") {
+        let scalars_path = json_path(_path, "combatant_scalars");
+        for key in ["minor", "elite", "miniboss", "champion", "boss", "vehicle"] {
+            if let Some(scalar) = number(_report, scalars, &scalars_path, key, false) {
+                if scalar <= 0.0 {
+                    _report.push(
+                        Severity::Error,
+                        IssueKind::ImpossibleValue,
+                        &json_path(&scalars_path, key),
+                        format!("combatant scalar must be positive, got {}", scalar),
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn validate_sub_family(_report: &mut ValidationReport, _fam: &Value, _path: &str) {
+    if let Some(damage) = number(_report, _fam, _path, "damage", true) {
+        if damage <= 0.0 {
+            _report.push(
+                Severity::Warning,
+                IssueKind::ImpossibleValue,
+                &json_path(_path, "damage"),
+                format!("damage should be positive, got {}", damage),
+            );
+        }
+    }
+    //crit_mult is an offset in 51ths from the 1.5x base
+    if let Some(crit) = number(_report, _fam, _path, "crit_mult", true) {
+        let crit_mult = 1.5 + crit / 51.0;
+        if crit_mult <= 0.0 {
+            _report.push(
+                Severity::Error,
+                IssueKind::ImpossibleValue,
+                &json_path(_path, "crit_mult"),
+                format!("crit multiplier works out to {:.3}", crit_mult),
+            );
+        }
+    }
+    for key in ["burst_delay", "inner_burst_delay"] {
+        if let Some(delay) = number(_report, _fam, _path, key, key == "burst_delay") {
+            if delay < 0.0 {
+                _report.push(
+                    Severity::Error,
+                    IssueKind::ImpossibleValue,
+                    &json_path(_path, key),
+                    format!("delay can't be negative, got {}", delay),
+                );
+            }
+        }
+    }
+    if let Some(burst_size) = number(_report, _fam, _path, "burst_size", true) {
+        if burst_size < 1.0 || burst_size.fract() != 0.0 {
+            _report.push(
+                Severity::Warning,
+                IssueKind::ImpossibleValue,
+                &json_path(_path, "burst_size"),
+                format!(
+                    "burst size should be a whole number of at least 1, got {}",
+                    burst_size
+                ),
+            );
+        }
+    }
+    for key in ["one_ammo", "charge"] {
+        if _fam.get(key).is_some_and(|val| !val.is_boolean()) {
+            _report.push(
+                Severity::Error,
+                IssueKind::InvalidType,
+                &json_path(_path, key),
+                format!("{} must be true or false", key),
+            );
+        }
+    }
+    if let Some(melee) = _fam.get("melee") {
+        let melee_path = json_path(_path, "melee");
+        for key in ["light_delay", "heavy_delay", "guard_delay"] {
+            if let Some(delay) = number(_report, melee, &melee_path, key, false) {
+                if delay < 0.0 {
+                    _report.push(
+                        Severity::Error,
+                        IssueKind::ImpossibleValue,
+                        &json_path(&melee_path, key),
+                        format!("delay can't be negative, got {}", delay),
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn validate_mag_profile(_report: &mut ValidationReport, _mag: &Value, _path: &str) {
+    if let Some(mag) = object(_report, _mag, _path, "mag") {
+        let mag_path = json_path(_path, "mag");
+        quadratic(_report, mag, &mag_path, Slope::Rising, "magazine size");
+        let smallest = evaluate(mag, 0.0).min(evaluate(mag, MAX_STAT));
+        if smallest < 1.0 {
+            _report.push(
+                Severity::Warning,
+                IssueKind::ImpossibleValue,
+                &mag_path,
+                format!("magazine size drops to {:.2} within 0-100 stat", smallest),
+            );
+        }
+    }
+    if let Some(round_to) = number(_report, _mag, _path, "round_to", false) {
+        if round_to < 0.0 {
+            _report.push(
+                Severity::Error,
+                IssueKind::ImpossibleValue,
+                &json_path(_path, "round_to"),
+                format!("round to can't be negative, got {}", round_to),
+            );
+        }
+    }
+    number(_report, _mag, _path, "reserve_id", false);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Slope {
+    Rising,
+    Falling,
+}
+
+fn quadratic(
+    _report: &mut ValidationReport,
+    _val: &Value,
+    _path: &str,
+    _slope: Slope,
+    _name: &str,
+) {
+    for key in ["evpp", "vpp", "offset"] {
+        number(_report, _val, _path, key, false);
+    }
+    //the derivative is linear so checking both ends covers the whole 0-100 range
+    let evpp = _val.get("evpp").and_then(Value::as_f64).unwrap_or_default();
+    let vpp = _val.get("vpp").and_then(Value::as_f64).unwrap_or_default();
+    for stat in [0.0, MAX_STAT] {
+        let slope = 2.0 * evpp * stat + vpp;
+        let wrong_way = match _slope {
+            Slope::Rising => slope < -f64::EPSILON,
+            Slope::Falling => slope > f64::EPSILON,
+        };
+        if wrong_way {
+            let direction = match _slope {
+                Slope::Rising => "down",
+                Slope::Falling => "up",
+            };
+            _report.push(
+                Severity::Warning,
+                IssueKind::NonMonotonic,
+                _path,
+                format!(
+                    "{} goes {} as the stat goes up at {} stat",
+                    _name, direction, stat
+                ),
+            );
+            return;
+        }
+    }
+}
+
+fn evaluate(_val: &Value, _stat: f64) -> f64 {
+    let coefficient = |key: &str| _val.get(key).and_then(Value::as_f64).unwrap_or_default();
+    coefficient("evpp") * _stat * _stat + coefficient("vpp") * _stat + coefficient("offset")
+}
+
+fn object<'a>(
+    _report: &mut ValidationReport,
+    _val: &'a Value,
+    _path: &str,
+    _key: &str,
+) -> Option<&'a Value> {
+    match _val.get(_key) {
+        Some(inner) if inner.is_object() => Some(inner),
+        Some(_) => {
+            _report.push(
+                Severity::Error,
+                IssueKind::InvalidType,
+                &json_path(_path, _key),
+                format!("{} must be an object", _key),
+            );
+            None
+        }
+        None => {
+            _report.push(
+                Severity::Warning,
+                IssueKind::MissingField,
+                &json_path(_path, _key),
+                format!("{} is missing, every value defaults", _key),
+            );
+            None
+        }
+    }
+}
+
+///Reads a number, wrong types are errors and missing required fields are warnings
+fn number(
+    _report: &mut ValidationReport,
+    _val: &Value,
+    _path: &str,
+    _key: &str,
+    _required: bool,
+) -> Option<f64> {
+    match _val.get(_key) {
+        Some(Value::Number(num)) => num.as_f64(),
+        Some(_) => {
+            _report.push(
+                Severity::Error,
+                IssueKind::InvalidType,
+                &json_path(_path, _key),
+                format!("{} must be a number", _key),
+            );
+            None
+        }
+        None => {
+            if _required {
+                _report.push(
+                    Severity::Warning,
+                    IssueKind::MissingField,
+                    &json_path(_path, _key),
+                    format!("{} is missing and defaults to 0", _key),
+                );
+            }
+            None
+        }
+    }
+}
+
+fn json_path(_base: &str, _key: &str) -> String {
+    let plain = _key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && _key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        format!("{}.{}", _base, _key)
+    } else {
+        format!("{}['{}']", _base, _key.replace('\'', "\\'"))
+    }
+}
+
+//serde_json keeps the last of any duplicated keys, walking the raw tokens is the only way to see them
+struct KeyScan<'a> {
+    path: String,
+    duplicates: &'a mut Vec<String>,
+}
+impl<'de, 'a> DeserializeSeed<'de> for KeyScan<'a> {
+    type Value = ();
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+impl<'de, 'a> Visitor<'de> for KeyScan<'a> {
+    type Value = ();
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any json value")
+    }
+    fn visit_bool<E>(self, _v: bool) -> Result<(), E> {
+        Ok(())
+    }
+    fn visit_i64<E>(self, _v: i64) -> Result<(), E> {
+        Ok(())
+    }
+    fn visit_u64<E>(self, _v: u64) -> Result<(), E> {
+        Ok(())
+    }
+    fn visit_f64<E>(self, _v: f64) -> Result<(), E> {
+        Ok(())
+    }
+    fn visit_str<E>(self, _v: &str) -> Result<(), E> {
+        Ok(())
+    }
+    fn visit_unit<E>(self) -> Result<(), E> {
+        Ok(())
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let mut index = 0;
+        while seq
+            .next_element_seed(KeyScan {
+                path: format!("{}[{}]", self.path, index),
+                duplicates: &mut *self.duplicates,
+            })?
+            .is_some()
+        {
+            index += 1;
+        }
+        Ok(())
+    }
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let mut seen = HashSet::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let path = json_path(&self.path, &key);
+            if !seen.insert(key) {
+                self.duplicates.push(path.clone());
+            }
+            map.next_value_seed(KeyScan {
+                path,
+                duplicates: &mut *self.duplicates,
+            })?;
+        }
+        Ok(())
+    }
+}
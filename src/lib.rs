@@ -4,8 +4,12 @@
 use logging::LogLevel;
 pub mod abilities;
 pub mod activity;
+pub mod build_spec;
+pub mod calculator;
 pub mod d2_enums;
 pub mod enemies;
+pub mod formula_database;
+pub mod formula_validator;
 pub mod logging;
 pub mod perks;
 #[cfg(test)]
@@ -14,11 +18,23 @@ pub mod types;
 pub mod weapons;
 
 use crate::perks::{Perk, Perks};
+#[cfg(feature = "wasm")]
+use crate::weapons::accuracy_calc::AccuracyModel;
+#[cfg(any(feature = "wasm", feature = "python"))]
+use crate::weapons::accuracy_calc::MonteCarloSettings;
+#[cfg(any(feature = "wasm", feature = "python"))]
+use crate::weapons::dps_calc::{AddClearWave, StartingMagState};
+#[cfg(any(feature = "wasm", feature = "python"))]
+use crate::weapons::falloff_calc::Engagement;
+use crate::weapons::pvp_ttk_calc::PvpTarget;
 use crate::weapons::{Stat, Weapon};
 use abilities::Ability;
 use activity::Activity;
+use build_spec::BuildSpec;
+use calculator::Calculator;
 use d2_enums::StatHashes;
-use enemies::Enemy;
+use enemies::{enemy_data::EnemyCatalogue, Enemy};
+use formula_database::FormulaDatabase;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::panic;
@@ -47,28 +63,20 @@ use crate::types::js_types::JsScalarResponse;
 //python
 #[cfg(feature = "python")]
 use crate::types::py_types::{
-    PyActivity, PyDifficultyOptions, PyDpsResponse, PyEnemy, PyEnemyType, PyFiringResponse,
-    PyHandlingResponse, PyPerk, PyPlayer, PyPlayerClass, PyRangeResponse, PyResillienceSummary,
+    PyAbilityDamageResponse, PyAbilityDpsResponse, PyAccuracyDpsResponse, PyAccuracyModel,
+    PyAccuracyTtkResponse, PyActivity, PyAddClearResponse, PyDifficultyOptions, PyDpsResponse,
+    PyDurationDpsResponse, PyEnemy, PyEnemyType, PyFiringResponse, PyHandlingResponse,
+    PyOvershieldType, PyPatchDiff, PyPercentiles, PyPerk, PyPlayer, PyPlayerBuff, PyPlayerClass,
+    PyPveKillData, PyPveTtkResponse, PyPvpHealing, PyPvpShotMix, PyPvpTarget, PyPvpTtkResponse,
+    PyRangeResponse, PyResillienceSummary, PyTtkCurveResponse, PyTtkDistancePoint,
 };
 #[cfg(feature = "python")]
 use pyo3::{prelude::*, types::PyDict};
 
-#[derive(Debug, Clone, Default)]
-pub struct PersistentData {
-    pub weapon: Weapon,
-    pub activity: Activity,
-    pub ability: Ability,
-    pub enemy: Enemy,
-    pub log_level: LogLevel,
-}
-impl PersistentData {
-    pub fn new() -> PersistentData {
-        Self::default()
-    }
-}
-
+//the global api is a thin shim over a per thread calculator,
+//use `Calculator` directly to keep multiple weapons around
 thread_local! {
-    static PERS_DATA: RefCell<PersistentData> = RefCell::new(PersistentData::new());
+    static PERS_DATA: RefCell<Calculator> = RefCell::new(Calculator::new());
 }
 
 #[cfg(feature = "wasm")]
@@ -113,13 +121,103 @@ pub fn weapon_as_string() -> Result<String, JsValue> {
     Ok(format!("{:?}", weapon))
 }
 
-// #[cfg(feature = "wasm")]
-// #[wasm_bindgen(js_name = "weaponJSON")]
-// ///Returns the weapon as a JSON structure, snake case fields
-// pub fn weapon_as_json() -> Result<JsValue, JsValue> {
-//     let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
-//     Ok(serde_wasm_bindgen::to_value(&weapon).unwrap())
-// }
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "exportBuild")]
+///Returns the current weapon and encounter as a build spec JSON string
+pub fn export_build() -> Result<String, JsValue> {
+    PERS_DATA
+        .with(|perm_data| perm_data.borrow().export_build().to_json())
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "importBuild")]
+pub fn import_build(_build_json: String) -> Result<(), JsValue> {
+    let build = BuildSpec::from_json(&_build_json).map_err(|err| JsValue::from_str(&err))?;
+    PERS_DATA
+        .with(|perm_data| perm_data.borrow_mut().load_build(&build))
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "setFormulaDatabase")]
+///Hot swaps the weapon formulas with ones parsed from a weapon_formulas.json string
+pub fn set_formula_database(_formula_json: String) -> Result<(), JsValue> {
+    let db = FormulaDatabase::from_json(&_formula_json).map_err(|err| JsValue::from_str(&err))?;
+    PERS_DATA.with(|perm_data| perm_data.borrow_mut().set_formula_database(db));
+    Ok(())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "setFormulaDatabaseBytes")]
+pub fn set_formula_database_bytes(_bytes: Vec<u8>) -> Result<(), JsValue> {
+    let db = FormulaDatabase::from_bytes(&_bytes).map_err(|err| JsValue::from_str(&err))?;
+    PERS_DATA.with(|perm_data| perm_data.borrow_mut().set_formula_database(db));
+    Ok(())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "resetFormulaDatabase")]
+///Goes back to the formulas compiled into the api
+pub fn reset_formula_database() {
+    PERS_DATA.with(|perm_data| {
+        perm_data
+            .borrow_mut()
+            .set_formula_database(FormulaDatabase::default())
+    });
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "addFormulaPatch")]
+///Registers a season or patch from a weapon_formulas.json string, an existing id is replaced
+pub fn add_formula_patch(_id: String, _formula_json: String) -> Result<(), JsValue> {
+    let db = FormulaDatabase::from_json(&_formula_json).map_err(|err| JsValue::from_str(&err))?;
+    PERS_DATA.with(|perm_data| perm_data.borrow_mut().add_formula_patch(&_id, db));
+    Ok(())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "getFormulaPatches")]
+pub fn get_formula_patches() -> Result<JsValue, JsValue> {
+    let ids = PERS_DATA.with(|perm_data| perm_data.borrow().patches.patch_ids());
+    Ok(serde_wasm_bindgen::to_value(&ids).unwrap())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "useFormulaPatch")]
+pub fn use_formula_patch(_id: String) -> Result<(), JsValue> {
+    PERS_DATA
+        .with(|perm_data| perm_data.borrow_mut().use_formula_patch(&_id))
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "diffFormulaPatches")]
+///Values of the current weapon that changed between two patches
+pub fn diff_formula_patches(
+    _old_patch: String,
+    _new_patch: String,
+    _pvp: bool,
+) -> Result<JsValue, JsValue> {
+    let diff = PERS_DATA
+        .with(|perm_data| {
+            perm_data
+                .borrow()
+                .diff_formula_patches(&_old_patch, &_new_patch, _pvp)
+        })
+        .map_err(|err| JsValue::from_str(&err))?;
+    Ok(serde_wasm_bindgen::to_value(&diff).unwrap())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "optimizePerks")]
+///Ranks perk and masterwork combinations for the current weapon and encounter
+pub fn optimize_perks(_input: JsValue) -> Result<JsValue, JsValue> {
+    let input: weapons::optimizer::OptimizerInput = serde_wasm_bindgen::from_value(_input)
+        .map_err(|err| JsValue::from_str(&format!("Invalid optimizer input: {}", err)))?;
+    let rolls = PERS_DATA.with(|perm_data| perm_data.borrow().optimize_perks(&input));
+    Ok(serde_wasm_bindgen::to_value(&rolls).unwrap())
+}
 
 #[cfg(feature = "wasm")]
 #[wasm_bindgen(js_name = "setWeapon")]
@@ -131,24 +229,14 @@ pub fn set_weapon(
     _damage_type_id: u32,
 ) -> Result<(), JsValue> {
     PERS_DATA.with(|perm_data| {
-        let new_weapon = Weapon::generate_weapon(
+        //errors are logged and the weapon is reset, matching old behavior
+        let _ = perm_data.borrow_mut().set_weapon(
             _hash,
             _weapon_type_id,
             _intrinsic_hash,
             _ammo_type_id,
             _damage_type_id,
         );
-        if new_weapon.is_err() {
-            console_log!(
-                "Could not find weapon data for type: {}, intrinsic: {}, Err: {:?}",
-                _weapon_type_id,
-                _intrinsic_hash,
-                new_weapon
-            );
-            perm_data.borrow_mut().weapon = Weapon::default();
-        } else {
-            perm_data.borrow_mut().weapon = new_weapon.unwrap();
-        };
     });
     Ok(())
 }
@@ -156,60 +244,39 @@ pub fn set_weapon(
 #[cfg(feature = "wasm")]
 #[wasm_bindgen(js_name = "getStats")]
 pub fn get_stats() -> Result<JsValue, JsValue> {
-    let stat_map = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.stats.clone());
-    let mut js_stat_map = HashMap::new();
-    for (key, value) in stat_map {
-        js_stat_map.insert(key, JsStat::from(value));
-    }
-    let value = serde_wasm_bindgen::to_value(&js_stat_map);
-    if value.is_err() {
-        return Err(JsValue::from_str("Could not convert stats to JsValue"));
-    }
-    Ok(value.unwrap())
+    let stat_map = PERS_DATA.with(|perm_data| perm_data.borrow().get_stats());
+    stats_to_js(stat_map)
 }
 
 #[cfg(feature = "wasm")]
 #[wasm_bindgen(js_name = "setStats")]
 pub fn set_stats(_stats: JsValue) -> Result<(), JsValue> {
     let in_stats: HashMap<u32, i32> = serde_wasm_bindgen::from_value(_stats).unwrap();
-    let mut stats = HashMap::new();
-    for (key, value) in in_stats {
-        stats.insert(key, Stat::from(value));
-    }
-    PERS_DATA.with(|perm_data| perm_data.borrow_mut().weapon.stats = stats);
+    PERS_DATA.with(|perm_data| perm_data.borrow_mut().set_stats(in_stats));
     Ok(())
 }
 
 #[cfg(feature = "wasm")]
 #[wasm_bindgen(js_name = "addTrait")]
 pub fn add_perk(_stats: JsValue, _value: u32, _hash: u32) -> Result<(), JsValue> {
-    let data = perks::enhanced_check(_hash);
-    let perk = Perk {
-        stat_buffs: serde_wasm_bindgen::from_value(_stats).unwrap(),
-        enhanced: data.1,
-        value: _value,
-        raw_hash: _hash,
-        hash: data.0,
-    };
-    PERS_DATA.with(|perm_data| perm_data.borrow_mut().weapon.add_perk(perk));
+    let perk = js_perk(_stats, _value, _hash);
+    PERS_DATA.with(|perm_data| perm_data.borrow_mut().add_perk(perk));
     Ok(())
 }
 
 #[cfg(feature = "wasm")]
 #[wasm_bindgen(js_name = "getTraitHashes")]
 pub fn query_perks() -> Vec<u32> {
-    PERS_DATA.with(|perm_data| perm_data.borrow_mut().weapon.list_perk_ids())
+    PERS_DATA.with(|perm_data| perm_data.borrow().get_perk_hashes())
 }
 
 #[cfg(feature = "wasm")]
 #[wasm_bindgen(js_name = "setTraitValue")]
 pub fn change_perk_value(perk_hash: u32, new_value: u32) {
-    let data = perks::enhanced_check(perk_hash);
     PERS_DATA.with(|perm_data| {
         perm_data
             .borrow_mut()
-            .weapon
-            .change_perk_val(data.0, new_value)
+            .change_perk_value(perk_hash, new_value)
     });
 }
 
@@ -229,14 +296,9 @@ pub fn get_perk_options_js(_perks: Vec<u32>) -> Result<JsValue, JsValue> {
 #[cfg(feature = "wasm")]
 #[wasm_bindgen(js_name = "getWeaponRangeFalloff")]
 pub fn get_weapon_range(_dynamic_traits: bool, _pvp: bool) -> Result<JsRangeResponse, JsValue> {
-    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
-    if _dynamic_traits {
-        Ok(weapon
-            .calc_range_falloff(Some(weapon.static_calc_input()), None, _pvp)
-            .into())
-    } else {
-        Ok(weapon.calc_range_falloff(None, None, _pvp).into())
-    }
+    Ok(PERS_DATA
+        .with(|perm_data| perm_data.borrow().get_weapon_range(_dynamic_traits, _pvp))
+        .into())
 }
 
 #[cfg(feature = "wasm")]
@@ -245,64 +307,68 @@ pub fn get_weapon_handling(
     _dynamic_traits: bool,
     _pvp: bool,
 ) -> Result<JsHandlingResponse, JsValue> {
-    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
-    if _dynamic_traits {
-        Ok(weapon
-            .calc_handling_times(Some(weapon.static_calc_input()), None, _pvp)
-            .into())
-    } else {
-        Ok(weapon.calc_handling_times(None, None, _pvp).into())
-    }
+    Ok(PERS_DATA
+        .with(|perm_data| {
+            perm_data
+                .borrow()
+                .get_weapon_handling(_dynamic_traits, _pvp)
+        })
+        .into())
 }
 
 #[cfg(feature = "wasm")]
 #[wasm_bindgen(js_name = "getWeaponReloadTimes")]
 pub fn get_weapon_reload(_dynamic_traits: bool, _pvp: bool) -> Result<JsReloadResponse, JsValue> {
-    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
-    if _dynamic_traits {
-        Ok(weapon
-            .calc_reload_time(Some(weapon.static_calc_input()), None, _pvp)
-            .into())
-    } else {
-        Ok(weapon.calc_reload_time(None, None, _pvp).into())
-    }
+    Ok(PERS_DATA
+        .with(|perm_data| perm_data.borrow().get_weapon_reload(_dynamic_traits, _pvp))
+        .into())
 }
 
 #[cfg(feature = "wasm")]
 #[wasm_bindgen(js_name = "getWeaponAmmoSizes")]
 pub fn get_weapon_ammo(_dynamic_traits: bool, _pvp: bool) -> Result<JsAmmoResponse, JsValue> {
-    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
-    if _dynamic_traits {
-        Ok(weapon
-            .calc_ammo_sizes(Some(weapon.static_calc_input()), None, _pvp)
-            .into())
-    } else {
-        Ok(weapon.calc_ammo_sizes(None, None, _pvp).into())
-    }
+    Ok(PERS_DATA
+        .with(|perm_data| perm_data.borrow().get_weapon_ammo(_dynamic_traits, _pvp))
+        .into())
 }
 
 #[cfg(feature = "wasm")]
 #[wasm_bindgen(js_name = "getWeaponTtk")]
 pub fn get_weapon_ttk(_overshield: f64) -> Result<JsValue, JsValue> {
-    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
-    let ttk_data = weapon.calc_ttk(_overshield);
+    let ttk_data = PERS_DATA.with(|perm_data| perm_data.borrow().get_weapon_ttk(_overshield));
     let js_ttk_data: Vec<JsResillienceSummary> = ttk_data.into_iter().map(|r| r.into()).collect();
     Ok(serde_wasm_bindgen::to_value(&js_ttk_data).unwrap())
 }
 
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "getWeaponPveTtk")]
+pub fn get_weapon_pve_ttk() -> Result<JsValue, JsValue> {
+    let ttk_data = PERS_DATA.with(|perm_data| perm_data.borrow().get_weapon_pve_ttk());
+    Ok(serde_wasm_bindgen::to_value(&ttk_data).unwrap())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "getWeaponPvpTtk")]
+pub fn get_weapon_pvp_ttk(_target: JsValue) -> Result<JsValue, JsValue> {
+    let target: PvpTarget = serde_wasm_bindgen::from_value(_target)?;
+    let ttk_data = PERS_DATA.with(|perm_data| perm_data.borrow().get_weapon_pvp_ttk(&target));
+    Ok(serde_wasm_bindgen::to_value(&ttk_data).unwrap())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "getWeaponMirrorTtk")]
+pub fn get_weapon_mirror_ttk() -> Result<JsValue, JsValue> {
+    let ttk_data = PERS_DATA.with(|perm_data| perm_data.borrow().get_weapon_mirror_ttk());
+    Ok(serde_wasm_bindgen::to_value(&ttk_data).unwrap())
+}
+
 ///DEPRECATED for now
 // #[cfg(feature = "wasm")]
 // #[wasm_bindgen(js_name = "getWeaponDps")]
 // pub fn get_weapon_dps(_use_rpl: bool) -> Result<JsDpsResponse, JsValue> {
-//     let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
-//     let enemy = PERS_DATA.with(|perm_data| perm_data.borrow().enemy.clone());
-//     let pl_dmg_mult = PERS_DATA.with(|perm_data| perm_data.borrow().activity.get_pl_delta());
-//     let mut dps_response = weapon.calc_dps(enemy, pl_dmg_mult);
-//     let rpl_mult = PERS_DATA.with(|perm_data| perm_data.borrow().activity.get_rpl_mult());
-//     if _use_rpl {
-//         dps_response.apply_rpl(rpl_mult)
-//     }
-//     Ok(dps_response.into())
+//     Ok(PERS_DATA
+//         .with(|perm_data| perm_data.borrow().get_weapon_dps(_use_rpl))
+//         .into())
 // }
 
 #[cfg(feature = "wasm")]
@@ -312,26 +378,13 @@ pub fn get_weapon_firing_data(
     _pvp: bool,
     _use_rpl: bool,
 ) -> Result<JsFiringResponse, JsValue> {
-    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
-    let mut response: types::rs_types::FiringResponse;
-    if _dynamic_traits {
-        response = weapon.calc_firing_data(Some(weapon.static_calc_input()), None, _pvp);
-    } else {
-        response = weapon.calc_firing_data(None, None, _pvp);
-    };
-    PERS_DATA.with(|perm_data| {
-        response.apply_pve_bonuses(
-            perm_data.borrow().activity.get_rpl_mult(),
-            perm_data.borrow().activity.get_pl_delta(),
-            perm_data.borrow().weapon.damage_mods.pve,
+    Ok(PERS_DATA
+        .with(|perm_data| {
             perm_data
                 .borrow()
-                .weapon
-                .damage_mods
-                .get_mod(&perm_data.borrow().enemy.type_),
-        )
-    });
-    Ok(response.into())
+                .get_weapon_firing_data(_dynamic_traits, _pvp)
+        })
+        .into())
 }
 
 #[cfg(feature = "wasm")]
@@ -341,31 +394,40 @@ pub fn get_weapon_flinch(
     _pvp: bool,
     _resilience: u8,
 ) -> Result<f64, JsValue> {
-    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
-    if _dynamic_traits {
-        Ok(weapon.calc_flinch_resist(
-            Some(weapon.static_calc_input()),
-            _resilience as i32,
-            _pvp,
-            None,
-        ))
-    } else {
-        Ok(weapon.calc_flinch_resist(None, _resilience as i32, _pvp, None))
-    }
+    Ok(PERS_DATA.with(|perm_data| {
+        perm_data
+            .borrow()
+            .get_weapon_flinch(_dynamic_traits, _pvp, _resilience)
+    }))
 }
 
 #[cfg(feature = "wasm")]
 #[wasm_bindgen(js_name = "getMiscData")]
 pub fn get_misc_data(_dynamic_traits: bool, _pvp: bool) -> Result<JsValue, JsValue> {
-    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
-    if _dynamic_traits {
-        Ok(serde_wasm_bindgen::to_value(
-            &weapon.get_misc_stats(Some(weapon.static_calc_input()), _pvp),
-        )
-        .unwrap())
-    } else {
-        Ok(serde_wasm_bindgen::to_value(&weapon.get_misc_stats(None, _pvp)).unwrap())
-    }
+    let misc = PERS_DATA.with(|perm_data| perm_data.borrow().get_misc_data(_dynamic_traits, _pvp));
+    Ok(serde_wasm_bindgen::to_value(&misc).unwrap())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "setAbility")]
+pub fn set_ability(_hash: u32) -> Result<(), JsValue> {
+    PERS_DATA
+        .with(|perm_data| perm_data.borrow_mut().set_ability(_hash))
+        .map_err(|err| JsValue::from_str(&err))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "getAbilityDamage")]
+pub fn get_ability_damage(_pvp: bool) -> Result<JsValue, JsValue> {
+    let damage = PERS_DATA.with(|perm_data| perm_data.borrow().get_ability_damage(_pvp));
+    Ok(serde_wasm_bindgen::to_value(&damage).unwrap())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "getAbilityDps")]
+pub fn get_ability_dps(_pvp: bool) -> Result<JsValue, JsValue> {
+    let dps = PERS_DATA.with(|perm_data| perm_data.borrow().get_ability_dps(_pvp));
+    Ok(serde_wasm_bindgen::to_value(&dps).unwrap())
 }
 
 #[cfg(feature = "wasm")]
@@ -376,21 +438,68 @@ pub fn set_encounter(
     _override_cap: i32,
     _difficulty: JsDifficultyOptions,
     _enemy_type: JsEnemyType,
+    _custom_deltas: Option<Vec<f64>>,
+    _custom_values: Option<Vec<f64>>,
 ) -> Result<(), JsValue> {
+    let difficulty = _difficulty
+        .into_difficulty(_custom_deltas, _custom_values, _override_cap)
+        .map_err(|err| JsValue::from_str(&err))?;
     PERS_DATA.with(|perm_data| {
-        let mut activity = &mut perm_data.borrow_mut().activity;
-        activity.rpl = _reccomended_pl;
-        activity.cap = _override_cap;
-        activity.difficulty = _difficulty.into();
-        activity.player.pl = _player_pl;
+        perm_data.borrow_mut().set_encounter(
+            _reccomended_pl,
+            _player_pl,
+            _override_cap,
+            difficulty,
+            _enemy_type.into(),
+        )
     });
+    Ok(())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "setPlayerState")]
+pub fn set_player_state(_state: JsValue) -> Result<(), JsValue> {
+    let state: activity::PlayerState = serde_wasm_bindgen::from_value(_state)?;
     PERS_DATA.with(|perm_data| {
-        let mut enemy = &mut perm_data.borrow_mut().enemy;
-        enemy.type_ = _enemy_type.into();
+        let mut player = perm_data.borrow().activity.player.clone();
+        player.state = state;
+        perm_data.borrow_mut().set_player(player);
     });
     Ok(())
 }
 
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "getPlayerState")]
+pub fn get_player_state() -> Result<JsValue, JsValue> {
+    let state = PERS_DATA.with(|perm_data| perm_data.borrow().activity.player.state.clone());
+    Ok(serde_wasm_bindgen::to_value(&state).unwrap())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "getPlDeltaCurve")]
+pub fn get_pl_delta_curve() -> Result<JsValue, JsValue> {
+    let curve = PERS_DATA.with(|perm_data| perm_data.borrow().get_pl_delta_curve());
+    Ok(serde_wasm_bindgen::to_value(&curve).unwrap())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "setEnemyCatalogue")]
+///Loads the enemy archetypes from a json list, every entry has to cite a source
+pub fn set_enemy_catalogue(_catalogue_json: String) -> Result<(), JsValue> {
+    let catalogue =
+        EnemyCatalogue::from_json(&_catalogue_json).map_err(|err| JsValue::from_str(&err))?;
+    PERS_DATA.with(|perm_data| perm_data.borrow_mut().set_enemy_catalogue(catalogue));
+    Ok(())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "setEnemyArchetype")]
+pub fn set_enemy_archetype(_id: u32) -> Result<(), JsValue> {
+    PERS_DATA
+        .with(|perm_data| perm_data.borrow_mut().set_enemy_archetype(_id))
+        .map_err(|err| JsValue::from_str(&err))
+}
+
 #[cfg(feature = "wasm")]
 #[wasm_bindgen(js_name = "setLoggingLevel")]
 pub fn set_logging_level(_level: usize) -> Result<(), JsValue> {
@@ -403,12 +512,11 @@ pub fn set_logging_level(_level: usize) -> Result<(), JsValue> {
 #[cfg(feature = "wasm")]
 #[wasm_bindgen(js_name = "getModifierResponseSummary")]
 pub fn get_modifier_response(_dynamic_traits: bool, _pvp: bool) -> Result<JsValue, JsValue> {
-    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
-    let modifier = weapon.get_modifier_summary(
-        _dynamic_traits.then_some(weapon.static_calc_input()),
-        _pvp,
-        None,
-    );
+    let modifier = PERS_DATA.with(|perm_data| {
+        perm_data
+            .borrow()
+            .get_modifier_response(_dynamic_traits, _pvp)
+    });
     Ok(serde_wasm_bindgen::to_value(&modifier).unwrap())
 }
 
@@ -416,16 +524,20 @@ pub fn get_modifier_response(_dynamic_traits: bool, _pvp: bool) -> Result<JsValu
 #[wasm_bindgen(js_name = "getScalarResponseSummary")]
 pub fn get_scalar_response(_pvp: bool) -> Result<JsScalarResponse, JsValue> {
     let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    Ok(scalar_response(&weapon, _pvp))
+}
+
+#[cfg(all(feature = "wasm", feature = "foundry"))]
+fn scalar_response(_weapon: &Weapon, _pvp: bool) -> JsScalarResponse {
+    let weapon = _weapon;
     let input_data = weapon.static_calc_input();
-    let mut cached_data = HashMap::new();
-    let rmr = perks::get_range_modifier(weapon.list_perks(), &input_data, _pvp, &mut cached_data);
-    let rsmr = perks::get_reload_modifier(weapon.list_perks(), &input_data, _pvp, &mut cached_data);
-    let mmr =
-        perks::get_magazine_modifier(weapon.list_perks(), &input_data, _pvp, &mut cached_data);
-    let hmr =
-        perks::get_handling_modifier(weapon.list_perks(), &input_data, _pvp, &mut cached_data);
-    let imr = perks::get_reserve_modifier(weapon.list_perks(), &input_data, _pvp, &mut cached_data);
-    Ok(JsScalarResponse {
+    let mut sim_state = perks::simulation_state::SimulationState::default();
+    let rmr = perks::get_range_modifier(weapon.list_perks(), &input_data, _pvp, &mut sim_state);
+    let rsmr = perks::get_reload_modifier(weapon.list_perks(), &input_data, _pvp, &mut sim_state);
+    let mmr = perks::get_magazine_modifier(weapon.list_perks(), &input_data, _pvp, &mut sim_state);
+    let hmr = perks::get_handling_modifier(weapon.list_perks(), &input_data, _pvp, &mut sim_state);
+    let imr = perks::get_reserve_modifier(weapon.list_perks(), &input_data, _pvp, &mut sim_state);
+    JsScalarResponse {
         ads_range_scalar: rmr.range_zoom_scale,
         global_range_scalar: rmr.range_all_scale,
         hipfire_range_scalar: rmr.range_hip_scale,
@@ -435,7 +547,79 @@ pub fn get_scalar_response(_pvp: bool) -> Result<JsScalarResponse, JsValue> {
         reload_scalar: rsmr.reload_time_scale,
         mag_size_scalar: mmr.magazine_scale,
         reserve_size_scalar: imr.inv_scale,
-    })
+    }
+}
+
+#[cfg(feature = "wasm")]
+fn stats_to_js(_stats: HashMap<u32, Stat>) -> Result<JsValue, JsValue> {
+    let mut js_stat_map = HashMap::new();
+    for (key, value) in _stats {
+        js_stat_map.insert(key, JsStat::from(value));
+    }
+    let value = serde_wasm_bindgen::to_value(&js_stat_map);
+    if value.is_err() {
+        return Err(JsValue::from_str("Could not convert stats to JsValue"));
+    }
+    Ok(value.unwrap())
+}
+
+#[cfg(feature = "wasm")]
+fn js_perk(_stats: JsValue, _value: u32, _hash: u32) -> Perk {
+    let data = perks::enhanced_check(_hash);
+    Perk {
+        stat_buffs: serde_wasm_bindgen::from_value(_stats).unwrap(),
+        enhanced: data.1,
+        value: _value,
+        raw_hash: _hash,
+        hash: data.0,
+    }
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "set_ability")]
+fn set_ability(_hash: u32) -> PyResult<()> {
+    PERS_DATA
+        .with(|perm_data| perm_data.borrow_mut().set_ability(_hash))
+        .map_err(pyo3::exceptions::PyValueError::new_err)
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "get_ability_damage")]
+fn get_ability_damage(_pvp: bool) -> PyResult<PyAbilityDamageResponse> {
+    Ok(PERS_DATA
+        .with(|perm_data| perm_data.borrow().get_ability_damage(_pvp))
+        .into())
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "get_ability_dps")]
+fn get_ability_dps(_pvp: bool) -> PyResult<PyAbilityDpsResponse> {
+    Ok(PERS_DATA
+        .with(|perm_data| perm_data.borrow().get_ability_dps(_pvp))
+        .into())
+}
+
+#[cfg(feature = "python")]
+fn register_ability_interface(py: Python<'_>, parent_module: &PyModule) -> PyResult<()> {
+    let ability_interface = PyModule::new(py, "AbilityInterface")?;
+    //functions
+    ability_interface.add_function(wrap_pyfunction!(set_ability, ability_interface)?)?;
+    ability_interface.add_function(wrap_pyfunction!(get_ability_damage, ability_interface)?)?;
+    ability_interface.add_function(wrap_pyfunction!(get_ability_dps, ability_interface)?)?;
+
+    //classes
+    ability_interface.add_class::<PyAbilityDamageResponse>()?;
+    ability_interface.add_class::<PyAbilityDpsResponse>()?;
+
+    parent_module.add_submodule(ability_interface)?;
+    Ok(())
+}
+
+#[cfg(feature = "wasm")]
+impl Default for JsCalculator {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 //////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -456,37 +640,93 @@ fn set_weapon(
     _damage_type_id: u32,
 ) -> PyResult<()> {
     PERS_DATA.with(|perm_data| {
-        let new_weapon = Weapon::generate_weapon(
+        //errors are logged and the weapon is reset, matching old behavior
+        let _ = perm_data.borrow_mut().set_weapon(
             _hash,
             _weapon_type_id,
             _intrinsic_hash,
             _ammo_type_id,
             _damage_type_id,
         );
-        if new_weapon.is_err() {
-            #[cfg(feature = "python")]
-            println!(
-                "Could not find weapon data for type: {}, intrinsic: {}",
-                _weapon_type_id, _intrinsic_hash
-            );
-            perm_data.borrow_mut().weapon = Weapon::default();
-        } else {
-            perm_data.borrow_mut().weapon = new_weapon.unwrap();
-        };
     });
     Ok(())
 }
 
+#[cfg(feature = "python")]
+#[pyfunction(name = "set_formula_database")]
+fn set_formula_database(_formula_json: &str) -> PyResult<()> {
+    let db = FormulaDatabase::from_json(_formula_json)
+        .map_err(pyo3::exceptions::PyValueError::new_err)?;
+    PERS_DATA.with(|perm_data| perm_data.borrow_mut().set_formula_database(db));
+    Ok(())
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "set_formula_database_bytes")]
+fn set_formula_database_bytes(_bytes: Vec<u8>) -> PyResult<()> {
+    let db =
+        FormulaDatabase::from_bytes(&_bytes).map_err(pyo3::exceptions::PyValueError::new_err)?;
+    PERS_DATA.with(|perm_data| perm_data.borrow_mut().set_formula_database(db));
+    Ok(())
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "reset_formula_database")]
+fn reset_formula_database() -> PyResult<()> {
+    PERS_DATA.with(|perm_data| {
+        perm_data
+            .borrow_mut()
+            .set_formula_database(FormulaDatabase::default())
+    });
+    Ok(())
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "add_formula_patch")]
+fn add_formula_patch(_id: &str, _formula_json: &str) -> PyResult<()> {
+    let db = FormulaDatabase::from_json(_formula_json)
+        .map_err(pyo3::exceptions::PyValueError::new_err)?;
+    PERS_DATA.with(|perm_data| perm_data.borrow_mut().add_formula_patch(_id, db));
+    Ok(())
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "get_formula_patches")]
+fn get_formula_patches() -> PyResult<Vec<String>> {
+    Ok(PERS_DATA.with(|perm_data| perm_data.borrow().patches.patch_ids()))
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "use_formula_patch")]
+fn use_formula_patch(_id: &str) -> PyResult<()> {
+    PERS_DATA
+        .with(|perm_data| perm_data.borrow_mut().use_formula_patch(_id))
+        .map_err(pyo3::exceptions::PyValueError::new_err)
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "diff_formula_patches")]
+fn diff_formula_patches(_old_patch: &str, _new_patch: &str, _pvp: bool) -> PyResult<PyPatchDiff> {
+    PERS_DATA
+        .with(|perm_data| {
+            perm_data
+                .borrow()
+                .diff_formula_patches(_old_patch, _new_patch, _pvp)
+        })
+        .map(|diff| diff.into())
+        .map_err(pyo3::exceptions::PyValueError::new_err)
+}
+
 #[cfg(feature = "python")]
 #[pyfunction(name = "add_trait")]
 fn add_perk(_perk: PyPerk) -> PyResult<()> {
-    PERS_DATA.with(|perm_data| perm_data.borrow_mut().weapon.add_perk(_perk.into()));
+    PERS_DATA.with(|perm_data| perm_data.borrow_mut().add_perk(_perk.into()));
     Ok(())
 }
 #[cfg(feature = "python")]
 #[pyfunction(name = "remove_trait")]
 fn remove_perk(_perk_hash: u32) -> PyResult<()> {
-    PERS_DATA.with(|perm_data| perm_data.borrow_mut().weapon.remove_perk(_perk_hash));
+    PERS_DATA.with(|perm_data| perm_data.borrow_mut().remove_perk(_perk_hash));
     Ok(())
 }
 #[cfg(feature = "python")]
@@ -498,26 +738,16 @@ fn weapon_as_string() -> PyResult<String> {
 #[cfg(feature = "python")]
 #[pyfunction(name = "get_range_falloff")]
 fn get_weapon_range(_use_traits: bool, _pvp: bool) -> PyResult<PyRangeResponse> {
-    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
-    if _use_traits {
-        Ok(weapon
-            .calc_range_falloff(Some(weapon.static_calc_input()), None, _pvp)
-            .into())
-    } else {
-        Ok(weapon.calc_range_falloff(None, None, _pvp).into())
-    }
+    Ok(PERS_DATA
+        .with(|perm_data| perm_data.borrow().get_weapon_range(_use_traits, _pvp))
+        .into())
 }
 #[cfg(feature = "python")]
 #[pyfunction(name = "get_handling_times")]
 fn get_weapon_handling(_use_traits: bool, _pvp: bool) -> PyResult<PyHandlingResponse> {
-    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
-    if _use_traits {
-        Ok(weapon
-            .calc_handling_times(Some(weapon.static_calc_input()), None, _pvp)
-            .into())
-    } else {
-        Ok(weapon.calc_handling_times(None, None, _pvp).into())
-    }
+    Ok(PERS_DATA
+        .with(|perm_data| perm_data.borrow().get_weapon_handling(_use_traits, _pvp))
+        .into())
 }
 
 #[cfg(feature = "python")]
@@ -527,47 +757,136 @@ fn get_firing_data(
     _pvp: bool,
     _use_rpl: bool,
 ) -> PyResult<PyFiringResponse> {
-    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
-    let mut response: types::rs_types::FiringResponse;
-    if _dynamic_traits {
-        response = weapon.calc_firing_data(Some(weapon.static_calc_input()), None, _pvp);
-    } else {
-        response = weapon.calc_firing_data(None, None, _pvp);
-    };
-    PERS_DATA.with(|perm_data| {
-        response.apply_pve_bonuses(
-            perm_data.borrow().activity.get_rpl_mult(),
-            perm_data.borrow().activity.get_pl_delta(),
-            perm_data.borrow().weapon.damage_mods.pve,
+    Ok(PERS_DATA
+        .with(|perm_data| {
             perm_data
                 .borrow()
-                .weapon
-                .damage_mods
-                .get_mod(&perm_data.borrow().enemy.type_),
-        )
-    });
-    Ok(response.into())
+                .get_weapon_firing_data(_dynamic_traits, _pvp)
+        })
+        .into())
 }
 
 #[cfg(feature = "python")]
 #[pyfunction(name = "get_dps")]
 fn get_weapon_dps(_do_rpl_mult: bool) -> PyResult<PyDpsResponse> {
-    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
-    let enemy = PERS_DATA.with(|perm_data| perm_data.borrow().enemy.clone());
-    let pl_dmg_mult = PERS_DATA.with(|perm_data| perm_data.borrow().activity.get_pl_delta());
-    let mut dps_response = weapon.calc_dps(enemy, pl_dmg_mult);
-    let rpl_mult = PERS_DATA.with(|perm_data| perm_data.borrow().activity.get_rpl_mult());
-    if _do_rpl_mult {
-        dps_response.apply_rpl(rpl_mult)
+    Ok(PERS_DATA
+        .with(|perm_data| perm_data.borrow().get_weapon_dps(_do_rpl_mult))
+        .into())
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "get_dps_at_distance")]
+fn get_weapon_dps_at_distance(
+    _distance: f64,
+    _ads: bool,
+    _do_rpl_mult: bool,
+) -> PyResult<PyDpsResponse> {
+    let engagement = Engagement {
+        distance: _distance,
+        ads: _ads,
+    };
+    Ok(PERS_DATA
+        .with(|perm_data| {
+            perm_data
+                .borrow()
+                .get_weapon_dps_at_distance(&engagement, _do_rpl_mult)
+        })
+        .into())
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "get_dps_for_duration")]
+fn get_weapon_dps_for_duration(
+    _seconds: f64,
+    _do_rpl_mult: bool,
+    _mag: Option<i32>,
+    _reserves: Option<i32>,
+    _perk_values: Option<HashMap<u32, u32>>,
+    _kill_interval: Option<f64>,
+) -> PyResult<PyDurationDpsResponse> {
+    let starting_mag_state = StartingMagState {
+        mag: _mag,
+        reserves: _reserves,
+        perk_values: _perk_values.unwrap_or_default(),
+        kill_interval: _kill_interval,
+    };
+    Ok(PERS_DATA
+        .with(|perm_data| {
+            perm_data.borrow().get_weapon_dps_for_duration(
+                _seconds,
+                &starting_mag_state,
+                _do_rpl_mult,
+            )
+        })
+        .into())
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "get_add_clear")]
+fn get_weapon_add_clear(
+    _count: u32,
+    _retarget_time: f64,
+    _do_rpl_mult: bool,
+) -> PyResult<PyAddClearResponse> {
+    let wave = AddClearWave {
+        count: _count,
+        retarget_time: _retarget_time,
+    };
+    Ok(PERS_DATA
+        .with(|perm_data| perm_data.borrow().get_weapon_add_clear(&wave, _do_rpl_mult))
+        .into())
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "get_accuracy_dps")]
+fn get_weapon_accuracy_dps(
+    _model: PyAccuracyModel,
+    _do_rpl_mult: bool,
+    _trials: Option<u32>,
+    _seed: Option<u64>,
+) -> PyResult<PyAccuracyDpsResponse> {
+    let settings = monte_carlo_settings(_trials, _seed);
+    Ok(PERS_DATA
+        .with(|perm_data| {
+            perm_data
+                .borrow()
+                .get_weapon_accuracy_dps(&_model.into(), &settings, _do_rpl_mult)
+        })
+        .into())
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "get_accuracy_ttk")]
+fn get_weapon_accuracy_ttk(
+    _target: PyPvpTarget,
+    _model: PyAccuracyModel,
+    _trials: Option<u32>,
+    _seed: Option<u64>,
+) -> PyResult<PyAccuracyTtkResponse> {
+    let settings = monte_carlo_settings(_trials, _seed);
+    Ok(PERS_DATA
+        .with(|perm_data| {
+            perm_data
+                .borrow()
+                .get_weapon_accuracy_ttk(&_target.into(), &_model.into(), &settings)
+        })
+        .into())
+}
+
+#[cfg(feature = "python")]
+fn monte_carlo_settings(_trials: Option<u32>, _seed: Option<u64>) -> MonteCarloSettings {
+    let default = MonteCarloSettings::default();
+    MonteCarloSettings {
+        trials: _trials.unwrap_or(default.trials),
+        seed: _seed.unwrap_or(default.seed),
     }
-    Ok(dps_response.into())
 }
 
 #[cfg(feature = "python")]
 #[pyfunction(name = "get_ttk")]
 fn get_weapon_ttk(_overshield: f64) -> PyResult<Vec<PyResillienceSummary>> {
     let rs_resill_array =
-        PERS_DATA.with(|perm_data| perm_data.borrow().weapon.calc_ttk(_overshield));
+        PERS_DATA.with(|perm_data| perm_data.borrow().get_weapon_ttk(_overshield));
     //call into on every item in the array
     let mut py_resill_array = Vec::new();
     for rs_resill in rs_resill_array {
@@ -576,18 +895,89 @@ fn get_weapon_ttk(_overshield: f64) -> PyResult<Vec<PyResillienceSummary>> {
     Ok(py_resill_array)
 }
 
+#[cfg(feature = "python")]
+#[pyfunction(name = "get_ttk_at_distance")]
+fn get_weapon_ttk_at_distance(
+    _overshield: f64,
+    _distance: f64,
+    _ads: bool,
+) -> PyResult<Vec<PyResillienceSummary>> {
+    let engagement = Engagement {
+        distance: _distance,
+        ads: _ads,
+    };
+    Ok(PERS_DATA
+        .with(|perm_data| {
+            perm_data
+                .borrow()
+                .get_weapon_ttk_at_distance(_overshield, &engagement)
+        })
+        .into_iter()
+        .map(|r| r.into())
+        .collect())
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "get_pve_ttk")]
+fn get_weapon_pve_ttk() -> PyResult<PyPveTtkResponse> {
+    Ok(PERS_DATA
+        .with(|perm_data| perm_data.borrow().get_weapon_pve_ttk())
+        .into())
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "get_pvp_ttk")]
+fn get_weapon_pvp_ttk(_target: PyPvpTarget) -> PyResult<PyPvpTtkResponse> {
+    let target: PvpTarget = _target.into();
+    Ok(PERS_DATA
+        .with(|perm_data| perm_data.borrow().get_weapon_pvp_ttk(&target))
+        .into())
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "get_mirror_ttk")]
+fn get_weapon_mirror_ttk() -> PyResult<PyPvpTtkResponse> {
+    Ok(PERS_DATA
+        .with(|perm_data| perm_data.borrow().get_weapon_mirror_ttk())
+        .into())
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "get_pvp_ttk_at_distance")]
+fn get_weapon_pvp_ttk_at_distance(
+    _target: PyPvpTarget,
+    _distance: f64,
+    _ads: bool,
+) -> PyResult<PyPvpTtkResponse> {
+    let target: PvpTarget = _target.into();
+    let engagement = Engagement {
+        distance: _distance,
+        ads: _ads,
+    };
+    Ok(PERS_DATA
+        .with(|perm_data| {
+            perm_data
+                .borrow()
+                .get_weapon_pvp_ttk_at_distance(&target, &engagement)
+        })
+        .into())
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "get_ttk_curve")]
+fn get_weapon_ttk_curve(_target: PyPvpTarget, _ads: bool) -> PyResult<PyTtkCurveResponse> {
+    let target: PvpTarget = _target.into();
+    Ok(PERS_DATA
+        .with(|perm_data| perm_data.borrow().get_weapon_ttk_curve(&target, _ads))
+        .into())
+}
+
 #[cfg(feature = "python")]
 #[pyfunction(name = "set_stats")]
-fn set_weapon_stats(_in: &PyDict) {
-    let mut stats = HashMap::new();
-    for (key, value) in _in.iter() {
-        let key = key.extract::<u32>().unwrap();
-        let value: Stat = value.extract::<i32>().unwrap().into();
-        stats.insert(key, value);
-    }
-    PERS_DATA.with(|perm_data| {
-        perm_data.borrow_mut().weapon.stats = stats;
-    });
+fn set_weapon_stats(_in: &PyDict) -> PyResult<()> {
+    let stats = py_stats(_in)?;
+    PERS_DATA.with(|perm_data| perm_data.borrow_mut().set_stats(stats));
+    Ok(())
 }
 
 #[cfg(feature = "python")]
@@ -597,23 +987,20 @@ fn reverse_pve_calc(
     _combatant_mult: Option<f64>,
     _pve_mult: Option<f64>,
 ) -> PyResult<f64> {
-    use logging::extern_log;
-    let output = PERS_DATA.with(|perm_data| {
-        let combatant_mult = _combatant_mult.unwrap_or(1.0);
-        let pve_mult = _pve_mult.unwrap_or(1.0);
-        if perm_data.borrow().activity.name == "Default" {
-            extern_log(
-                "Activity is default and can return bad values",
-                LogLevel::Warning,
-            )
-        }
-        activity::damage_calc::remove_pve_bonuses(
-            _damage,
-            combatant_mult,
-            &perm_data.borrow().activity,
-        ) / pve_mult
-    });
-    Ok(output)
+    Ok(PERS_DATA.with(|perm_data| {
+        perm_data
+            .borrow()
+            .reverse_pve_calc(_damage, _combatant_mult, _pve_mult)
+    }))
+}
+
+#[cfg(feature = "python")]
+fn py_stats(_in: &PyDict) -> PyResult<HashMap<u32, i32>> {
+    let mut stats = HashMap::new();
+    for (key, value) in _in.iter() {
+        stats.insert(key.extract::<u32>()?, value.extract::<i32>()?);
+    }
+    Ok(stats)
 }
 
 #[cfg(feature = "python")]
@@ -627,18 +1014,67 @@ fn register_weapon_interface(py: Python<'_>, parent_module: &PyModule) -> PyResu
     weapon_interface.add_function(wrap_pyfunction!(add_perk, weapon_interface)?)?;
     weapon_interface.add_function(wrap_pyfunction!(get_weapon_hash, weapon_interface)?)?;
     weapon_interface.add_function(wrap_pyfunction!(get_weapon_dps, weapon_interface)?)?;
+    weapon_interface.add_function(wrap_pyfunction!(
+        get_weapon_dps_at_distance,
+        weapon_interface
+    )?)?;
+    weapon_interface.add_function(wrap_pyfunction!(
+        get_weapon_dps_for_duration,
+        weapon_interface
+    )?)?;
+    weapon_interface.add_function(wrap_pyfunction!(get_weapon_add_clear, weapon_interface)?)?;
+    weapon_interface.add_function(wrap_pyfunction!(get_weapon_accuracy_dps, weapon_interface)?)?;
+    weapon_interface.add_function(wrap_pyfunction!(get_weapon_accuracy_ttk, weapon_interface)?)?;
     weapon_interface.add_function(wrap_pyfunction!(set_weapon, weapon_interface)?)?;
     weapon_interface.add_function(wrap_pyfunction!(get_weapon_ttk, weapon_interface)?)?;
+    weapon_interface.add_function(wrap_pyfunction!(get_weapon_pve_ttk, weapon_interface)?)?;
+    weapon_interface.add_function(wrap_pyfunction!(get_weapon_pvp_ttk, weapon_interface)?)?;
+    weapon_interface.add_function(wrap_pyfunction!(get_weapon_mirror_ttk, weapon_interface)?)?;
+    weapon_interface.add_function(wrap_pyfunction!(
+        get_weapon_ttk_at_distance,
+        weapon_interface
+    )?)?;
+    weapon_interface.add_function(wrap_pyfunction!(
+        get_weapon_pvp_ttk_at_distance,
+        weapon_interface
+    )?)?;
+    weapon_interface.add_function(wrap_pyfunction!(get_weapon_ttk_curve, weapon_interface)?)?;
     weapon_interface.add_function(wrap_pyfunction!(set_weapon_stats, weapon_interface)?)?;
     weapon_interface.add_function(wrap_pyfunction!(get_firing_data, weapon_interface)?)?;
     weapon_interface.add_function(wrap_pyfunction!(reverse_pve_calc, weapon_interface)?)?;
+    weapon_interface.add_function(wrap_pyfunction!(set_formula_database, weapon_interface)?)?;
+    weapon_interface.add_function(wrap_pyfunction!(
+        set_formula_database_bytes,
+        weapon_interface
+    )?)?;
+    weapon_interface.add_function(wrap_pyfunction!(reset_formula_database, weapon_interface)?)?;
+    weapon_interface.add_function(wrap_pyfunction!(add_formula_patch, weapon_interface)?)?;
+    weapon_interface.add_function(wrap_pyfunction!(get_formula_patches, weapon_interface)?)?;
+    weapon_interface.add_function(wrap_pyfunction!(use_formula_patch, weapon_interface)?)?;
+    weapon_interface.add_function(wrap_pyfunction!(diff_formula_patches, weapon_interface)?)?;
 
     //classes
     weapon_interface.add_class::<PyPerk>()?;
     weapon_interface.add_class::<PyRangeResponse>()?;
     weapon_interface.add_class::<PyHandlingResponse>()?;
     weapon_interface.add_class::<PyDpsResponse>()?;
+    weapon_interface.add_class::<PyDurationDpsResponse>()?;
+    weapon_interface.add_class::<PyAddClearResponse>()?;
+    weapon_interface.add_class::<PyAccuracyModel>()?;
+    weapon_interface.add_class::<PyPercentiles>()?;
+    weapon_interface.add_class::<PyAccuracyDpsResponse>()?;
+    weapon_interface.add_class::<PyAccuracyTtkResponse>()?;
+    weapon_interface.add_class::<PyPatchDiff>()?;
     weapon_interface.add_class::<PyResillienceSummary>()?;
+    weapon_interface.add_class::<PyPveKillData>()?;
+    weapon_interface.add_class::<PyPveTtkResponse>()?;
+    weapon_interface.add_class::<PyPvpTarget>()?;
+    weapon_interface.add_class::<PyOvershieldType>()?;
+    weapon_interface.add_class::<PyPvpHealing>()?;
+    weapon_interface.add_class::<PyPvpShotMix>()?;
+    weapon_interface.add_class::<PyPvpTtkResponse>()?;
+    weapon_interface.add_class::<PyTtkDistancePoint>()?;
+    weapon_interface.add_class::<PyTtkCurveResponse>()?;
     parent_module.add_submodule(weapon_interface)?;
     Ok(())
 }
@@ -646,11 +1082,12 @@ fn register_weapon_interface(py: Python<'_>, parent_module: &PyModule) -> PyResu
 #[cfg(feature = "python")]
 #[pyfunction(name = "set_activity")]
 fn set_activity(_activity: PyActivity) -> PyResult<()> {
+    let difficulty = _activity.difficulty_options()?;
     PERS_DATA.with(|perm_data| {
         perm_data.borrow_mut().activity.cap = _activity.cap;
         perm_data.borrow_mut().activity.name = _activity.name;
         perm_data.borrow_mut().activity.rpl = _activity.rpl;
-        perm_data.borrow_mut().activity.difficulty = _activity.difficulty.into();
+        perm_data.borrow_mut().activity.difficulty = difficulty;
     });
     Ok(())
 }
@@ -666,7 +1103,7 @@ fn get_activity() -> PyResult<PyActivity> {
 #[pyfunction(name = "set_player")]
 fn set_player(_player: PyPlayer) -> PyResult<()> {
     PERS_DATA.with(|perm_data| {
-        perm_data.borrow_mut().activity.player = _player.into();
+        perm_data.borrow_mut().set_player(_player.into());
     });
     Ok(())
 }
@@ -678,6 +1115,12 @@ fn get_player() -> PyResult<PyPlayer> {
     Ok(player.into())
 }
 
+#[cfg(feature = "python")]
+#[pyfunction(name = "get_pl_delta_curve")]
+fn get_pl_delta_curve() -> PyResult<Vec<(i32, f64)>> {
+    Ok(PERS_DATA.with(|perm_data| perm_data.borrow().get_pl_delta_curve()))
+}
+
 #[cfg(feature = "python")]
 fn register_activity_interface(py: Python<'_>, parent_module: &PyModule) -> PyResult<()> {
     let activity_interface = PyModule::new(py, "ActivityInterface")?;
@@ -686,12 +1129,14 @@ fn register_activity_interface(py: Python<'_>, parent_module: &PyModule) -> PyRe
     activity_interface.add_function(wrap_pyfunction!(set_activity, activity_interface)?)?;
     activity_interface.add_function(wrap_pyfunction!(get_player, activity_interface)?)?;
     activity_interface.add_function(wrap_pyfunction!(set_player, activity_interface)?)?;
+    activity_interface.add_function(wrap_pyfunction!(get_pl_delta_curve, activity_interface)?)?;
 
     //classes
     activity_interface.add_class::<PyActivity>()?;
     activity_interface.add_class::<PyPlayer>()?;
     activity_interface.add_class::<PyDifficultyOptions>()?;
     activity_interface.add_class::<PyPlayerClass>()?;
+    activity_interface.add_class::<PyPlayerBuff>()?;
 
     parent_module.add_submodule(activity_interface)?;
     Ok(())
@@ -722,6 +1167,23 @@ fn set_enemy_type(_enemy_type: PyEnemyType) -> PyResult<()> {
     Ok(())
 }
 
+#[cfg(feature = "python")]
+#[pyfunction(name = "set_enemy_catalogue")]
+fn set_enemy_catalogue(_catalogue_json: String) -> PyResult<()> {
+    let catalogue = EnemyCatalogue::from_json(&_catalogue_json)
+        .map_err(pyo3::exceptions::PyValueError::new_err)?;
+    PERS_DATA.with(|perm_data| perm_data.borrow_mut().set_enemy_catalogue(catalogue));
+    Ok(())
+}
+
+#[cfg(feature = "python")]
+#[pyfunction(name = "set_enemy_archetype")]
+fn set_enemy_archetype(_id: u32) -> PyResult<()> {
+    PERS_DATA
+        .with(|perm_data| perm_data.borrow_mut().set_enemy_archetype(_id))
+        .map_err(pyo3::exceptions::PyValueError::new_err)
+}
+
 #[cfg(feature = "python")]
 fn register_enemy_interface(py: Python<'_>, parent_module: &PyModule) -> PyResult<()> {
     let enemy_interface = PyModule::new(py, "EnemyInterface")?;
@@ -729,6 +1191,8 @@ fn register_enemy_interface(py: Python<'_>, parent_module: &PyModule) -> PyResul
     enemy_interface.add_function(wrap_pyfunction!(get_enemy, enemy_interface)?)?;
     enemy_interface.add_function(wrap_pyfunction!(set_enemy, enemy_interface)?)?;
     enemy_interface.add_function(wrap_pyfunction!(set_enemy_type, enemy_interface)?)?;
+    enemy_interface.add_function(wrap_pyfunction!(set_enemy_catalogue, enemy_interface)?)?;
+    enemy_interface.add_function(wrap_pyfunction!(set_enemy_archetype, enemy_interface)?)?;
 
     //classes
     enemy_interface.add_class::<PyEnemy>()?;
@@ -738,11 +1202,715 @@ fn register_enemy_interface(py: Python<'_>, parent_module: &PyModule) -> PyResul
     Ok(())
 }
 
+//---------------CALCULATOR---------------//
+
+///Instance based version of the global api,
+///each calculator keeps its own weapon and encounter
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_name = "Calculator")]
+pub struct JsCalculator {
+    calc: Calculator,
+}
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(js_class = "Calculator")]
+impl JsCalculator {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> JsCalculator {
+        JsCalculator {
+            calc: Calculator::new(),
+        }
+    }
+    #[wasm_bindgen(js_name = "stringifyWeapon")]
+    pub fn weapon_as_string(&self) -> String {
+        format!("{:?}", self.calc.weapon)
+    }
+    #[wasm_bindgen(js_name = "setWeapon")]
+    pub fn set_weapon(
+        &mut self,
+        _hash: u32,
+        _weapon_type_id: u8,
+        _intrinsic_hash: u32,
+        _ammo_type_id: u32,
+        _damage_type_id: u32,
+    ) -> Result<(), JsValue> {
+        self.calc
+            .set_weapon(
+                _hash,
+                _weapon_type_id,
+                _intrinsic_hash,
+                _ammo_type_id,
+                _damage_type_id,
+            )
+            .map_err(|err| JsValue::from_str(&err))
+    }
+    #[wasm_bindgen(js_name = "exportBuild")]
+    pub fn export_build(&self) -> Result<String, JsValue> {
+        self.calc
+            .export_build()
+            .to_json()
+            .map_err(|err| JsValue::from_str(&err))
+    }
+    #[wasm_bindgen(js_name = "importBuild")]
+    pub fn import_build(&mut self, _build_json: String) -> Result<(), JsValue> {
+        let build = BuildSpec::from_json(&_build_json).map_err(|err| JsValue::from_str(&err))?;
+        self.calc
+            .load_build(&build)
+            .map_err(|err| JsValue::from_str(&err))
+    }
+    #[wasm_bindgen(js_name = "setFormulaDatabase")]
+    pub fn set_formula_database(&mut self, _formula_json: String) -> Result<(), JsValue> {
+        let db =
+            FormulaDatabase::from_json(&_formula_json).map_err(|err| JsValue::from_str(&err))?;
+        self.calc.set_formula_database(db);
+        Ok(())
+    }
+    #[wasm_bindgen(js_name = "setFormulaDatabaseBytes")]
+    pub fn set_formula_database_bytes(&mut self, _bytes: Vec<u8>) -> Result<(), JsValue> {
+        let db = FormulaDatabase::from_bytes(&_bytes).map_err(|err| JsValue::from_str(&err))?;
+        self.calc.set_formula_database(db);
+        Ok(())
+    }
+    #[wasm_bindgen(js_name = "resetFormulaDatabase")]
+    pub fn reset_formula_database(&mut self) {
+        self.calc.set_formula_database(FormulaDatabase::default());
+    }
+    #[wasm_bindgen(js_name = "addFormulaPatch")]
+    pub fn add_formula_patch(&mut self, _id: String, _formula_json: String) -> Result<(), JsValue> {
+        let db =
+            FormulaDatabase::from_json(&_formula_json).map_err(|err| JsValue::from_str(&err))?;
+        self.calc.add_formula_patch(&_id, db);
+        Ok(())
+    }
+    #[wasm_bindgen(js_name = "getFormulaPatches")]
+    pub fn get_formula_patches(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.calc.patches.patch_ids()).unwrap())
+    }
+    #[wasm_bindgen(js_name = "useFormulaPatch")]
+    pub fn use_formula_patch(&mut self, _id: String) -> Result<(), JsValue> {
+        self.calc
+            .use_formula_patch(&_id)
+            .map_err(|err| JsValue::from_str(&err))
+    }
+    #[wasm_bindgen(js_name = "diffFormulaPatches")]
+    pub fn diff_formula_patches(
+        &self,
+        _old_patch: String,
+        _new_patch: String,
+        _pvp: bool,
+    ) -> Result<JsValue, JsValue> {
+        let diff = self
+            .calc
+            .diff_formula_patches(&_old_patch, &_new_patch, _pvp)
+            .map_err(|err| JsValue::from_str(&err))?;
+        Ok(serde_wasm_bindgen::to_value(&diff).unwrap())
+    }
+    #[wasm_bindgen(js_name = "optimizePerks")]
+    pub fn optimize_perks(&self, _input: JsValue) -> Result<JsValue, JsValue> {
+        let input: weapons::optimizer::OptimizerInput = serde_wasm_bindgen::from_value(_input)
+            .map_err(|err| JsValue::from_str(&format!("Invalid optimizer input: {}", err)))?;
+        Ok(serde_wasm_bindgen::to_value(&self.calc.optimize_perks(&input)).unwrap())
+    }
+    #[wasm_bindgen(js_name = "getStats")]
+    pub fn get_stats(&self) -> Result<JsValue, JsValue> {
+        stats_to_js(self.calc.get_stats())
+    }
+    #[wasm_bindgen(js_name = "setStats")]
+    pub fn set_stats(&mut self, _stats: JsValue) -> Result<(), JsValue> {
+        let in_stats: HashMap<u32, i32> = serde_wasm_bindgen::from_value(_stats)?;
+        self.calc.set_stats(in_stats);
+        Ok(())
+    }
+    #[wasm_bindgen(js_name = "addTrait")]
+    pub fn add_perk(&mut self, _stats: JsValue, _value: u32, _hash: u32) {
+        self.calc.add_perk(js_perk(_stats, _value, _hash));
+    }
+    #[wasm_bindgen(js_name = "removeTrait")]
+    pub fn remove_perk(&mut self, _hash: u32) {
+        self.calc.remove_perk(perks::enhanced_check(_hash).0);
+    }
+    #[wasm_bindgen(js_name = "getTraitHashes")]
+    pub fn query_perks(&self) -> Vec<u32> {
+        self.calc.get_perk_hashes()
+    }
+    #[wasm_bindgen(js_name = "setTraitValue")]
+    pub fn change_perk_value(&mut self, perk_hash: u32, new_value: u32) {
+        self.calc.change_perk_value(perk_hash, new_value);
+    }
+    #[wasm_bindgen(js_name = "getWeaponRangeFalloff")]
+    pub fn get_weapon_range(&self, _dynamic_traits: bool, _pvp: bool) -> JsRangeResponse {
+        self.calc.get_weapon_range(_dynamic_traits, _pvp).into()
+    }
+    #[wasm_bindgen(js_name = "getWeaponHandlingTimes")]
+    pub fn get_weapon_handling(&self, _dynamic_traits: bool, _pvp: bool) -> JsHandlingResponse {
+        self.calc.get_weapon_handling(_dynamic_traits, _pvp).into()
+    }
+    #[wasm_bindgen(js_name = "getWeaponReloadTimes")]
+    pub fn get_weapon_reload(&self, _dynamic_traits: bool, _pvp: bool) -> JsReloadResponse {
+        self.calc.get_weapon_reload(_dynamic_traits, _pvp).into()
+    }
+    #[wasm_bindgen(js_name = "getWeaponAmmoSizes")]
+    pub fn get_weapon_ammo(&self, _dynamic_traits: bool, _pvp: bool) -> JsAmmoResponse {
+        self.calc.get_weapon_ammo(_dynamic_traits, _pvp).into()
+    }
+    #[wasm_bindgen(js_name = "getWeaponTtk")]
+    pub fn get_weapon_ttk(&self, _overshield: f64) -> Result<JsValue, JsValue> {
+        let js_ttk_data: Vec<JsResillienceSummary> = self
+            .calc
+            .get_weapon_ttk(_overshield)
+            .into_iter()
+            .map(|r| r.into())
+            .collect();
+        Ok(serde_wasm_bindgen::to_value(&js_ttk_data)?)
+    }
+    #[wasm_bindgen(js_name = "getWeaponPveTtk")]
+    pub fn get_weapon_pve_ttk(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(
+            &self.calc.get_weapon_pve_ttk(),
+        )?)
+    }
+    #[wasm_bindgen(js_name = "getWeaponPvpTtk")]
+    pub fn get_weapon_pvp_ttk(&self, _target: JsValue) -> Result<JsValue, JsValue> {
+        let target: PvpTarget = serde_wasm_bindgen::from_value(_target)?;
+        Ok(serde_wasm_bindgen::to_value(
+            &self.calc.get_weapon_pvp_ttk(&target),
+        )?)
+    }
+    #[wasm_bindgen(js_name = "getWeaponMirrorTtk")]
+    pub fn get_weapon_mirror_ttk(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(
+            &self.calc.get_weapon_mirror_ttk(),
+        )?)
+    }
+    #[wasm_bindgen(js_name = "getWeaponTtkAtDistance")]
+    pub fn get_weapon_ttk_at_distance(
+        &self,
+        _overshield: f64,
+        _engagement: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let engagement: Engagement = serde_wasm_bindgen::from_value(_engagement)?;
+        let js_ttk_data: Vec<JsResillienceSummary> = self
+            .calc
+            .get_weapon_ttk_at_distance(_overshield, &engagement)
+            .into_iter()
+            .map(|r| r.into())
+            .collect();
+        Ok(serde_wasm_bindgen::to_value(&js_ttk_data)?)
+    }
+    #[wasm_bindgen(js_name = "getWeaponPvpTtkAtDistance")]
+    pub fn get_weapon_pvp_ttk_at_distance(
+        &self,
+        _target: JsValue,
+        _engagement: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let target: PvpTarget = serde_wasm_bindgen::from_value(_target)?;
+        let engagement: Engagement = serde_wasm_bindgen::from_value(_engagement)?;
+        Ok(serde_wasm_bindgen::to_value(
+            &self
+                .calc
+                .get_weapon_pvp_ttk_at_distance(&target, &engagement),
+        )?)
+    }
+    #[wasm_bindgen(js_name = "getWeaponTtkCurve")]
+    pub fn get_weapon_ttk_curve(&self, _target: JsValue, _ads: bool) -> Result<JsValue, JsValue> {
+        let target: PvpTarget = serde_wasm_bindgen::from_value(_target)?;
+        Ok(serde_wasm_bindgen::to_value(
+            &self.calc.get_weapon_ttk_curve(&target, _ads),
+        )?)
+    }
+    #[wasm_bindgen(js_name = "getWeaponDps")]
+    pub fn get_weapon_dps(&self, _use_rpl: bool) -> JsDpsResponse {
+        self.calc.get_weapon_dps(_use_rpl).into()
+    }
+    #[wasm_bindgen(js_name = "getWeaponDpsAtDistance")]
+    pub fn get_weapon_dps_at_distance(
+        &self,
+        _engagement: JsValue,
+        _use_rpl: bool,
+    ) -> Result<JsDpsResponse, JsValue> {
+        let engagement: Engagement = serde_wasm_bindgen::from_value(_engagement)?;
+        Ok(self
+            .calc
+            .get_weapon_dps_at_distance(&engagement, _use_rpl)
+            .into())
+    }
+    ///Leaving the starting state undefined starts with a full magazine and default perk values
+    #[wasm_bindgen(js_name = "getWeaponDpsForDuration")]
+    pub fn get_weapon_dps_for_duration(
+        &self,
+        _seconds: f64,
+        _starting_mag_state: JsValue,
+        _use_rpl: bool,
+    ) -> Result<JsValue, JsValue> {
+        let starting_mag_state: StartingMagState = if _starting_mag_state.is_undefined()
+            || _starting_mag_state.is_null()
+        {
+            StartingMagState::default()
+        } else {
+            serde_wasm_bindgen::from_value(_starting_mag_state)
+                .map_err(|err| JsValue::from_str(&format!("Invalid starting mag state: {}", err)))?
+        };
+        Ok(serde_wasm_bindgen::to_value(
+            &self
+                .calc
+                .get_weapon_dps_for_duration(_seconds, &starting_mag_state, _use_rpl),
+        )?)
+    }
+    ///Leaving the settings undefined runs 100 trials with seed 0
+    #[wasm_bindgen(js_name = "getWeaponAccuracyDps")]
+    pub fn get_weapon_accuracy_dps(
+        &self,
+        _model: JsValue,
+        _settings: JsValue,
+        _use_rpl: bool,
+    ) -> Result<JsValue, JsValue> {
+        let model: AccuracyModel = serde_wasm_bindgen::from_value(_model)?;
+        let settings: MonteCarloSettings = if _settings.is_undefined() || _settings.is_null() {
+            MonteCarloSettings::default()
+        } else {
+            serde_wasm_bindgen::from_value(_settings)?
+        };
+        Ok(serde_wasm_bindgen::to_value(
+            &self
+                .calc
+                .get_weapon_accuracy_dps(&model, &settings, _use_rpl),
+        )?)
+    }
+    #[wasm_bindgen(js_name = "getWeaponAccuracyTtk")]
+    pub fn get_weapon_accuracy_ttk(
+        &self,
+        _target: JsValue,
+        _model: JsValue,
+        _settings: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let target: PvpTarget = serde_wasm_bindgen::from_value(_target)?;
+        let model: AccuracyModel = serde_wasm_bindgen::from_value(_model)?;
+        let settings: MonteCarloSettings = if _settings.is_undefined() || _settings.is_null() {
+            MonteCarloSettings::default()
+        } else {
+            serde_wasm_bindgen::from_value(_settings)?
+        };
+        Ok(serde_wasm_bindgen::to_value(
+            &self
+                .calc
+                .get_weapon_accuracy_ttk(&target, &model, &settings),
+        )?)
+    }
+    #[wasm_bindgen(js_name = "getWeaponAddClear")]
+    pub fn get_weapon_add_clear(&self, _wave: JsValue, _use_rpl: bool) -> Result<JsValue, JsValue> {
+        let wave: AddClearWave = serde_wasm_bindgen::from_value(_wave)?;
+        Ok(serde_wasm_bindgen::to_value(
+            &self.calc.get_weapon_add_clear(&wave, _use_rpl),
+        )?)
+    }
+    #[wasm_bindgen(js_name = "getWeaponFiringData")]
+    pub fn get_weapon_firing_data(&self, _dynamic_traits: bool, _pvp: bool) -> JsFiringResponse {
+        self.calc
+            .get_weapon_firing_data(_dynamic_traits, _pvp)
+            .into()
+    }
+    #[wasm_bindgen(js_name = "getWeaponFlinch")]
+    pub fn get_weapon_flinch(&self, _dynamic_traits: bool, _pvp: bool, _resilience: u8) -> f64 {
+        self.calc
+            .get_weapon_flinch(_dynamic_traits, _pvp, _resilience)
+    }
+    #[wasm_bindgen(js_name = "getMiscData")]
+    pub fn get_misc_data(&self, _dynamic_traits: bool, _pvp: bool) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(
+            &self.calc.get_misc_data(_dynamic_traits, _pvp),
+        )?)
+    }
+    #[wasm_bindgen(js_name = "getModifierResponseSummary")]
+    pub fn get_modifier_response(
+        &self,
+        _dynamic_traits: bool,
+        _pvp: bool,
+    ) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(
+            &self.calc.get_modifier_response(_dynamic_traits, _pvp),
+        )?)
+    }
+    #[cfg(feature = "foundry")]
+    #[wasm_bindgen(js_name = "getScalarResponseSummary")]
+    pub fn get_scalar_response(&self, _pvp: bool) -> JsScalarResponse {
+        scalar_response(&self.calc.weapon, _pvp)
+    }
+    #[wasm_bindgen(js_name = "setAbility")]
+    pub fn set_ability(&mut self, _hash: u32) -> Result<(), JsValue> {
+        self.calc
+            .set_ability(_hash)
+            .map_err(|err| JsValue::from_str(&err))
+    }
+    #[wasm_bindgen(js_name = "getAbilityDamage")]
+    pub fn get_ability_damage(&self, _pvp: bool) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(
+            &self.calc.get_ability_damage(_pvp),
+        )?)
+    }
+    #[wasm_bindgen(js_name = "getAbilityDps")]
+    pub fn get_ability_dps(&self, _pvp: bool) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(
+            &self.calc.get_ability_dps(_pvp),
+        )?)
+    }
+    #[wasm_bindgen(js_name = "setEncounter")]
+    pub fn set_encounter(
+        &mut self,
+        _reccomended_pl: u32,
+        _player_pl: u32,
+        _override_cap: i32,
+        _difficulty: JsDifficultyOptions,
+        _enemy_type: JsEnemyType,
+        _custom_deltas: Option<Vec<f64>>,
+        _custom_values: Option<Vec<f64>>,
+    ) -> Result<(), JsValue> {
+        let difficulty = _difficulty
+            .into_difficulty(_custom_deltas, _custom_values, _override_cap)
+            .map_err(|err| JsValue::from_str(&err))?;
+        self.calc.set_encounter(
+            _reccomended_pl,
+            _player_pl,
+            _override_cap,
+            difficulty,
+            _enemy_type.into(),
+        );
+        Ok(())
+    }
+    #[wasm_bindgen(js_name = "setPlayerState")]
+    pub fn set_player_state(&mut self, _state: JsValue) -> Result<(), JsValue> {
+        let mut player = self.calc.activity.player.clone();
+        player.state = serde_wasm_bindgen::from_value(_state)?;
+        self.calc.set_player(player);
+        Ok(())
+    }
+    #[wasm_bindgen(js_name = "getPlayerState")]
+    pub fn get_player_state(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(
+            &self.calc.activity.player.state,
+        )?)
+    }
+    #[wasm_bindgen(js_name = "getPlDeltaCurve")]
+    pub fn get_pl_delta_curve(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(
+            &self.calc.get_pl_delta_curve(),
+        )?)
+    }
+    #[wasm_bindgen(js_name = "setEnemyCatalogue")]
+    pub fn set_enemy_catalogue(&mut self, _catalogue_json: String) -> Result<(), JsValue> {
+        let catalogue =
+            EnemyCatalogue::from_json(&_catalogue_json).map_err(|err| JsValue::from_str(&err))?;
+        self.calc.set_enemy_catalogue(catalogue);
+        Ok(())
+    }
+    #[wasm_bindgen(js_name = "setEnemyArchetype")]
+    pub fn set_enemy_archetype(&mut self, _id: u32) -> Result<(), JsValue> {
+        self.calc
+            .set_enemy_archetype(_id)
+            .map_err(|err| JsValue::from_str(&err))
+    }
+    #[wasm_bindgen(js_name = "setLoggingLevel")]
+    pub fn set_logging_level(&mut self, _level: usize) {
+        self.calc.log_level = _level.into();
+    }
+}
+
+///Instance based version of the global api,
+///each calculator keeps its own weapon, activity and enemy
+#[cfg(feature = "python")]
+#[pyclass(name = "Calculator")]
+#[derive(Debug, Clone, Default)]
+pub struct PyCalculator {
+    calc: Calculator,
+}
+#[cfg(feature = "python")]
+#[pymethods]
+impl PyCalculator {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+    fn get_hash(&self) -> u32 {
+        self.calc.weapon.hash
+    }
+    fn set_weapon(
+        &mut self,
+        _hash: u32,
+        _weapon_type_id: u8,
+        _intrinsic_hash: u32,
+        _ammo_type_id: u32,
+        _damage_type_id: u32,
+    ) -> PyResult<()> {
+        self.calc
+            .set_weapon(
+                _hash,
+                _weapon_type_id,
+                _intrinsic_hash,
+                _ammo_type_id,
+                _damage_type_id,
+            )
+            .map_err(pyo3::exceptions::PyValueError::new_err)
+    }
+    fn export_build(&self) -> PyResult<String> {
+        self.calc
+            .export_build()
+            .to_json()
+            .map_err(pyo3::exceptions::PyValueError::new_err)
+    }
+    fn import_build(&mut self, _build_json: &str) -> PyResult<()> {
+        BuildSpec::from_json(_build_json)
+            .and_then(|build| self.calc.load_build(&build))
+            .map_err(pyo3::exceptions::PyValueError::new_err)
+    }
+    fn set_formula_database(&mut self, _formula_json: &str) -> PyResult<()> {
+        let db = FormulaDatabase::from_json(_formula_json)
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        self.calc.set_formula_database(db);
+        Ok(())
+    }
+    fn set_formula_database_bytes(&mut self, _bytes: Vec<u8>) -> PyResult<()> {
+        let db = FormulaDatabase::from_bytes(&_bytes)
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        self.calc.set_formula_database(db);
+        Ok(())
+    }
+    fn reset_formula_database(&mut self) {
+        self.calc.set_formula_database(FormulaDatabase::default());
+    }
+    fn add_formula_patch(&mut self, _id: &str, _formula_json: &str) -> PyResult<()> {
+        let db = FormulaDatabase::from_json(_formula_json)
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        self.calc.add_formula_patch(_id, db);
+        Ok(())
+    }
+    fn get_formula_patches(&self) -> Vec<String> {
+        self.calc.patches.patch_ids()
+    }
+    fn use_formula_patch(&mut self, _id: &str) -> PyResult<()> {
+        self.calc
+            .use_formula_patch(_id)
+            .map_err(pyo3::exceptions::PyValueError::new_err)
+    }
+    fn diff_formula_patches(
+        &self,
+        _old_patch: &str,
+        _new_patch: &str,
+        _pvp: bool,
+    ) -> PyResult<PyPatchDiff> {
+        self.calc
+            .diff_formula_patches(_old_patch, _new_patch, _pvp)
+            .map(|diff| diff.into())
+            .map_err(pyo3::exceptions::PyValueError::new_err)
+    }
+    fn add_trait(&mut self, _perk: PyPerk) {
+        self.calc.add_perk(_perk.into());
+    }
+    fn remove_trait(&mut self, _perk_hash: u32) {
+        self.calc.remove_perk(_perk_hash);
+    }
+    fn stringify_weapon(&self) -> String {
+        format!("{:?}", self.calc.weapon)
+    }
+    fn set_stats(&mut self, _in: &PyDict) -> PyResult<()> {
+        self.calc.set_stats(py_stats(_in)?);
+        Ok(())
+    }
+    fn get_range_falloff(&self, _use_traits: bool, _pvp: bool) -> PyRangeResponse {
+        self.calc.get_weapon_range(_use_traits, _pvp).into()
+    }
+    fn get_handling_times(&self, _use_traits: bool, _pvp: bool) -> PyHandlingResponse {
+        self.calc.get_weapon_handling(_use_traits, _pvp).into()
+    }
+    fn get_firing_data(&self, _dynamic_traits: bool, _pvp: bool) -> PyFiringResponse {
+        self.calc
+            .get_weapon_firing_data(_dynamic_traits, _pvp)
+            .into()
+    }
+    fn get_dps(&self, _do_rpl_mult: bool) -> PyDpsResponse {
+        self.calc.get_weapon_dps(_do_rpl_mult).into()
+    }
+    fn get_dps_at_distance(&self, _distance: f64, _ads: bool, _do_rpl_mult: bool) -> PyDpsResponse {
+        let engagement = Engagement {
+            distance: _distance,
+            ads: _ads,
+        };
+        self.calc
+            .get_weapon_dps_at_distance(&engagement, _do_rpl_mult)
+            .into()
+    }
+    fn get_dps_for_duration(
+        &self,
+        _seconds: f64,
+        _do_rpl_mult: bool,
+        _mag: Option<i32>,
+        _reserves: Option<i32>,
+        _perk_values: Option<HashMap<u32, u32>>,
+        _kill_interval: Option<f64>,
+    ) -> PyDurationDpsResponse {
+        let starting_mag_state = StartingMagState {
+            mag: _mag,
+            reserves: _reserves,
+            perk_values: _perk_values.unwrap_or_default(),
+            kill_interval: _kill_interval,
+        };
+        self.calc
+            .get_weapon_dps_for_duration(_seconds, &starting_mag_state, _do_rpl_mult)
+            .into()
+    }
+    fn get_add_clear(
+        &self,
+        _count: u32,
+        _retarget_time: f64,
+        _do_rpl_mult: bool,
+    ) -> PyAddClearResponse {
+        let wave = AddClearWave {
+            count: _count,
+            retarget_time: _retarget_time,
+        };
+        self.calc.get_weapon_add_clear(&wave, _do_rpl_mult).into()
+    }
+    fn get_accuracy_dps(
+        &self,
+        _model: PyAccuracyModel,
+        _do_rpl_mult: bool,
+        _trials: Option<u32>,
+        _seed: Option<u64>,
+    ) -> PyAccuracyDpsResponse {
+        let settings = monte_carlo_settings(_trials, _seed);
+        self.calc
+            .get_weapon_accuracy_dps(&_model.into(), &settings, _do_rpl_mult)
+            .into()
+    }
+    fn get_accuracy_ttk(
+        &self,
+        _target: PyPvpTarget,
+        _model: PyAccuracyModel,
+        _trials: Option<u32>,
+        _seed: Option<u64>,
+    ) -> PyAccuracyTtkResponse {
+        let settings = monte_carlo_settings(_trials, _seed);
+        self.calc
+            .get_weapon_accuracy_ttk(&_target.into(), &_model.into(), &settings)
+            .into()
+    }
+    fn get_ttk(&self, _overshield: f64) -> Vec<PyResillienceSummary> {
+        self.calc
+            .get_weapon_ttk(_overshield)
+            .into_iter()
+            .map(|r| r.into())
+            .collect()
+    }
+    fn get_pve_ttk(&self) -> PyPveTtkResponse {
+        self.calc.get_weapon_pve_ttk().into()
+    }
+    fn get_pvp_ttk(&self, _target: PyPvpTarget) -> PyPvpTtkResponse {
+        self.calc.get_weapon_pvp_ttk(&_target.into()).into()
+    }
+    fn get_mirror_ttk(&self) -> PyPvpTtkResponse {
+        self.calc.get_weapon_mirror_ttk().into()
+    }
+    fn get_ttk_at_distance(
+        &self,
+        _overshield: f64,
+        _distance: f64,
+        _ads: bool,
+    ) -> Vec<PyResillienceSummary> {
+        let engagement = Engagement {
+            distance: _distance,
+            ads: _ads,
+        };
+        self.calc
+            .get_weapon_ttk_at_distance(_overshield, &engagement)
+            .into_iter()
+            .map(|r| r.into())
+            .collect()
+    }
+    fn get_pvp_ttk_at_distance(
+        &self,
+        _target: PyPvpTarget,
+        _distance: f64,
+        _ads: bool,
+    ) -> PyPvpTtkResponse {
+        let engagement = Engagement {
+            distance: _distance,
+            ads: _ads,
+        };
+        self.calc
+            .get_weapon_pvp_ttk_at_distance(&_target.into(), &engagement)
+            .into()
+    }
+    fn get_ttk_curve(&self, _target: PyPvpTarget, _ads: bool) -> PyTtkCurveResponse {
+        self.calc.get_weapon_ttk_curve(&_target.into(), _ads).into()
+    }
+    fn reverse_pve_calc(
+        &self,
+        _damage: f64,
+        _combatant_mult: Option<f64>,
+        _pve_mult: Option<f64>,
+    ) -> f64 {
+        self.calc
+            .reverse_pve_calc(_damage, _combatant_mult, _pve_mult)
+    }
+    fn set_activity(&mut self, _activity: PyActivity) -> PyResult<()> {
+        self.calc.activity.difficulty = _activity.difficulty_options()?;
+        self.calc.activity.cap = _activity.cap;
+        self.calc.activity.name = _activity.name;
+        self.calc.activity.rpl = _activity.rpl;
+        Ok(())
+    }
+    fn get_activity(&self) -> PyActivity {
+        self.calc.activity.clone().into()
+    }
+    fn set_player(&mut self, _player: PyPlayer) {
+        self.calc.set_player(_player.into());
+    }
+    fn get_player(&self) -> PyPlayer {
+        self.calc.activity.player.clone().into()
+    }
+    fn get_pl_delta_curve(&self) -> Vec<(i32, f64)> {
+        self.calc.get_pl_delta_curve()
+    }
+    fn set_enemy(&mut self, _enemy: PyEnemy) {
+        self.calc.enemy = _enemy.into();
+    }
+    fn get_enemy(&self) -> PyEnemy {
+        self.calc.enemy.clone().into()
+    }
+    fn set_enemy_type(&mut self, _enemy_type: PyEnemyType) {
+        self.calc.enemy.type_ = _enemy_type.into();
+    }
+    fn set_enemy_catalogue(&mut self, _catalogue_json: String) -> PyResult<()> {
+        let catalogue = EnemyCatalogue::from_json(&_catalogue_json)
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        self.calc.set_enemy_catalogue(catalogue);
+        Ok(())
+    }
+    fn set_enemy_archetype(&mut self, _id: u32) -> PyResult<()> {
+        self.calc
+            .set_enemy_archetype(_id)
+            .map_err(pyo3::exceptions::PyValueError::new_err)
+    }
+    fn set_ability(&mut self, _hash: u32) -> PyResult<()> {
+        self.calc
+            .set_ability(_hash)
+            .map_err(pyo3::exceptions::PyValueError::new_err)
+    }
+    fn get_ability_damage(&self, _pvp: bool) -> PyAbilityDamageResponse {
+        self.calc.get_ability_damage(_pvp).into()
+    }
+    fn get_ability_dps(&self, _pvp: bool) -> PyAbilityDpsResponse {
+        self.calc.get_ability_dps(_pvp).into()
+    }
+    fn set_logging_level(&mut self, _level: usize) {
+        self.calc.log_level = _level.into();
+    }
+}
+
 #[cfg(feature = "python")]
 #[pymodule]
 fn d2_calculation_api(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     register_weapon_interface(_py, m)?;
     register_activity_interface(_py, m)?;
     register_enemy_interface(_py, m)?;
+    register_ability_interface(_py, m)?;
+    m.add_class::<PyCalculator>()?;
     Ok(())
 }
@@ -32,12 +32,9 @@ impl Default for LogLevel {
     }
 }
 
-fn get_log_level() -> LogLevel {
-    crate::PERS_DATA.with(|perm_data| perm_data.borrow().log_level)
-}
-
-pub fn extern_log(s: &str, log_level: LogLevel) {
-    if log_level > get_log_level() {
+///Logs if the message is within the callers log level, usually a calculators
+pub fn extern_log(s: &str, log_level: LogLevel, max_level: LogLevel) {
+    if log_level > max_level {
         return;
     }
     #[cfg(feature = "wasm")]
@@ -46,6 +43,6 @@ pub fn extern_log(s: &str, log_level: LogLevel) {
     println!("{}", s);
 }
 
-pub fn log(s: &str, log_level: usize) {
-    extern_log(s, log_level.into())
+pub fn log(s: &str, log_level: usize, max_level: LogLevel) {
+    extern_log(s, log_level.into(), max_level)
 }
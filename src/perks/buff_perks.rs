@@ -1,28 +1,24 @@
 use std::collections::HashMap;
 
-use crate::d2_enums::{AmmoType, DamageType, StatHashes, WeaponType};
+use crate::d2_enums::{AmmoType, DamageSource, DamageType, StatHashes, WeaponType};
 
 use super::{
-    add_dmr, add_epr, add_fmr, add_hmr, add_mmr, add_rmr, add_rsmr, add_sbr, add_vmr, clamp,
+    clamp,
     lib::{
-        CalculationInput, DamageModifierResponse, ExtraDamageResponse, FiringModifierResponse,
+        CalculationInput, DamageModifierResponse, DamageResistModifierResponse,
+        ExtraDamageResponse, FiringModifierResponse,
         HandlingModifierResponse, RangeModifierResponse, RefundResponse, ReloadModifierResponse,
         ReloadOverrideResponse,
     },
-    ModifierResponseInput, Perks,
+    simulation_state::{BuffCategory, SimulationState},
+    ModifierResponseInput, Perks, PersistentModifierResponses,
 };
 
-fn emp_buff(_cached_data: &mut HashMap<String, f64>, _desired_buff: f64) -> f64 {
-    let current_buff = _cached_data.get("empowering").unwrap_or(&1.0).to_owned();
-    if current_buff >= _desired_buff {
-        return 1.0;
-    } else {
-        _cached_data.insert("empowering".to_string(), _desired_buff);
-        return _desired_buff / current_buff;
-    }
+fn emp_buff(_sim_state: &mut SimulationState, _desired_buff: f64) -> f64 {
+    _sim_state.stack_buff(BuffCategory::Empowering, _desired_buff)
 }
 
-fn srg_buff(_cached_data: &mut HashMap<String, f64>, _desired_tier: u32, _is_pvp: bool) -> f64 {
+fn srg_buff(_sim_state: &mut SimulationState, _desired_tier: u32, _is_pvp: bool) -> f64 {
     let pve_buff = match _desired_tier {
         0 => 1.0,
         1 => 1.1,
@@ -38,77 +34,86 @@ fn srg_buff(_cached_data: &mut HashMap<String, f64>, _desired_tier: u32, _is_pvp
         _ => 1.06,
     };
     let buff = if _is_pvp { pvp_buff } else { pve_buff };
-    let current_buff = _cached_data.get("surge").unwrap_or(&1.0).to_owned();
-    if current_buff >= buff {
-        return 1.0;
-    } else {
-        _cached_data.insert("surge".to_string(), buff);
-        return buff / current_buff;
-    }
+    _sim_state.stack_buff(BuffCategory::Surge, buff)
 }
 
-fn gbl_debuff(_cached_data: &mut HashMap<String, f64>, _desired_buff: f64) -> f64 {
-    let current_buff = _cached_data.get("debuff").unwrap_or(&1.0).to_owned();
-    if current_buff >= _desired_buff {
-        return 1.0;
-    } else {
-        _cached_data.insert("debuff".to_string(), _desired_buff);
-        return _desired_buff / current_buff;
-    }
+fn gbl_debuff(_sim_state: &mut SimulationState, _desired_buff: f64) -> f64 {
+    _sim_state.stack_buff(BuffCategory::Debuff, _desired_buff)
 }
 
 //surge mod dmr is in meta_perks.rs
 
+///Empowering buffs and debuffs that scale ability damage as well as weapon damage
+pub const GLOBAL_BUFFS: [Perks; 9] = [
+    Perks::WellOfRadiance,
+    Perks::NobleRounds,
+    Perks::Radiant,
+    Perks::BannerShield,
+    Perks::EmpRift,
+    Perks::WardOfDawn,
+    Perks::UmbralSharpening,
+    Perks::Weaken,
+    Perks::EnhancedScannerAugment,
+];
+
+///Armor surges, these only scale abilities of the players subclass element
+pub const SURGE_BUFFS: [Perks; 5] = [
+    Perks::PathOfTheBurningSteps,
+    Perks::EternalWarrior,
+    Perks::MantleOfBattleHarmony,
+    Perks::SanguineAlchemy,
+    Perks::MaskOfBakris,
+];
+
 //
 // BUFFS
 //
-pub fn buff_perks() {
-    add_dmr(
+pub fn buff_perks(map: &mut PersistentModifierResponses) {
+    map.add_dmr(
         Perks::WellOfRadiance,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
-            let buff = emp_buff(_input.cached_data, 1.25);
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
+            let buff = emp_buff(_input.sim_state, 1.25);
             DamageModifierResponse {
                 impact_dmg_scale: buff,
                 explosive_dmg_scale: buff,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::NobleRounds,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             if _input.value == 0 {
                 return DamageModifierResponse::default();
             }
             let des_buff = if _input.pvp { 1.15 } else { 1.35 };
-            let buff = emp_buff(_input.cached_data, des_buff);
+            let buff = emp_buff(_input.sim_state, des_buff);
             DamageModifierResponse {
                 impact_dmg_scale: buff,
                 explosive_dmg_scale: buff,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::Radiant,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let des_buff = if _input.pvp { 1.1 } else { 1.25 };
-            let buff = emp_buff(_input.cached_data, des_buff);
-            _input.cached_data.insert("radiant".to_string(), 1.0);
+            let buff = emp_buff(_input.sim_state, des_buff);
             DamageModifierResponse {
                 impact_dmg_scale: buff,
                 explosive_dmg_scale: buff,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::PathOfTheBurningSteps,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
-            let buff = srg_buff(_input.cached_data, _input.value, _input.pvp);
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
+            let buff = srg_buff(_input.sim_state, _input.value, _input.pvp);
             DamageModifierResponse {
                 impact_dmg_scale: if *_input.calc_data.damage_type == DamageType::SOLAR {
                     buff
@@ -122,13 +127,13 @@ pub fn buff_perks() {
                 },
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::EternalWarrior,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
-            let buff = srg_buff(_input.cached_data, _input.value, _input.pvp);
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
+            let buff = srg_buff(_input.sim_state, _input.value, _input.pvp);
             DamageModifierResponse {
                 impact_dmg_scale: if *_input.calc_data.damage_type == DamageType::ARC {
                     buff
@@ -142,226 +147,265 @@ pub fn buff_perks() {
                 },
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::BannerShield,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let des_buff = if _input.pvp { 1.35 } else { 1.4 };
-            let buff = emp_buff(_input.cached_data, des_buff);
+            let buff = emp_buff(_input.sim_state, des_buff);
             DamageModifierResponse {
                 impact_dmg_scale: buff,
                 explosive_dmg_scale: buff,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::EmpRift,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let des_buff = if _input.pvp { 1.15 } else { 1.2 };
-            let buff = emp_buff(_input.cached_data, des_buff);
+            let buff = emp_buff(_input.sim_state, des_buff);
             DamageModifierResponse {
                 impact_dmg_scale: buff,
                 explosive_dmg_scale: buff,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::MantleOfBattleHarmony,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let tier = if _input.value > 0 { 4 } else { 0 };
-            let buff = srg_buff(_input.cached_data, tier, _input.pvp);
+            let buff = srg_buff(_input.sim_state, tier, _input.pvp);
             DamageModifierResponse {
                 impact_dmg_scale: buff,
                 explosive_dmg_scale: buff,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::SanguineAlchemy,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let tier = if _input.value > 0 { 2 } else { 0 };
-            let buff = srg_buff(_input.cached_data, tier, _input.pvp);
+            let buff = srg_buff(_input.sim_state, tier, _input.pvp);
             DamageModifierResponse {
                 impact_dmg_scale: buff,
                 explosive_dmg_scale: buff,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::WardOfDawn,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
-            let buff = emp_buff(_input.cached_data, 1.25);
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
+            let buff = emp_buff(_input.sim_state, 1.25);
             DamageModifierResponse {
                 impact_dmg_scale: buff,
                 explosive_dmg_scale: buff,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::Gyrfalcon,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let des_buff = if _input.pvp { 1.0 } else { 1.35 };
-            let buff = emp_buff(_input.cached_data, des_buff);
+            let buff = emp_buff(_input.sim_state, des_buff);
             DamageModifierResponse {
                 impact_dmg_scale: buff,
                 explosive_dmg_scale: buff,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::AeonInsight,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let des_buff = if _input.pvp { 1.0 } else { 1.35 };
-            let buff = emp_buff(_input.cached_data, des_buff);
+            let buff = emp_buff(_input.sim_state, des_buff);
             DamageModifierResponse {
                 impact_dmg_scale: buff,
                 explosive_dmg_scale: buff,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::UmbralSharpening,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let pve_values = [1.2, 1.25, 1.35, 1.4];
             let des_buff = if _input.pvp {
                 1.0
             } else {
                 pve_values[clamp(_input.value, 0, 3) as usize]
             };
-            let buff = emp_buff(_input.cached_data, des_buff);
+            let buff = emp_buff(_input.sim_state, des_buff);
             DamageModifierResponse {
                 impact_dmg_scale: buff,
                 explosive_dmg_scale: buff,
                 ..Default::default()
             }
-        }),
+        },
     );
 
     //
     // DEBUFFS
     //
 
-    add_dmr(
+    map.add_dmr(
         Perks::Weaken,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let des_debuff = if _input.pvp { 1.075 } else { 1.15 };
-            let debuff = gbl_debuff(_input.cached_data, des_debuff);
+            let debuff = gbl_debuff(_input.sim_state, des_debuff);
             DamageModifierResponse {
                 impact_dmg_scale: debuff,
                 explosive_dmg_scale: debuff,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::TractorCannon,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let des_debuff = if _input.pvp { 1.5 } else { 1.3 };
-            let debuff = gbl_debuff(_input.cached_data, des_debuff);
+            let debuff = gbl_debuff(_input.sim_state, des_debuff);
             DamageModifierResponse {
                 impact_dmg_scale: debuff,
                 explosive_dmg_scale: debuff,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::MoebiusQuiver,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let des_debuff = if _input.pvp { 1.5 } else { 1.3 };
-            let debuff = gbl_debuff(_input.cached_data, des_debuff);
+            let debuff = gbl_debuff(_input.sim_state, des_debuff);
             DamageModifierResponse {
                 impact_dmg_scale: debuff,
                 explosive_dmg_scale: debuff,
                 ..Default::default()
             }
-        }),
+        },
     );
-    add_dmr(
+    map.add_dmr(
         Perks::DeadFall,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let des_debuff = if _input.pvp { 1.5 } else { 1.3 };
-            let debuff = gbl_debuff(_input.cached_data, des_debuff);
+            let debuff = gbl_debuff(_input.sim_state, des_debuff);
             DamageModifierResponse {
                 impact_dmg_scale: debuff,
                 explosive_dmg_scale: debuff,
                 ..Default::default()
             }
-        }),
+        },
     );
-    add_dmr(
+    map.add_dmr(
         Perks::Felwinters,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
-            let debuff = gbl_debuff(_input.cached_data, 1.3);
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
+            let debuff = gbl_debuff(_input.sim_state, 1.3);
             DamageModifierResponse {
                 impact_dmg_scale: debuff,
                 explosive_dmg_scale: debuff,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::EnhancedScannerAugment,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let pve_values = [1.08, 1.137, 1.173, 1.193, 1.2];
             let des_debuff = if _input.pvp {
                 1.0
             } else {
                 pve_values[clamp(_input.value, 0, 4) as usize]
             };
-            let debuff = gbl_debuff(_input.cached_data, des_debuff);
+            let debuff = gbl_debuff(_input.sim_state, des_debuff);
             DamageModifierResponse {
                 impact_dmg_scale: debuff,
                 explosive_dmg_scale: debuff,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::MaskOfBakris,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut tier = if _input.value > 0 { 4 } else { 0 };
             if *_input.calc_data.damage_type != DamageType::ARC && *_input.calc_data.damage_type != DamageType::STASIS {
                 tier = 0;
             }
-            let buff = srg_buff(_input.cached_data, tier, _input.pvp);
+            let buff = srg_buff(_input.sim_state, tier, _input.pvp);
             DamageModifierResponse {
                 impact_dmg_scale: buff,
                 explosive_dmg_scale: buff,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::RaijusHarness,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let buff = if _input.value > 0 && *_input.calc_data.damage_type == DamageType::ARC { 1.15 } else { 1.0 };
             DamageModifierResponse {
                 impact_dmg_scale: buff,
                 explosive_dmg_scale: buff,
                 ..Default::default()
             }
-        }),
+        },
+    );
+
+    //
+    // TARGET RESISTS
+    //
+    map.add_drmr(
+        Perks::FrostArmor,
+        |_input: ModifierResponseInput| -> DamageResistModifierResponse {
+            let resist = 1.0 - clamp(_input.value, 0, 5) as f64 * 0.05;
+            DamageResistModifierResponse {
+                body_shot_resist: resist,
+                head_shot_resist: resist,
+                ..Default::default()
+            }
+        },
+    );
+
+    map.add_drmr(
+        Perks::WovenMail,
+        |_input: ModifierResponseInput| -> DamageResistModifierResponse {
+            //precision damage goes through woven mail
+            DamageResistModifierResponse {
+                body_shot_resist: 0.55,
+                ..Default::default()
+            }
+        },
+    );
+
+    map.add_drmr(
+        Perks::ConcussiveDampener,
+        |_input: ModifierResponseInput| -> DamageResistModifierResponse {
+            //only blast damage, so rockets and grenade launchers
+            DamageResistModifierResponse {
+                body_shot_resist: 0.9,
+                head_shot_resist: 0.9,
+                source: Some(DamageSource::EXPLOSION),
+                ..Default::default()
+            }
+        },
     );
 }
@@ -4,20 +4,19 @@ use std::collections::HashMap;
 use crate::{d2_enums::StatHashes, enemies::EnemyType, weapons::Stat};
 
 use super::{
-    add_dmr, add_edr, add_epr, add_fmr, add_hmr, add_mmr, add_rmr, add_rr, add_rsmr, add_sbr,
-    add_vmr, clamp,
+    clamp,
     lib::{
         CalculationInput, DamageModifierResponse, ExtraDamageResponse, FiringModifierResponse,
         HandlingModifierResponse, InventoryModifierResponse, MagazineModifierResponse,
         RangeModifierResponse, RefundResponse, ReloadModifierResponse, ReloadOverrideResponse,
     },
-    ModifierResponseInput, Perks,
+    ModifierResponseInput, Perks, PersistentModifierResponses,
 };
 
-pub fn exotic_perks() {
-    add_dmr(
+pub fn exotic_perks(map: &mut PersistentModifierResponses) {
+    map.add_dmr(
         Perks::ParacausalShot,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let bufflist_pve = vec![1.0, 3.92, 4.0, 4.4, 5.25, 7.67, 11.71, 18.36];
             let bufflist_pvp = vec![1.0, 1.01, 1.03, 1.13, 1.41, 1.96, 3.0, 4.73];
             let mut damage_buff = 1.0;
@@ -44,12 +43,12 @@ pub fn exotic_perks() {
                 explosive_dmg_scale: damage_buff,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::HuntersTrance,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut out = HashMap::new();
             let inter_val = *_input
                 .calc_data
@@ -61,12 +60,12 @@ pub fn exotic_perks() {
             out.insert(StatHashes::RANGE.into(), buff_val);
             out.insert(StatHashes::HANDLING.into(), buff_val);
             out
-        }),
+        },
     );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::HuntersTrance,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             let inter_val = *_input
                 .calc_data
                 .perk_value_map
@@ -77,12 +76,12 @@ pub fn exotic_perks() {
                 reload_stat_add: buff_val,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_rmr(
+    map.add_rmr(
         Perks::HuntersTrance,
-        Box::new(|_input: ModifierResponseInput| -> RangeModifierResponse {
+        |_input: ModifierResponseInput| -> RangeModifierResponse {
             let inter_val = *_input
                 .calc_data
                 .perk_value_map
@@ -93,30 +92,28 @@ pub fn exotic_perks() {
                 range_stat_add: buff_val,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_hmr(
+    map.add_hmr(
         Perks::HuntersTrance,
-        Box::new(
-            |_input: ModifierResponseInput| -> HandlingModifierResponse {
-                let inter_val = *_input
-                    .calc_data
-                    .perk_value_map
-                    .get(&213689231)
-                    .unwrap_or(&0);
-                let buff_val = (clamp(inter_val, 0, 7) * 5) as i32;
-                HandlingModifierResponse {
-                    stat_add: buff_val,
-                    ..Default::default()
-                }
-            },
-        ),
+        |_input: ModifierResponseInput| -> HandlingModifierResponse {
+            let inter_val = *_input
+                .calc_data
+                .perk_value_map
+                .get(&213689231)
+                .unwrap_or(&0);
+            let buff_val = (clamp(inter_val, 0, 7) * 5) as i32;
+            HandlingModifierResponse {
+                stat_add: buff_val,
+                ..Default::default()
+            }
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::MementoMori,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut damage_buff = 1.0;
             if _input.value > 0 && _input.calc_data.total_shots_fired < 7.0 {
                 damage_buff = if _input.pvp { 1.5 } else { 1.285 };
@@ -126,12 +123,12 @@ pub fn exotic_perks() {
                 explosive_dmg_scale: damage_buff,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::AgersCall,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut damage_buff = 1.0;
             if _input.value > 0 && _input.calc_data.num_reloads == 0.0 {
                 damage_buff = 1.8;
@@ -141,39 +138,37 @@ pub fn exotic_perks() {
                 explosive_dmg_scale: damage_buff,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
-    add_mmr(
+    map.add_mmr(
         Perks::AgersCall,
-        Box::new(
-            |_input: ModifierResponseInput| -> MagazineModifierResponse {
-                let mut mag_buff = 1.0;
-                if _input.value > 0 && _input.calc_data.total_shots_fired == 0.0 {
-                    mag_buff = 2.0;
-                };
-                MagazineModifierResponse {
-                    magazine_scale: mag_buff,
-                    ..Default::default()
-                }
-            },
-        ),
+        |_input: ModifierResponseInput| -> MagazineModifierResponse {
+            let mut mag_buff = 1.0;
+            if _input.value > 0 && _input.calc_data.total_shots_fired == 0.0 {
+                mag_buff = 2.0;
+            };
+            MagazineModifierResponse {
+                magazine_scale: mag_buff,
+                ..Default::default()
+            }
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::Roadborn,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut out = HashMap::new();
             if _input.value > 0 {
                 out.insert(StatHashes::HANDLING.into(), 20);
                 out.insert(StatHashes::RELOAD.into(), 40);
             };
             out
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::Roadborn,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut crit_mult = 1.0;
             if _input.value > 0 {
                 crit_mult = 1.17;
@@ -183,12 +178,12 @@ pub fn exotic_perks() {
                 explosive_dmg_scale: 1.0,
                 impact_dmg_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_fmr(
+    map.add_fmr(
         Perks::Roadborn,
-        Box::new(|_input: ModifierResponseInput| -> FiringModifierResponse {
+        |_input: ModifierResponseInput| -> FiringModifierResponse {
             let mut delay_mult = 1.0;
             if _input.value > 0 {
                 delay_mult = 0.583;
@@ -199,12 +194,12 @@ pub fn exotic_perks() {
                 inner_burst_scale: 1.0,
                 burst_size_add: 0.0,
             }
-        }),
+        },
     );
 
-    add_rmr(
+    map.add_rmr(
         Perks::Roadborn,
-        Box::new(|_input: ModifierResponseInput| -> RangeModifierResponse {
+        |_input: ModifierResponseInput| -> RangeModifierResponse {
             let mut range_scale = 1.05;
             if _input.value > 0 {
                 range_scale = 1.15; //roughly
@@ -215,12 +210,12 @@ pub fn exotic_perks() {
                 range_hip_scale: 1.0,
                 range_zoom_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::Roadborn,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             let mut reload = 0;
             if _input.value > 0 {
                 reload = 40;
@@ -229,12 +224,12 @@ pub fn exotic_perks() {
                 reload_stat_add: reload,
                 reload_time_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_fmr(
+    map.add_fmr(
         Perks::ReignHavoc,
-        Box::new(|_input: ModifierResponseInput| -> FiringModifierResponse {
+        |_input: ModifierResponseInput| -> FiringModifierResponse {
             let mut delay_mult = 1.0;
             if _input.calc_data.shots_fired_this_mag >= _input.calc_data.base_mag * 0.2 {
                 delay_mult = 0.75;
@@ -248,12 +243,12 @@ pub fn exotic_perks() {
                 inner_burst_scale: 1.0,
                 burst_size_add: 0.0,
             }
-        }),
+        },
     );
 
-    add_edr(
+    map.add_edr(
         Perks::ReignHavoc,
-        Box::new(|_input: ModifierResponseInput| -> ExtraDamageResponse {
+        |_input: ModifierResponseInput| -> ExtraDamageResponse {
             let dmg = if _input.pvp { 65.0 } else { 65.0 * 1.3 };
             ExtraDamageResponse {
                 additive_damage: dmg,
@@ -266,24 +261,24 @@ pub fn exotic_perks() {
                 crit_scale: false,
                 combatant_scale: true,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::WormsHunger,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let val = clamp(_input.value, 0, 20);
             DamageModifierResponse {
                 impact_dmg_scale: 1.0 + (val as f64) * 0.1,
                 explosive_dmg_scale: 1.0 + (val as f64) * 0.1,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::LagragianSight,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut damage_buff = 1.0;
             if _input.value > 0 && _input.calc_data.time_total < 30.0 {
                 damage_buff = 1.4;
@@ -293,12 +288,12 @@ pub fn exotic_perks() {
                 explosive_dmg_scale: damage_buff,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::ToM,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut damage_buff = 1.0;
             if _input.calc_data.curr_mag == 1.0 {
                 damage_buff = 2.0;
@@ -308,12 +303,12 @@ pub fn exotic_perks() {
                 explosive_dmg_scale: damage_buff,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_rr(
+    map.add_rr(
         Perks::ToM,
-        Box::new(|_input: ModifierResponseInput| -> RefundResponse {
+        |_input: ModifierResponseInput| -> RefundResponse {
             RefundResponse {
                 refund_mag: if _input.calc_data.curr_mag == 0.0 {
                     1
@@ -324,12 +319,12 @@ pub fn exotic_perks() {
                 crit: false,
                 requirement: 1,
             }
-        }),
+        },
     );
 
-    add_edr(
+    map.add_edr(
         Perks::RocketTracers,
-        Box::new(|_input: ModifierResponseInput| -> ExtraDamageResponse {
+        |_input: ModifierResponseInput| -> ExtraDamageResponse {
             let dmg = if _input.pvp { 24.0 } else { 105.0 };
             ExtraDamageResponse {
                 additive_damage: dmg,
@@ -342,7 +337,7 @@ pub fn exotic_perks() {
                 crit_scale: false,
                 combatant_scale: true,
             }
-        }),
+        },
     );
 
     // add_edr_guidance_ring(
@@ -395,32 +390,32 @@ pub fn exotic_perks() {
     //     };
     // }
 
-    add_fmr(
+    map.add_fmr(
         Perks::HakkeHeavyBurst,
-        Box::new(|_input: ModifierResponseInput| -> FiringModifierResponse {
+        |_input: ModifierResponseInput| -> FiringModifierResponse {
             FiringModifierResponse {
                 burst_size_add: -2.0,
                 burst_delay_add: -1.0 / 30.0,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::HakkeHeavyBurst,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let crit_scale = (1.5 + 5.0 / 51.0) / _input.calc_data.base_crit_mult;
             DamageModifierResponse {
                 explosive_dmg_scale: 1.48,
                 impact_dmg_scale: 1.48,
                 crit_scale,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::SwoopingTalons,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut dmg_mult = 1.0;
             if _input.value > 0 {
                 dmg_mult = 1.4;
@@ -432,11 +427,11 @@ pub fn exotic_perks() {
                 explosive_dmg_scale: dmg_mult,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
-    add_dmr(
+    map.add_dmr(
         Perks::IgnitionTrigger,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut dmg_mult = 1.0;
             if _input.value > 0 || _input.calc_data.total_shots_fired > 20.0 {
                 dmg_mult = if _input.pvp { 1.55 } else { 1.99 };
@@ -446,12 +441,12 @@ pub fn exotic_perks() {
                 explosive_dmg_scale: dmg_mult,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::CalculatedBalance,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut damage_mult = if _input.value > 0 { 0.2 } else { 0.0 };
             let duration = 5.0;
             if _input.calc_data.time_total > duration {
@@ -462,12 +457,12 @@ pub fn exotic_perks() {
                 explosive_dmg_scale: 1.0 + damage_mult,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_fmr(
+    map.add_fmr(
         Perks::RavenousBeast,
-        Box::new(|_input: ModifierResponseInput| -> FiringModifierResponse {
+        |_input: ModifierResponseInput| -> FiringModifierResponse {
             if _input.value > 0 {
                 FiringModifierResponse {
                     burst_delay_scale: 0.8,
@@ -476,12 +471,12 @@ pub fn exotic_perks() {
             } else {
                 FiringModifierResponse::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::RavenousBeast,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut damage_mult = 1.0;
             let mut crit_mult = 1.0;
             if _input.value > 0 {
@@ -497,12 +492,12 @@ pub fn exotic_perks() {
                 explosive_dmg_scale: damage_mult,
                 crit_scale: crit_mult,
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::ReleaseTheWolves,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let has_cat = _input.calc_data.perk_value_map.contains_key(&431220296);
             let mut out = HashMap::new();
             if has_cat {
@@ -513,12 +508,12 @@ pub fn exotic_perks() {
                 }
             }
             out
-        }),
+        },
     );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::ReleaseTheWolves,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             let has_cat = _input.calc_data.perk_value_map.contains_key(&431220296);
             if _input.value == 1 && has_cat {
                 ReloadModifierResponse {
@@ -528,12 +523,12 @@ pub fn exotic_perks() {
             } else {
                 ReloadModifierResponse::default()
             }
-        }),
+        },
     );
 
-    add_fmr(
+    map.add_fmr(
         Perks::ReleaseTheWolves,
-        Box::new(|_input: ModifierResponseInput| -> FiringModifierResponse {
+        |_input: ModifierResponseInput| -> FiringModifierResponse {
             if _input.value > 0 {
                 FiringModifierResponse {
                     burst_delay_scale: 0.4,
@@ -542,24 +537,24 @@ pub fn exotic_perks() {
             } else {
                 FiringModifierResponse::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::ReleaseTheWolves,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let damage_mult = if _input.value > 0 { 1.4 } else { 1.0 };
             DamageModifierResponse {
                 impact_dmg_scale: damage_mult,
                 explosive_dmg_scale: damage_mult,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::Fundamentals,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut stats = HashMap::new();
             if _input.value == 1 {
                 stats.insert(StatHashes::STABILITY.into(), 20);
@@ -572,28 +567,26 @@ pub fn exotic_perks() {
                 stats.insert(StatHashes::HANDLING.into(), 25);
             };
             stats
-        }),
+        },
     );
 
-    add_hmr(
+    map.add_hmr(
         Perks::Fundamentals,
-        Box::new(
-            |_input: ModifierResponseInput| -> HandlingModifierResponse {
-                let mut handling = 0;
-                if _input.value == 3 {
-                    handling = 25;
-                }
-                HandlingModifierResponse {
-                    stat_add: handling,
-                    ..Default::default()
-                }
-            },
-        ),
+        |_input: ModifierResponseInput| -> HandlingModifierResponse {
+            let mut handling = 0;
+            if _input.value == 3 {
+                handling = 25;
+            }
+            HandlingModifierResponse {
+                stat_add: handling,
+                ..Default::default()
+            }
+        },
     );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::Fundamentals,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             let mut reload = 0;
             if _input.value == 2 {
                 reload = 35;
@@ -602,12 +595,12 @@ pub fn exotic_perks() {
                 reload_stat_add: reload,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_rmr(
+    map.add_rmr(
         Perks::Fundamentals,
-        Box::new(|_input: ModifierResponseInput| -> RangeModifierResponse {
+        |_input: ModifierResponseInput| -> RangeModifierResponse {
             let mut range = 0;
             if _input.value == 3 {
                 range = 5;
@@ -616,23 +609,23 @@ pub fn exotic_perks() {
                 range_stat_add: range,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::ThinTheHerd,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut out = HashMap::new();
             if _input.value > 0 {
                 out.insert(StatHashes::RELOAD.into(), 70);
             }
             out
-        }),
+        },
     );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::ThinTheHerd,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             if _input.value > 0 {
                 ReloadModifierResponse {
                     reload_stat_add: 70,
@@ -641,39 +634,37 @@ pub fn exotic_perks() {
             } else {
                 ReloadModifierResponse::default()
             }
-        }),
+        },
     );
 
-    add_hmr(
+    map.add_hmr(
         Perks::Chimera,
-        Box::new(
-            |_input: ModifierResponseInput| -> HandlingModifierResponse {
-                if _input.value > 0 {
-                    HandlingModifierResponse {
-                        stat_add: 100,
-                        ..Default::default()
-                    }
-                } else {
-                    HandlingModifierResponse::default()
+        |_input: ModifierResponseInput| -> HandlingModifierResponse {
+            if _input.value > 0 {
+                HandlingModifierResponse {
+                    stat_add: 100,
+                    ..Default::default()
                 }
-            },
-        ),
+            } else {
+                HandlingModifierResponse::default()
+            }
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::Chimera,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut out = HashMap::new();
             if _input.value > 0 {
                 out.insert(StatHashes::RELOAD.into(), 100);
             }
             out
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::FirstGlance,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut damage_mult = 1.0;
             let mut crit_mult = 1.0;
             if _input.value > 0 {
@@ -688,12 +679,12 @@ pub fn exotic_perks() {
                 impact_dmg_scale: damage_mult,
                 crit_scale: crit_mult,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::FateOfAllFools,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut damage_mult = 1.0;
             let mut crit_mult = 1.0;
             if _input.value as f64 > _input.calc_data.total_shots_fired {
@@ -706,12 +697,12 @@ pub fn exotic_perks() {
                 impact_dmg_scale: damage_mult,
                 crit_scale: crit_mult,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::HonedEdge,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut damage_mult = 1.0;
             let has_cat = _input.calc_data.perk_value_map.contains_key(&529188544);
             if _input.value == 2 {
@@ -728,12 +719,12 @@ pub fn exotic_perks() {
                 impact_dmg_scale: damage_mult,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::TakenPredator,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut damage_mult = 1.0;
             if _input.value == 1 || _input.value == 2 {
                 damage_mult = 1.25;
@@ -745,12 +736,12 @@ pub fn exotic_perks() {
                 impact_dmg_scale: damage_mult,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::MarkovChain,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let val = clamp(_input.value, 0, 5);
             let damage_mult = (1.0 / 15.0) * val as f64;
             DamageModifierResponse {
@@ -758,12 +749,12 @@ pub fn exotic_perks() {
                 impact_dmg_scale: 1.0 + damage_mult,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::StormAndStress,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut damage_mult = 1.0;
             if _input.value > 0 {
                 damage_mult = if _input.pvp { 3.62 } else { 1.8 };
@@ -773,12 +764,12 @@ pub fn exotic_perks() {
                 impact_dmg_scale: damage_mult,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_rmr(
+    map.add_rmr(
         Perks::DualSpeedReceiver,
-        Box::new(|_input: ModifierResponseInput| -> RangeModifierResponse {
+        |_input: ModifierResponseInput| -> RangeModifierResponse {
             let zoom_stat = _input
                 .calc_data
                 .stats
@@ -795,35 +786,35 @@ pub fn exotic_perks() {
             } else {
                 RangeModifierResponse::default()
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::DualSpeedReceiver,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut out = HashMap::new();
             if _input.value > 0 {
                 out.insert(StatHashes::ZOOM.into(), 3);
                 out.insert(StatHashes::RANGE.into(), 30);
             }
             out
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::FullStop,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             DamageModifierResponse {
                 explosive_dmg_scale: 1.0,
                 impact_dmg_scale: 1.0,
                 crit_scale: if !_input.pvp { 2.9 } else { 1.0 },
             }
-        }),
+        },
     );
 
-    add_fmr(
+    map.add_fmr(
         Perks::RatPack,
-        Box::new(|_input: ModifierResponseInput| -> FiringModifierResponse {
+        |_input: ModifierResponseInput| -> FiringModifierResponse {
             let val;
             if _input.value > 0 {
                 val = clamp(_input.value - 1, 0, 4);
@@ -834,84 +825,82 @@ pub fn exotic_perks() {
                 burst_delay_add: val as f64 * (-0.625 / 30.0),
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_mmr(
+    map.add_mmr(
         Perks::RatPack,
-        Box::new(
-            |_input: ModifierResponseInput| -> MagazineModifierResponse {
-                let val = clamp(_input.value - 1, 0, 4);
-                MagazineModifierResponse {
-                    magazine_add: val as f64 * if val == 4 { 2.25 } else { 2.0 },
-                    ..Default::default()
-                }
-            },
-        ),
+        |_input: ModifierResponseInput| -> MagazineModifierResponse {
+            let val = clamp(_input.value - 1, 0, 4);
+            MagazineModifierResponse {
+                magazine_add: val as f64 * if val == 4 { 2.25 } else { 2.0 },
+                ..Default::default()
+            }
+        },
     );
 
-    add_fmr(
+    map.add_fmr(
         Perks::RideTheBull,
-        Box::new(|_input: ModifierResponseInput| -> FiringModifierResponse {
+        |_input: ModifierResponseInput| -> FiringModifierResponse {
             let extra_value = _input.calc_data.shots_fired_this_mag as f64 / 10.0;
             let val = clamp(_input.value + extra_value as u32, 0, 2);
             FiringModifierResponse {
                 burst_delay_add: val as f64 * (-0.25 / 30.0),
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_fmr(
+    map.add_fmr(
         Perks::SpinningUp,
-        Box::new(|_input: ModifierResponseInput| -> FiringModifierResponse {
+        |_input: ModifierResponseInput| -> FiringModifierResponse {
             let extra_value = _input.calc_data.shots_fired_this_mag as f64 / 12.0;
             let val = clamp(_input.value + extra_value as u32, 0, 2);
             FiringModifierResponse {
                 burst_delay_add: val as f64 * (-0.5 / 30.0),
                 ..Default::default()
             }
-        }),
+        },
     );
 
 
-    add_sbr(
+    map.add_sbr(
         Perks::CranialSpike,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut out = HashMap::new();
             let val = clamp(_input.value, 0, 5) as i32;
             out.insert(StatHashes::RANGE.into(), 8*val);
             out.insert(StatHashes::AIM_ASSIST.into(), 4*val);
             out
-        }),
+        },
     );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::CranialSpike,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             let val = clamp(_input.value, 0, 5) as i32;
             let rel = 0.97_f64.powi(val);
             ReloadModifierResponse {
                 reload_time_scale: rel,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_rmr(
+    map.add_rmr(
         Perks::CranialSpike,
-        Box::new(|_input: ModifierResponseInput| -> RangeModifierResponse {
+        |_input: ModifierResponseInput| -> RangeModifierResponse {
             let val = clamp(_input.value, 0, 5) as i32;
             RangeModifierResponse {
                 range_stat_add: 8*val,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_fmr(
+    map.add_fmr(
         Perks::DarkForgedTrigger,
-        Box::new(|_input: ModifierResponseInput| -> FiringModifierResponse {
+        |_input: ModifierResponseInput| -> FiringModifierResponse {
             if _input.value == 0 {
                 if _input.calc_data.perk_value_map.get(&1319823571).unwrap_or(&0) > &4 {
                     FiringModifierResponse {
@@ -927,18 +916,18 @@ pub fn exotic_perks() {
             } else {
                 FiringModifierResponse::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::BlackHole,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let buff = ((_input.calc_data.shots_fired_this_mag+1f64) % 2.0) * 0.35;
             DamageModifierResponse {
                 explosive_dmg_scale: 1.0 + buff,
                 impact_dmg_scale: 1.0 + buff,
                 ..Default::default()
             }
-        }),
+        },
     )
 }
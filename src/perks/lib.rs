@@ -1,6 +1,7 @@
 use crate::{
+    activity::PlayerState,
     d2_enums::{AmmoType, BungieHash, DamageSource, DamageType, StatBump, StatHashes, WeaponType},
-    enemies::EnemyType,
+    enemies::{champion::ChampionType, EnemyType},
     types::rs_types::{FiringData, HandlingResponse},
     weapons::Stat,
 };
@@ -29,6 +30,7 @@ pub struct CalculationInput<'a> {
     pub enemy_type: &'a EnemyType,
     pub perk_value_map: &'a HashMap<u32, u32>,
     pub has_overshield: bool,
+    pub player: &'a PlayerState,
 }
 impl<'a> CalculationInput<'a> {
     //stuff like mag size can use this, not reload, damage, etc.
@@ -45,6 +47,7 @@ impl<'a> CalculationInput<'a> {
         _base_mag_size: i32,
         _total_shots_hit: i32,
         _total_time: f64,
+        _player: &'a PlayerState,
     ) -> Self {
         Self {
             intrinsic_hash: _intrinsic_hash,
@@ -67,6 +70,7 @@ impl<'a> CalculationInput<'a> {
             enemy_type: &EnemyType::BOSS,
             perk_value_map: _perk_value_map,
             has_overshield: false,
+            player: _player,
         }
     }
     pub fn construct_pvp(
@@ -81,6 +85,7 @@ impl<'a> CalculationInput<'a> {
         _mag_size: f64,
         _has_overshield: bool,
         _handling_data: HandlingResponse,
+        _player: &'a PlayerState,
     ) -> Self {
         Self {
             intrinsic_hash: _intrinsic_hash,
@@ -103,6 +108,7 @@ impl<'a> CalculationInput<'a> {
             enemy_type: &EnemyType::PLAYER,
             perk_value_map: _perk_value_map,
             has_overshield: _has_overshield,
+            player: _player,
         }
     }
     pub fn construct_static(
@@ -114,6 +120,7 @@ impl<'a> CalculationInput<'a> {
         _ammo_type: &'a AmmoType,
         _damage_type: &'a DamageType,
         _crit_mult: f64,
+        _player: &'a PlayerState,
     ) -> Self {
         Self {
             intrinsic_hash: _intrinsic_hash,
@@ -136,6 +143,7 @@ impl<'a> CalculationInput<'a> {
             enemy_type: &EnemyType::ENCLAVE,
             perk_value_map: _perk_value_map,
             has_overshield: false,
+            player: _player,
         }
     }
 }
@@ -389,6 +397,22 @@ impl Default for DamageResistModifierResponse {
     }
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ChampionModResponse {
+    pub anti_barrier: bool,
+    pub overload: bool,
+    pub unstoppable: bool,
+}
+impl ChampionModResponse {
+    pub fn stuns(&self, _champion_type: ChampionType) -> bool {
+        match _champion_type {
+            ChampionType::BARRIER => self.anti_barrier,
+            ChampionType::OVERLOAD => self.overload,
+            ChampionType::UNSTOPPABLE => self.unstoppable,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct ModifierResponseSummary {
     pub rmr: Option<RangeModifierResponse>,
@@ -6,21 +6,20 @@ use crate::{
 };
 
 use super::{
-    add_dmr, add_epr, add_flmr, add_fmr, add_hmr, add_imr, add_mmr, add_rmr, add_rsmr, add_sbr,
-    add_vmr, clamp,
+    clamp,
     lib::{
-        CalculationInput, DamageModifierResponse, ExplosivePercentResponse, ExtraDamageResponse,
-        FiringModifierResponse, FlinchModifierResponse, HandlingModifierResponse,
-        InventoryModifierResponse, MagazineModifierResponse, RangeModifierResponse, RefundResponse,
-        ReloadModifierResponse,
+        CalculationInput, ChampionModResponse, DamageModifierResponse, ExplosivePercentResponse,
+        ExtraDamageResponse, FiringModifierResponse, FlinchModifierResponse,
+        HandlingModifierResponse, InventoryModifierResponse, MagazineModifierResponse,
+        RangeModifierResponse, RefundResponse, ReloadModifierResponse,
     },
-    ModifierResponseInput, Perks,
+    ModifierResponseInput, Perks, PersistentModifierResponses,
 };
 
-pub fn meta_perks() {
-    add_dmr(
+pub fn meta_perks(map: &mut PersistentModifierResponses) {
+    map.add_dmr(
         Perks::BuiltIn,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut crit_scale = 1.0;
             let mut dmg_scale = 1.0;
             if *_input.calc_data.weapon_type == WeaponType::LINEARFUSIONRIFLE && !_input.pvp {
@@ -60,12 +59,12 @@ pub fn meta_perks() {
                 impact_dmg_scale: dmg_scale,
                 explosive_dmg_scale: dmg_scale,
             }
-        }),
+        },
     );
 
-    add_fmr(
+    map.add_fmr(
         Perks::BuiltIn,
-        Box::new(|_input: ModifierResponseInput| -> FiringModifierResponse {
+        |_input: ModifierResponseInput| -> FiringModifierResponse {
             #[allow(unused_mut)]
             let mut delay_add = 0.0;
             if *_input
@@ -89,13 +88,10 @@ pub fn meta_perks() {
                 }
             }
 
-            if _input.calc_data.weapon_type == &WeaponType::BOW {
-                let draw_time = _input
-                    .calc_data
-                    .stats
-                    .get(&StatHashes::DRAW_TIME.into())
-                    .unwrap()
-                    .clone();
+            let draw_time = _input.calc_data.stats.get(&StatHashes::DRAW_TIME.into());
+            if let (true, Some(draw_time)) =
+                (_input.calc_data.weapon_type == &WeaponType::BOW, draw_time)
+            {
                 delay_add += match _input.calc_data.intrinsic_hash {
                     //Lightweights, Wishender, Ticcus, Verglas
                     905 | 1470121888 | 3239299468 | 2636679416 => {
@@ -114,88 +110,82 @@ pub fn meta_perks() {
                 burst_delay_add: delay_add,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_epr(
+    map.add_epr(
         Perks::BuiltIn,
-        Box::new(
-            |_input: ModifierResponseInput| -> ExplosivePercentResponse {
-                if *_input.calc_data.weapon_type == WeaponType::GRENADELAUNCHER {
-                    let blast_radius_struct =
-                        _input.calc_data.stats.get(&StatHashes::BLAST_RADIUS.into());
-                    let blast_radius;
-                    if blast_radius_struct.is_none() {
-                        blast_radius = 0;
-                    } else {
-                        blast_radius = blast_radius_struct.unwrap().perk_val();
-                    };
-                    if _input.calc_data.ammo_type == &AmmoType::SPECIAL {
-                        return ExplosivePercentResponse {
-                            percent: 0.5 + 0.003 * blast_radius as f64,
-                            delyed: 0.0,
-                            retain_base_total: true,
-                        };
-                    } else if _input.calc_data.ammo_type == &AmmoType::HEAVY {
-                        return ExplosivePercentResponse {
-                            percent: 0.7 + 0.00175 * blast_radius as f64,
-                            delyed: 0.0,
-                            retain_base_total: true,
-                        };
+        |_input: ModifierResponseInput| -> ExplosivePercentResponse {
+            if *_input.calc_data.weapon_type == WeaponType::GRENADELAUNCHER {
+                let blast_radius_struct =
+                    _input.calc_data.stats.get(&StatHashes::BLAST_RADIUS.into());
+                let blast_radius;
+                if blast_radius_struct.is_none() {
+                    blast_radius = 0;
+                } else {
+                    blast_radius = blast_radius_struct.unwrap().perk_val();
+                };
+                if _input.calc_data.ammo_type == &AmmoType::SPECIAL {
+                    return ExplosivePercentResponse {
+                        percent: 0.5 + 0.003 * blast_radius as f64,
+                        delyed: 0.0,
+                        retain_base_total: true,
                     };
-                }
-                if *_input.calc_data.weapon_type == WeaponType::ROCKET
-                    && _input.calc_data.intrinsic_hash < 1000
-                //ensures not exotic
-                {
+                } else if _input.calc_data.ammo_type == &AmmoType::HEAVY {
                     return ExplosivePercentResponse {
-                        percent: 0.28,
+                        percent: 0.7 + 0.00175 * blast_radius as f64,
                         delyed: 0.0,
                         retain_base_total: true,
                     };
-                }
-                ExplosivePercentResponse {
-                    percent: 0.0,
+                };
+            }
+            if *_input.calc_data.weapon_type == WeaponType::ROCKET
+                && _input.calc_data.intrinsic_hash < 1000
+            //ensures not exotic
+            {
+                return ExplosivePercentResponse {
+                    percent: 0.28,
                     delyed: 0.0,
                     retain_base_total: true,
-                }
-            },
-        ),
+                };
+            }
+            ExplosivePercentResponse {
+                percent: 0.0,
+                delyed: 0.0,
+                retain_base_total: true,
+            }
+        },
     );
 
-    add_hmr(
+    map.add_hmr(
         Perks::DexterityMod,
-        Box::new(
-            |_input: ModifierResponseInput| -> HandlingModifierResponse {
-                let swap_scale = if _input.value > 0 {
-                    0.85 - clamp(_input.value, 1, 3) as f64 * 0.05
-                } else {
-                    1.0
-                };
-                HandlingModifierResponse {
-                    stow_scale: swap_scale,
-                    draw_scale: swap_scale,
-                    ..Default::default()
-                }
-            },
-        ),
+        |_input: ModifierResponseInput| -> HandlingModifierResponse {
+            let swap_scale = if _input.value > 0 {
+                0.85 - clamp(_input.value, 1, 3) as f64 * 0.05
+            } else {
+                1.0
+            };
+            HandlingModifierResponse {
+                stow_scale: swap_scale,
+                draw_scale: swap_scale,
+                ..Default::default()
+            }
+        },
     );
 
-    add_hmr(
+    map.add_hmr(
         Perks::TargetingMod,
-        Box::new(
-            |_input: ModifierResponseInput| -> HandlingModifierResponse {
-                HandlingModifierResponse {
-                    ads_scale: if _input.value > 0 { 0.75 } else { 1.0 },
-                    ..Default::default()
-                }
-            },
-        ),
+        |_input: ModifierResponseInput| -> HandlingModifierResponse {
+            HandlingModifierResponse {
+                ads_scale: if _input.value > 0 { 0.75 } else { 1.0 },
+                ..Default::default()
+            }
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::TargetingMod,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut stats = HashMap::new();
             if _input.value == 1 {
                 stats.insert(StatHashes::AIM_ASSIST.into(), 5);
@@ -205,32 +195,30 @@ pub fn meta_perks() {
                 stats.insert(StatHashes::AIM_ASSIST.into(), 10);
             }
             stats
-        }),
+        },
     );
 
-    add_imr(
+    map.add_imr(
         Perks::ReserveMod,
-        Box::new(
-            |_input: ModifierResponseInput| -> InventoryModifierResponse {
-                let mut inv_buff = if _input.value > 0 { 20 } else { 0 };
-                if _input.value == 2 {
-                    inv_buff += 20;
-                }
-                if _input.value > 2 {
-                    inv_buff += 30;
-                }
-                InventoryModifierResponse {
-                    inv_stat_add: inv_buff,
-                    inv_scale: 1.0,
-                    inv_add: 0.0,
-                }
-            },
-        ),
+        |_input: ModifierResponseInput| -> InventoryModifierResponse {
+            let mut inv_buff = if _input.value > 0 { 20 } else { 0 };
+            if _input.value == 2 {
+                inv_buff += 20;
+            }
+            if _input.value > 2 {
+                inv_buff += 30;
+            }
+            InventoryModifierResponse {
+                inv_stat_add: inv_buff,
+                inv_scale: 1.0,
+                inv_add: 0.0,
+            }
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::ReserveMod,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut inv_buff = if _input.value > 0 { 20 } else { 0 };
             if _input.value == 2 {
                 inv_buff += 15;
@@ -241,12 +229,12 @@ pub fn meta_perks() {
             let mut stats = HashMap::new();
             stats.insert(StatHashes::INVENTORY_SIZE.into(), inv_buff);
             stats
-        }),
+        },
     );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::LoaderMod,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             if _input.value > 0 {
                 let mut reload_stat_buff = 10;
                 if _input.value > 1 {
@@ -262,12 +250,12 @@ pub fn meta_perks() {
             } else {
                 return ReloadModifierResponse::default();
             };
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::LoaderMod,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut stats = HashMap::new();
             if _input.value > 0 {
                 let mut reload_stat_buff = 10;
@@ -280,12 +268,12 @@ pub fn meta_perks() {
                 stats.insert(StatHashes::RELOAD.into(), reload_stat_buff);
             };
             stats
-        }),
+        },
     );
 
-    add_flmr(
+    map.add_flmr(
         Perks::UnflinchingMod,
-        Box::new(|_input: ModifierResponseInput| -> FlinchModifierResponse {
+        |_input: ModifierResponseInput| -> FlinchModifierResponse {
             if _input.value > 2 {
                 FlinchModifierResponse { flinch_scale: 0.6 }
             } else if _input.value == 2 {
@@ -295,49 +283,49 @@ pub fn meta_perks() {
             } else {
                 FlinchModifierResponse::default()
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::RallyBarricade,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut stats = HashMap::new();
             stats.insert(StatHashes::STABILITY.into(), 30);
             stats.insert(StatHashes::RELOAD.into(), 100);
             stats
-        }),
+        },
     );
 
-    add_flmr(
+    map.add_flmr(
         Perks::RallyBarricade,
-        Box::new(|_input: ModifierResponseInput| -> FlinchModifierResponse {
+        |_input: ModifierResponseInput| -> FlinchModifierResponse {
             FlinchModifierResponse { flinch_scale: 0.5 }
-        }),
+        },
     );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::RallyBarricade,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             ReloadModifierResponse {
                 reload_stat_add: 100,
                 reload_time_scale: 0.9,
             }
-        }),
+        },
     );
 
-    add_rmr(
+    map.add_rmr(
         Perks::RallyBarricade,
-        Box::new(|_input: ModifierResponseInput| -> RangeModifierResponse {
+        |_input: ModifierResponseInput| -> RangeModifierResponse {
             RangeModifierResponse {
                 range_all_scale: 1.1,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::ChargetimeMW,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             fn down5(x: i32) -> f64 {
                 (x as f64 - 5.0) / x as f64
             }
@@ -353,12 +341,12 @@ pub fn meta_perks() {
                 impact_dmg_scale: damage_mod,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::SurgeMod,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let damage_mod;
             if _input.pvp {
                 if _input.value == 1 {
@@ -386,6 +374,37 @@ pub fn meta_perks() {
                 impact_dmg_scale: damage_mod,
                 ..Default::default()
             }
-        }),
+        },
+    );
+
+    //seasonal artifact mods, only add them to weapon types the artifact covers
+    map.add_cmr(
+        Perks::AntiBarrierMod,
+        |_input: ModifierResponseInput| -> ChampionModResponse {
+            ChampionModResponse {
+                anti_barrier: true,
+                ..Default::default()
+            }
+        },
+    );
+
+    map.add_cmr(
+        Perks::OverloadMod,
+        |_input: ModifierResponseInput| -> ChampionModResponse {
+            ChampionModResponse {
+                overload: true,
+                ..Default::default()
+            }
+        },
+    );
+
+    map.add_cmr(
+        Perks::UnstoppableMod,
+        |_input: ModifierResponseInput| -> ChampionModResponse {
+            ChampionModResponse {
+                unstoppable: true,
+                ..Default::default()
+            }
+        },
     );
 }
@@ -8,6 +8,8 @@ pub mod meta_perks;
 pub mod origin_perks;
 pub mod other_perks;
 pub mod perk_options_handler;
+pub mod perk_state;
+pub mod simulation_state;
 pub mod year_1_perks;
 pub mod year_2_perks;
 pub mod year_3_perks;
@@ -17,11 +19,12 @@ pub mod year_6_perks;
 
 use std::borrow::BorrowMut;
 use std::collections::HashMap;
+use std::sync::OnceLock;
 
 use num_enum::FromPrimitive;
 use serde::{Deserialize, Serialize};
 
-use crate::d2_enums::{BungieHash, StatBump, StatHashes, WeaponType};
+use crate::d2_enums::{BungieHash, DamageSource, StatBump, StatHashes, WeaponType};
 use crate::database;
 
 use self::{
@@ -29,7 +32,9 @@ use self::{
     exotic_armor::*,
     exotic_perks::*,
     lib::{
-        CalculationInput, DamageModifierResponse, ExplosivePercentResponse, ExtraDamageResponse,
+        CalculationInput, ChampionModResponse, DamageModifierResponse,
+        DamageResistModifierResponse,
+        ExplosivePercentResponse, ExtraDamageResponse,
         FiringModifierResponse, FlinchModifierResponse, HandlingModifierResponse,
         InventoryModifierResponse, MagazineModifierResponse, ModifierResponseSummary,
         RangeModifierResponse, RefundResponse, ReloadModifierResponse, ReloadOverrideResponse,
@@ -38,6 +43,7 @@ use self::{
     meta_perks::*,
     origin_perks::*,
     other_perks::*,
+    simulation_state::SimulationState,
     year_1_perks::*,
     year_2_perks::*,
     year_3_perks::*,
@@ -101,6 +107,10 @@ pub enum Perks {
     BannerShield = 4260353952,
     DeadFall = 2722573683,
     MoebiusQuiver = 2722573681,
+    //resists on the target, set on PvpTarget instead of being added to a weapon
+    FrostArmor = 666,
+    WovenMail = 888,
+    ConcussiveDampener = 777,
 
     //intrinsics
     RapidFireFrame = 902,
@@ -112,6 +122,9 @@ pub enum Perks {
     LoaderMod = 1004,
     UnflinchingMod = 1005,
     SurgeMod = 1006,
+    AntiBarrierMod = 1007,
+    OverloadMod = 1008,
+    UnstoppableMod = 1009,
     DragonShadow = 593361144,
     OphidianAspect = 1147638875,
     LunaFaction = 3347978672,
@@ -229,6 +242,8 @@ pub enum Perks {
     TripleTap = 3400784728,
     UnderPressure = 1645158859,
     PulseMonitor = 972757866,
+    //no modifiers, the rotation sim reloads stowed weapons
+    AutoLoadingHolster = 3300816228,
 
     //season 2 | year 1
     //lmao bozo
@@ -251,12 +266,14 @@ pub enum Perks {
     #[num_enum(alternatives = [1266037486, ])] //R0
     FourthTimesTheCharm = 1354429876,
     RapidHit = 247725512,
+    Dragonfly = 2848615171,
 
     //season 5 | year 2
     ResevoirBurst = 1427256713,
     Surrounded = 3708227201,
     AirAssault = 3722653512,
     OverFlow = 3643424744,
+    WhirlwindBlade = 3913600130,
 
     //season 6 | year 2
     FiringLine = 1771339417,
@@ -309,6 +326,7 @@ pub enum Perks {
     FireFly = 3824105627,
     DangerZone = 960810156,
     TunnelVision = 2946784966,
+    RelentlessStrikes = 3411186089,
 
     //season 15 | year 4
     Encore = 1195158366,
@@ -441,50 +459,60 @@ pub struct ModifierResponseInput<'a> {
     value: u32,
     is_enhanced: bool,
     pvp: bool,
-    cached_data: &'a mut HashMap<String, f64>,
+    sim_state: &'a mut SimulationState,
 }
+///Perk functions are plain fn pointers so the registry is Send + Sync
+pub type ModifierFn<T> = fn(ModifierResponseInput) -> T;
+
 #[derive(Default)]
 pub struct PersistentModifierResponses {
-    pub sbr: HashMap<Perks, Box<dyn Fn(ModifierResponseInput) -> HashMap<BungieHash, StatBump>>>,
-    pub dmr: HashMap<Perks, Box<dyn Fn(ModifierResponseInput) -> DamageModifierResponse>>,
-    pub hmr: HashMap<Perks, Box<dyn Fn(ModifierResponseInput) -> HandlingModifierResponse>>,
-    pub rmr: HashMap<Perks, Box<dyn Fn(ModifierResponseInput) -> RangeModifierResponse>>,
-    pub rsmr: HashMap<Perks, Box<dyn Fn(ModifierResponseInput) -> ReloadModifierResponse>>,
-    pub fmr: HashMap<Perks, Box<dyn Fn(ModifierResponseInput) -> FiringModifierResponse>>,
-    pub flmr: HashMap<Perks, Box<dyn Fn(ModifierResponseInput) -> FlinchModifierResponse>>,
-    pub edr: HashMap<Perks, Box<dyn Fn(ModifierResponseInput) -> ExtraDamageResponse>>,
-    pub rr: HashMap<Perks, Box<dyn Fn(ModifierResponseInput) -> RefundResponse>>,
-    pub vmr: HashMap<Perks, Box<dyn Fn(ModifierResponseInput) -> VelocityModifierResponse>>,
-    pub epr: HashMap<Perks, Box<dyn Fn(ModifierResponseInput) -> ExplosivePercentResponse>>,
-    pub mmr: HashMap<Perks, Box<dyn Fn(ModifierResponseInput) -> MagazineModifierResponse>>,
-    pub imr: HashMap<Perks, Box<dyn Fn(ModifierResponseInput) -> InventoryModifierResponse>>,
+    pub sbr: HashMap<Perks, ModifierFn<HashMap<BungieHash, StatBump>>>,
+    pub dmr: HashMap<Perks, ModifierFn<DamageModifierResponse>>,
+    pub hmr: HashMap<Perks, ModifierFn<HandlingModifierResponse>>,
+    pub rmr: HashMap<Perks, ModifierFn<RangeModifierResponse>>,
+    pub rsmr: HashMap<Perks, ModifierFn<ReloadModifierResponse>>,
+    pub fmr: HashMap<Perks, ModifierFn<FiringModifierResponse>>,
+    pub flmr: HashMap<Perks, ModifierFn<FlinchModifierResponse>>,
+    pub edr: HashMap<Perks, ModifierFn<ExtraDamageResponse>>,
+    ///Extra damage dealt when the weapon gets a kill
+    pub kedr: HashMap<Perks, ModifierFn<ExtraDamageResponse>>,
+    pub rr: HashMap<Perks, ModifierFn<RefundResponse>>,
+    pub vmr: HashMap<Perks, ModifierFn<VelocityModifierResponse>>,
+    pub epr: HashMap<Perks, ModifierFn<ExplosivePercentResponse>>,
+    pub mmr: HashMap<Perks, ModifierFn<MagazineModifierResponse>>,
+    pub imr: HashMap<Perks, ModifierFn<InventoryModifierResponse>>,
+    pub drmr: HashMap<Perks, ModifierFn<DamageResistModifierResponse>>,
+    pub cmr: HashMap<Perks, ModifierFn<ChampionModResponse>>,
 }
 impl PersistentModifierResponses {
-    fn is_empty(&self) -> bool {
-        self.sbr.is_empty()
+    fn build() -> Self {
+        let mut map = Self::default();
+        year_1_perks(&mut map);
+        year_2_perks(&mut map);
+        year_3_perks(&mut map);
+        year_4_perks(&mut map);
+        year_5_perks(&mut map);
+        year_6_perks(&mut map);
+        meta_perks(&mut map);
+        exotic_perks(&mut map);
+        exotic_armor(&mut map);
+        buff_perks(&mut map);
+        other_perks(&mut map);
+        origin_perks(&mut map);
+        map
     }
 }
 
-thread_local! {
-    static PERK_FUNC_MAP: std::cell::RefCell<PersistentModifierResponses>  = std::cell::RefCell::new(PersistentModifierResponses::default());
+static PERK_FUNC_MAP: OnceLock<PersistentModifierResponses> = OnceLock::new();
+
+///The registry is built the first time any thread asks for it
+fn perk_func_map() -> &'static PersistentModifierResponses {
+    PERK_FUNC_MAP.get_or_init(PersistentModifierResponses::build)
 }
 
+///Not required anymore, forces the perk registry to be built ahead of time
 pub fn map_perks() {
-    let is_empty = PERK_FUNC_MAP.with(|p| p.borrow().is_empty());
-    if is_empty {
-        year_1_perks();
-        year_2_perks();
-        year_3_perks();
-        year_4_perks();
-        year_5_perks();
-        year_6_perks();
-        meta_perks();
-        exotic_perks();
-        exotic_armor();
-        buff_perks();
-        other_perks();
-        origin_perks();
-    }
+    perk_func_map();
 }
 
 impl PersistentModifierResponses {
@@ -544,6 +572,13 @@ impl PersistentModifierResponses {
             ExtraDamageResponse::default()
         }
     }
+    fn get_kedr(&self, perk: Perks, input: ModifierResponseInput) -> ExtraDamageResponse {
+        if let Some(func) = self.kedr.get(&perk) {
+            func(input)
+        } else {
+            ExtraDamageResponse::default()
+        }
+    }
     fn get_rr(&self, perk: Perks, input: ModifierResponseInput) -> RefundResponse {
         if let Some(func) = self.rr.get(&perk) {
             func(input)
@@ -579,93 +614,92 @@ impl PersistentModifierResponses {
             InventoryModifierResponse::default()
         }
     }
+    fn get_drmr(&self, perk: Perks, input: ModifierResponseInput) -> DamageResistModifierResponse {
+        if let Some(func) = self.drmr.get(&perk) {
+            func(input)
+        } else {
+            DamageResistModifierResponse::default()
+        }
+    }
+    fn get_cmr(&self, perk: Perks, input: ModifierResponseInput) -> ChampionModResponse {
+        if let Some(func) = self.cmr.get(&perk) {
+            func(input)
+        } else {
+            ChampionModResponse::default()
+        }
+    }
 }
 
-fn add_sbr(perk: Perks, func: Box<dyn Fn(ModifierResponseInput) -> HashMap<BungieHash, StatBump>>) {
-    PERK_FUNC_MAP.with(|map| {
-        map.borrow_mut().sbr.insert(perk, func);
-    });
-}
-fn add_dmr(perk: Perks, func: Box<dyn Fn(ModifierResponseInput) -> DamageModifierResponse>) {
-    PERK_FUNC_MAP.with(|map| {
-        map.borrow_mut().dmr.insert(perk, func);
-    });
-}
-fn add_hmr(perk: Perks, func: Box<dyn Fn(ModifierResponseInput) -> HandlingModifierResponse>) {
-    PERK_FUNC_MAP.with(|map| {
-        map.borrow_mut().hmr.insert(perk, func);
-    });
-}
-fn add_rmr(perk: Perks, func: Box<dyn Fn(ModifierResponseInput) -> RangeModifierResponse>) {
-    PERK_FUNC_MAP.with(|map| {
-        map.borrow_mut().rmr.insert(perk, func);
-    });
-}
-fn add_rsmr(perk: Perks, func: Box<dyn Fn(ModifierResponseInput) -> ReloadModifierResponse>) {
-    PERK_FUNC_MAP.with(|map| {
-        map.borrow_mut().rsmr.insert(perk, func);
-    });
-}
-fn add_fmr(perk: Perks, func: Box<dyn Fn(ModifierResponseInput) -> FiringModifierResponse>) {
-    PERK_FUNC_MAP.with(|map| {
-        map.borrow_mut().fmr.insert(perk, func);
-    });
-}
-fn add_flmr(perk: Perks, func: Box<dyn Fn(ModifierResponseInput) -> FlinchModifierResponse>) {
-    PERK_FUNC_MAP.with(|map| {
-        map.borrow_mut().flmr.insert(perk, func);
-    });
-}
-fn add_edr(perk: Perks, func: Box<dyn Fn(ModifierResponseInput) -> ExtraDamageResponse>) {
-    PERK_FUNC_MAP.with(|map| {
-        map.borrow_mut().edr.insert(perk, func);
-    });
-}
-fn add_rr(perk: Perks, func: Box<dyn Fn(ModifierResponseInput) -> RefundResponse>) {
-    PERK_FUNC_MAP.with(|map| {
-        map.borrow_mut().rr.insert(perk, func);
-    });
-}
-fn add_vmr(perk: Perks, func: Box<dyn Fn(ModifierResponseInput) -> VelocityModifierResponse>) {
-    PERK_FUNC_MAP.with(|map| {
-        map.borrow_mut().vmr.insert(perk, func);
-    });
-}
-fn add_epr(perk: Perks, func: Box<dyn Fn(ModifierResponseInput) -> ExplosivePercentResponse>) {
-    PERK_FUNC_MAP.with(|map| {
-        map.borrow_mut().epr.insert(perk, func);
-    });
-}
-fn add_mmr(perk: Perks, func: Box<dyn Fn(ModifierResponseInput) -> MagazineModifierResponse>) {
-    PERK_FUNC_MAP.with(|map| {
-        map.borrow_mut().mmr.insert(perk, func);
-    });
-}
-fn add_imr(perk: Perks, func: Box<dyn Fn(ModifierResponseInput) -> InventoryModifierResponse>) {
-    PERK_FUNC_MAP.with(|map| {
-        map.borrow_mut().imr.insert(perk, func);
-    });
+impl PersistentModifierResponses {
+    fn add_sbr(&mut self, perk: Perks, func: ModifierFn<HashMap<BungieHash, StatBump>>) {
+        self.sbr.insert(perk, func);
+    }
+    fn add_dmr(&mut self, perk: Perks, func: ModifierFn<DamageModifierResponse>) {
+        self.dmr.insert(perk, func);
+    }
+    fn add_hmr(&mut self, perk: Perks, func: ModifierFn<HandlingModifierResponse>) {
+        self.hmr.insert(perk, func);
+    }
+    fn add_rmr(&mut self, perk: Perks, func: ModifierFn<RangeModifierResponse>) {
+        self.rmr.insert(perk, func);
+    }
+    fn add_rsmr(&mut self, perk: Perks, func: ModifierFn<ReloadModifierResponse>) {
+        self.rsmr.insert(perk, func);
+    }
+    fn add_fmr(&mut self, perk: Perks, func: ModifierFn<FiringModifierResponse>) {
+        self.fmr.insert(perk, func);
+    }
+    fn add_flmr(&mut self, perk: Perks, func: ModifierFn<FlinchModifierResponse>) {
+        self.flmr.insert(perk, func);
+    }
+    fn add_edr(&mut self, perk: Perks, func: ModifierFn<ExtraDamageResponse>) {
+        self.edr.insert(perk, func);
+    }
+    fn add_kedr(&mut self, perk: Perks, func: ModifierFn<ExtraDamageResponse>) {
+        self.kedr.insert(perk, func);
+    }
+    fn add_rr(&mut self, perk: Perks, func: ModifierFn<RefundResponse>) {
+        self.rr.insert(perk, func);
+    }
+    fn add_vmr(&mut self, perk: Perks, func: ModifierFn<VelocityModifierResponse>) {
+        self.vmr.insert(perk, func);
+    }
+    fn add_epr(&mut self, perk: Perks, func: ModifierFn<ExplosivePercentResponse>) {
+        self.epr.insert(perk, func);
+    }
+    fn add_mmr(&mut self, perk: Perks, func: ModifierFn<MagazineModifierResponse>) {
+        self.mmr.insert(perk, func);
+    }
+    fn add_imr(&mut self, perk: Perks, func: ModifierFn<InventoryModifierResponse>) {
+        self.imr.insert(perk, func);
+    }
+    fn add_drmr(&mut self, perk: Perks, func: ModifierFn<DamageResistModifierResponse>) {
+        self.drmr.insert(perk, func);
+    }
+    fn add_cmr(&mut self, perk: Perks, func: ModifierFn<ChampionModResponse>) {
+        self.cmr.insert(perk, func);
+    }
 }
 
 pub fn get_stat_bumps(
     _perks: Vec<Perk>,
     _input_data: CalculationInput,
     _pvp: bool,
-    _cached_data: &mut HashMap<String, f64>,
+    _sim_state: &mut SimulationState,
 ) -> [HashMap<u32, i32>; 2] {
     let mut dynamic_stats: HashMap<u32, i32> = HashMap::new();
     let mut static_stats: HashMap<u32, i32> = HashMap::new();
     for perk in _perks {
-        let perk_stats = PERK_FUNC_MAP.with(|pers_modifier| {
+        let perk_stats = {
             let inp = ModifierResponseInput {
                 is_enhanced: perk.enhanced,
                 value: perk.value,
                 calc_data: &_input_data,
                 pvp: _pvp,
-                cached_data: _cached_data,
+                sim_state: _sim_state,
             };
-            pers_modifier.borrow().get_sbr(perk.hash.into(), inp)
-        });
+            perk_func_map().get_sbr(perk.hash.into(), inp)
+        };
         for (key, value) in perk_stats {
             let entry = dynamic_stats.entry(key).or_insert(0);
             *entry += value;
@@ -682,20 +716,20 @@ pub fn get_dmg_modifier(
     _perks: Vec<Perk>,
     _input_data: &CalculationInput,
     _pvp: bool,
-    _cached_data: &mut HashMap<String, f64>,
+    _sim_state: &mut SimulationState,
 ) -> DamageModifierResponse {
     let mut dmg_modifier = DamageModifierResponse::default();
     for perk in _perks {
-        let tmp = PERK_FUNC_MAP.with(|pers_modifier| {
+        let tmp = {
             let inp = ModifierResponseInput {
                 is_enhanced: perk.enhanced,
                 value: perk.value,
                 calc_data: &_input_data,
                 pvp: _pvp,
-                cached_data: _cached_data,
+                sim_state: _sim_state,
             };
-            pers_modifier.borrow().get_dmr(perk.hash.into(), inp)
-        });
+            perk_func_map().get_dmr(perk.hash.into(), inp)
+        };
         dmg_modifier.impact_dmg_scale *= tmp.impact_dmg_scale;
         dmg_modifier.explosive_dmg_scale *= tmp.explosive_dmg_scale;
         dmg_modifier.crit_scale *= tmp.crit_scale;
@@ -707,20 +741,20 @@ pub fn get_reload_modifier(
     _perks: Vec<Perk>,
     _input_data: &CalculationInput,
     _pvp: bool,
-    _cached_data: &mut HashMap<String, f64>,
+    _sim_state: &mut SimulationState,
 ) -> ReloadModifierResponse {
     let mut reload_modifier = ReloadModifierResponse::default();
     for perk in _perks {
-        let tmp = PERK_FUNC_MAP.with(|pers_modifier| {
+        let tmp = {
             let inp = ModifierResponseInput {
                 is_enhanced: perk.enhanced,
                 value: perk.value,
                 calc_data: &_input_data,
                 pvp: _pvp,
-                cached_data: _cached_data,
+                sim_state: _sim_state,
             };
-            pers_modifier.borrow().get_rsmr(perk.hash.into(), inp)
-        });
+            perk_func_map().get_rsmr(perk.hash.into(), inp)
+        };
         reload_modifier.reload_stat_add += tmp.reload_stat_add;
         reload_modifier.reload_time_scale *= tmp.reload_time_scale;
     }
@@ -731,20 +765,20 @@ pub fn get_firing_modifier(
     _perks: Vec<Perk>,
     _input_data: &CalculationInput,
     _pvp: bool,
-    _cached_data: &mut HashMap<String, f64>,
+    _sim_state: &mut SimulationState,
 ) -> FiringModifierResponse {
     let mut firing_modifier = FiringModifierResponse::default();
     for perk in _perks {
-        let tmp = PERK_FUNC_MAP.with(|pers_modifier| {
+        let tmp = {
             let inp = ModifierResponseInput {
                 is_enhanced: perk.enhanced,
                 value: perk.value,
                 calc_data: &_input_data,
                 pvp: _pvp,
-                cached_data: _cached_data,
+                sim_state: _sim_state,
             };
-            pers_modifier.borrow().get_fmr(perk.hash.into(), inp)
-        });
+            perk_func_map().get_fmr(perk.hash.into(), inp)
+        };
         firing_modifier.burst_delay_scale *= tmp.burst_delay_scale;
         firing_modifier.burst_delay_add += tmp.burst_delay_add;
         firing_modifier.inner_burst_scale *= tmp.inner_burst_scale;
@@ -757,20 +791,20 @@ pub fn get_handling_modifier(
     _perks: Vec<Perk>,
     _input_data: &CalculationInput,
     _pvp: bool,
-    _cached_data: &mut HashMap<String, f64>,
+    _sim_state: &mut SimulationState,
 ) -> HandlingModifierResponse {
     let mut handling_modifier = HandlingModifierResponse::default();
     for perk in _perks {
-        let tmp = PERK_FUNC_MAP.with(|pers_modifier| {
+        let tmp = {
             let inp = ModifierResponseInput {
                 is_enhanced: perk.enhanced,
                 value: perk.value,
                 calc_data: &_input_data,
                 pvp: _pvp,
-                cached_data: _cached_data,
+                sim_state: _sim_state,
             };
-            pers_modifier.borrow().get_hmr(perk.hash.into(), inp)
-        });
+            perk_func_map().get_hmr(perk.hash.into(), inp)
+        };
         handling_modifier.stat_add += tmp.stat_add;
         handling_modifier.stow_scale *= tmp.stow_scale;
         handling_modifier.draw_scale *= tmp.draw_scale;
@@ -783,20 +817,20 @@ pub fn get_magazine_modifier(
     _perks: Vec<Perk>,
     _input_data: &CalculationInput,
     _pvp: bool,
-    _cached_data: &mut HashMap<String, f64>,
+    _sim_state: &mut SimulationState,
 ) -> MagazineModifierResponse {
     let mut magazine_modifier = MagazineModifierResponse::default();
     for perk in _perks {
-        let tmp = PERK_FUNC_MAP.with(|pers_modifier| {
+        let tmp = {
             let inp = ModifierResponseInput {
                 is_enhanced: perk.enhanced,
                 value: perk.value,
                 calc_data: &_input_data,
                 pvp: _pvp,
-                cached_data: _cached_data,
+                sim_state: _sim_state,
             };
-            pers_modifier.borrow().get_mmr(perk.hash.into(), inp)
-        });
+            perk_func_map().get_mmr(perk.hash.into(), inp)
+        };
         magazine_modifier.magazine_stat_add += tmp.magazine_stat_add;
         magazine_modifier.magazine_add += tmp.magazine_add;
         magazine_modifier.magazine_scale *= tmp.magazine_scale;
@@ -808,20 +842,20 @@ pub fn get_reserve_modifier(
     _perks: Vec<Perk>,
     _input_data: &CalculationInput,
     _pvp: bool,
-    _cached_data: &mut HashMap<String, f64>,
+    _sim_state: &mut SimulationState,
 ) -> InventoryModifierResponse {
     let mut reserve_modifier = InventoryModifierResponse::default();
     for perk in _perks {
-        let tmp = PERK_FUNC_MAP.with(|pers_modifier| {
+        let tmp = {
             let inp = ModifierResponseInput {
                 is_enhanced: perk.enhanced,
                 value: perk.value,
                 calc_data: &_input_data,
                 pvp: _pvp,
-                cached_data: _cached_data,
+                sim_state: _sim_state,
             };
-            pers_modifier.borrow().get_imr(perk.hash.into(), inp)
-        });
+            perk_func_map().get_imr(perk.hash.into(), inp)
+        };
         reserve_modifier.inv_stat_add += tmp.inv_stat_add;
         reserve_modifier.inv_add += tmp.inv_add;
         reserve_modifier.inv_scale *= tmp.inv_scale;
@@ -833,20 +867,20 @@ pub fn get_range_modifier(
     _perks: Vec<Perk>,
     _input_data: &CalculationInput,
     _pvp: bool,
-    _cached_data: &mut HashMap<String, f64>,
+    _sim_state: &mut SimulationState,
 ) -> RangeModifierResponse {
     let mut range_modifier = RangeModifierResponse::default();
     for perk in _perks {
-        let tmp = PERK_FUNC_MAP.with(|pers_modifier| {
+        let tmp = {
             let inp = ModifierResponseInput {
                 is_enhanced: perk.enhanced,
                 value: perk.value,
                 calc_data: &_input_data,
                 pvp: _pvp,
-                cached_data: _cached_data,
+                sim_state: _sim_state,
             };
-            pers_modifier.borrow().get_rmr(perk.hash.into(), inp)
-        });
+            perk_func_map().get_rmr(perk.hash.into(), inp)
+        };
         range_modifier.range_stat_add += tmp.range_stat_add;
         range_modifier.range_all_scale *= tmp.range_all_scale;
         range_modifier.range_hip_scale *= tmp.range_hip_scale;
@@ -859,20 +893,20 @@ pub fn get_refund_modifier(
     _perks: Vec<Perk>,
     _input_data: &CalculationInput,
     _pvp: bool,
-    _cached_data: &mut HashMap<String, f64>,
+    _sim_state: &mut SimulationState,
 ) -> Vec<RefundResponse> {
     let mut refund_modifier = vec![];
     for perk in _perks {
-        let tmp = PERK_FUNC_MAP.with(|pers_modifier| {
+        let tmp = {
             let inp = ModifierResponseInput {
                 is_enhanced: perk.enhanced,
                 value: perk.value,
                 calc_data: &_input_data,
                 pvp: _pvp,
-                cached_data: _cached_data,
+                sim_state: _sim_state,
             };
-            pers_modifier.borrow().get_rr(perk.hash.into(), inp)
-        });
+            perk_func_map().get_rr(perk.hash.into(), inp)
+        };
         if tmp.requirement > 0 {
             refund_modifier.push(tmp);
         }
@@ -884,20 +918,46 @@ pub fn get_extra_damage(
     _perks: Vec<Perk>,
     _input_data: &CalculationInput,
     _pvp: bool,
-    _cached_data: &mut HashMap<String, f64>,
+    _sim_state: &mut SimulationState,
+) -> Vec<ExtraDamageResponse> {
+    let mut extra_damage = vec![];
+    for perk in _perks {
+        let tmp = {
+            let inp = ModifierResponseInput {
+                is_enhanced: perk.enhanced,
+                value: perk.value,
+                calc_data: &_input_data,
+                pvp: _pvp,
+                sim_state: _sim_state,
+            };
+            perk_func_map().get_edr(perk.hash.into(), inp)
+        };
+        if tmp.additive_damage > 0.0 {
+            extra_damage.push(tmp);
+        }
+    }
+    extra_damage
+}
+
+///Extra damage from perks that go off when the weapon kills something
+pub fn get_kill_extra_damage(
+    _perks: Vec<Perk>,
+    _input_data: &CalculationInput,
+    _pvp: bool,
+    _sim_state: &mut SimulationState,
 ) -> Vec<ExtraDamageResponse> {
     let mut extra_damage = vec![];
     for perk in _perks {
-        let tmp = PERK_FUNC_MAP.with(|pers_modifier| {
+        let tmp = {
             let inp = ModifierResponseInput {
                 is_enhanced: perk.enhanced,
                 value: perk.value,
                 calc_data: &_input_data,
                 pvp: _pvp,
-                cached_data: _cached_data,
+                sim_state: _sim_state,
             };
-            pers_modifier.borrow().get_edr(perk.hash.into(), inp)
-        });
+            perk_func_map().get_kedr(perk.hash.into(), inp)
+        };
         if tmp.additive_damage > 0.0 {
             extra_damage.push(tmp);
         }
@@ -909,20 +969,20 @@ pub fn get_extra_damage(
 //     _perks: Vec<Perk>,
 //     _input_data: &CalculationInput,
 //     _pvp: bool,
-//     _cached_data: &mut HashMap<String, f64>,
+//     _sim_state: &mut SimulationState,
 // ) -> Vec<ReloadOverrideResponse> {
 //     let mut reload_overrides = vec![];
 //     for perk in _perks {
-//         let tmp = PERK_FUNC_MAP.with(|pers_modifier| {
+//         let tmp = {
 //             let inp = ModifierResponsInput {
 //                 is_enhanced: perk.enhanced,
 //                 value: perk.value,
 //                 calc_data: &_input_data,
 //                 pvp: _pvp,
-//                 cached_data: _cached_data,
+//                 sim_state: _sim_state,
 //             };
-//             pers_modifier.borrow().get_ror(perk.hash.into(), inp)
-//         });
+//             perk_func_map().get_ror(perk.hash.into(), inp)
+//         };
 //         if tmp.valid {
 //             reload_overrides.push(tmp);
 //         }
@@ -937,16 +997,16 @@ pub fn get_explosion_data(
 ) -> ExplosivePercentResponse {
     let mut highest_so_far = ExplosivePercentResponse::default();
     for perk in _perks {
-        let tmp = PERK_FUNC_MAP.with(|pers_modifier| {
+        let tmp = {
             let inp = ModifierResponseInput {
                 is_enhanced: perk.enhanced,
                 value: perk.value,
                 calc_data: &_input_data,
                 pvp: _pvp,
-                cached_data: &mut HashMap::new(),
+                sim_state: &mut SimulationState::default(),
             };
-            pers_modifier.borrow().get_epr(perk.hash.into(), inp)
-        });
+            perk_func_map().get_epr(perk.hash.into(), inp)
+        };
         if tmp.percent > highest_so_far.percent {
             highest_so_far = tmp;
         }
@@ -958,43 +1018,99 @@ pub fn get_flinch_modifier(
     _perks: Vec<Perk>,
     _input_data: &CalculationInput,
     _pvp: bool,
-    _cached_data: &mut HashMap<String, f64>,
+    _sim_state: &mut SimulationState,
 ) -> FlinchModifierResponse {
     let mut flinch = FlinchModifierResponse::default();
     for perk in _perks {
-        let tmp = PERK_FUNC_MAP.with(|pers_modifier| {
+        let tmp = {
             let inp = ModifierResponseInput {
                 is_enhanced: perk.enhanced,
                 value: perk.value,
                 calc_data: &_input_data,
                 pvp: _pvp,
-                cached_data: _cached_data,
+                sim_state: _sim_state,
             };
-            pers_modifier.borrow().get_flmr(perk.hash.into(), inp)
-        });
+            perk_func_map().get_flmr(perk.hash.into(), inp)
+        };
         flinch.flinch_scale *= tmp.flinch_scale;
     }
     flinch
 }
 
+///Resist the target has against the weapon, responses locked to another element or source are skipped
+pub fn get_damage_resist_modifier(
+    _perks: Vec<Perk>,
+    _input_data: &CalculationInput,
+    _pvp: bool,
+    _sim_state: &mut SimulationState,
+) -> DamageResistModifierResponse {
+    let mut resist = DamageResistModifierResponse::default();
+    let source = DamageSource::from(_input_data.weapon_type);
+    for perk in _perks {
+        let tmp = {
+            let inp = ModifierResponseInput {
+                is_enhanced: perk.enhanced,
+                value: perk.value,
+                calc_data: &_input_data,
+                pvp: _pvp,
+                sim_state: _sim_state,
+            };
+            perk_func_map().get_drmr(perk.hash.into(), inp)
+        };
+        if tmp.element.is_some_and(|element| element != *_input_data.damage_type)
+            || tmp.source.is_some_and(|tmp_source| tmp_source != source)
+        {
+            continue;
+        }
+        resist.body_shot_resist *= tmp.body_shot_resist;
+        resist.head_shot_resist *= tmp.head_shot_resist;
+    }
+    resist
+}
+
+///Champions the weapon can stun, any perk granting a mod is enough
+pub fn get_champion_mods(
+    _perks: Vec<Perk>,
+    _input_data: &CalculationInput,
+    _pvp: bool,
+) -> ChampionModResponse {
+    let mut champion_mods = ChampionModResponse::default();
+    for perk in _perks {
+        let tmp = {
+            let inp = ModifierResponseInput {
+                is_enhanced: perk.enhanced,
+                value: perk.value,
+                calc_data: &_input_data,
+                pvp: _pvp,
+                sim_state: &mut SimulationState::default(),
+            };
+            perk_func_map().get_cmr(perk.hash.into(), inp)
+        };
+        champion_mods.anti_barrier |= tmp.anti_barrier;
+        champion_mods.overload |= tmp.overload;
+        champion_mods.unstoppable |= tmp.unstoppable;
+    }
+    champion_mods
+}
+
 pub fn get_velocity_modifier(
     _perks: Vec<Perk>,
     _input_data: &CalculationInput,
     _pvp: bool,
-    _cached_data: &mut HashMap<String, f64>,
+    _sim_state: &mut SimulationState,
 ) -> VelocityModifierResponse {
     let mut velocity = VelocityModifierResponse::default();
     for perk in _perks {
-        let tmp = PERK_FUNC_MAP.with(|pers_modifier| {
+        let tmp = {
             let inp = ModifierResponseInput {
                 is_enhanced: perk.enhanced,
                 value: perk.value,
                 calc_data: &_input_data,
                 pvp: _pvp,
-                cached_data: _cached_data,
+                sim_state: _sim_state,
             };
-            pers_modifier.borrow().get_vmr(perk.hash.into(), inp)
-        });
+            perk_func_map().get_vmr(perk.hash.into(), inp)
+        };
         velocity.velocity_scaler *= tmp.velocity_scaler;
     }
     velocity
@@ -1005,10 +1121,10 @@ impl Weapon {
         &self,
         _calc_input: Option<CalculationInput>,
         _pvp: bool,
-        _cached_data: Option<&mut HashMap<String, f64>>,
+        _sim_state: Option<&mut SimulationState>,
     ) -> HashMap<BungieHash, ModifierResponseSummary> {
-        let mut default_cached_data = HashMap::new();
-        let cached_data = _cached_data.unwrap_or(&mut default_cached_data);
+        let mut default_sim_state = SimulationState::default();
+        let sim_state = _sim_state.unwrap_or(&mut default_sim_state);
         let mut buffer: HashMap<u32, ModifierResponseSummary> = HashMap::new();
         if _calc_input.is_none() {
             return buffer;
@@ -1017,8 +1133,8 @@ impl Weapon {
         let calc_input = _calc_input.unwrap();
 
         for perk in self.list_perks() {
-            let mod_buffer = PERK_FUNC_MAP.with(|pers_modifier| {
-                let perk_modifiers = pers_modifier.borrow();
+            let mod_buffer = {
+                let perk_modifiers = perk_func_map();
                 let mut mod_response = ModifierResponseSummary::default();
 
                 let inp = ModifierResponseInput {
@@ -1026,7 +1142,7 @@ impl Weapon {
                     value: perk.value,
                     calc_data: &calc_input,
                     pvp: _pvp,
-                    cached_data,
+                    sim_state,
                 };
                 let modifier = perk_modifiers.get_rmr(perk.hash.into(), inp);
                 if modifier != RangeModifierResponse::default() {
@@ -1038,7 +1154,7 @@ impl Weapon {
                     value: perk.value,
                     calc_data: &calc_input,
                     pvp: _pvp,
-                    cached_data,
+                    sim_state,
                 };
                 let modifier = perk_modifiers.get_dmr(perk.hash.into(), inp);
                 if modifier != DamageModifierResponse::default() {
@@ -1050,7 +1166,7 @@ impl Weapon {
                     value: perk.value,
                     calc_data: &calc_input,
                     pvp: _pvp,
-                    cached_data,
+                    sim_state,
                 };
                 let modifier = perk_modifiers.get_hmr(perk.hash.into(), inp);
                 if modifier != HandlingModifierResponse::default() {
@@ -1062,7 +1178,7 @@ impl Weapon {
                     value: perk.value,
                     calc_data: &calc_input,
                     pvp: _pvp,
-                    cached_data,
+                    sim_state,
                 };
                 let modifier = perk_modifiers.get_fmr(perk.hash.into(), inp);
                 if modifier != FiringModifierResponse::default() {
@@ -1074,7 +1190,7 @@ impl Weapon {
                     value: perk.value,
                     calc_data: &calc_input,
                     pvp: _pvp,
-                    cached_data,
+                    sim_state,
                 };
                 let modifier = perk_modifiers.get_flmr(perk.hash.into(), inp);
                 if modifier != FlinchModifierResponse::default() {
@@ -1086,7 +1202,7 @@ impl Weapon {
                     value: perk.value,
                     calc_data: &calc_input,
                     pvp: _pvp,
-                    cached_data,
+                    sim_state,
                 };
                 let modifier = perk_modifiers.get_rsmr(perk.hash.into(), inp);
                 if modifier != ReloadModifierResponse::default() {
@@ -1098,7 +1214,7 @@ impl Weapon {
                     value: perk.value,
                     calc_data: &calc_input,
                     pvp: _pvp,
-                    cached_data,
+                    sim_state,
                 };
                 let modifier = perk_modifiers.get_mmr(perk.hash.into(), inp);
                 if modifier != MagazineModifierResponse::default() {
@@ -1110,7 +1226,7 @@ impl Weapon {
                     value: perk.value,
                     calc_data: &calc_input,
                     pvp: _pvp,
-                    cached_data,
+                    sim_state,
                 };
                 let modifier = perk_modifiers.get_imr(perk.hash.into(), inp);
                 if modifier != InventoryModifierResponse::default() {
@@ -1122,7 +1238,19 @@ impl Weapon {
                     value: perk.value,
                     calc_data: &calc_input,
                     pvp: _pvp,
-                    cached_data,
+                    sim_state,
+                };
+                let modifier = perk_modifiers.get_drmr(perk.hash.into(), inp);
+                if modifier != DamageResistModifierResponse::default() {
+                    mod_response.drmr = Some(modifier);
+                }
+
+                let inp = ModifierResponseInput {
+                    is_enhanced: perk.enhanced,
+                    value: perk.value,
+                    calc_data: &calc_input,
+                    pvp: _pvp,
+                    sim_state,
                 };
                 let stat_mod = perk_modifiers.get_sbr(perk.hash.into(), inp);
                 let mut stat_buffer: HashMap<BungieHash, StatBump> = HashMap::new();
@@ -1137,8 +1265,8 @@ impl Weapon {
                         .or_insert(value);
                 }
                 mod_response.statbump = Some(stat_buffer);
-                return mod_response;
-            });
+                mod_response
+            };
             buffer.insert(perk.raw_hash, mod_buffer);
         }
 
@@ -3,38 +3,34 @@ use std::collections::HashMap;
 use crate::d2_enums::{StatHashes, WeaponType};
 
 use super::{
-    add_dmr, add_epr, add_flmr, add_fmr, add_hmr, add_mmr, add_rmr, add_rr, add_rsmr, add_sbr,
-    add_vmr, clamp,
+    clamp,
     lib::{
         CalculationInput, DamageModifierResponse, ExtraDamageResponse, FiringModifierResponse,
         FlinchModifierResponse, HandlingModifierResponse, MagazineModifierResponse,
         RangeModifierResponse, RefundResponse, ReloadModifierResponse, ReloadOverrideResponse,
     },
-    ModifierResponseInput, Perks,
+    ModifierResponseInput, Perks, PersistentModifierResponses,
 };
 
-pub fn origin_perks() {
-    add_rr(
+pub fn origin_perks(map: &mut PersistentModifierResponses) {
+    map.add_rr(
         Perks::VeistStinger,
-        Box::new(|_input: ModifierResponseInput| -> RefundResponse {
+        |_input: ModifierResponseInput| -> RefundResponse {
             if !(_input.value > 0) {
                 return RefundResponse::default();
             };
-            let data = _input.cached_data.get("veist_stinger");
-            let last_proc;
-            if data.is_none() {
-                last_proc = 0.0;
-            } else {
-                last_proc = *data.unwrap();
-            };
+            let last_proc = _input
+                .sim_state
+                .last_proc(Perks::VeistStinger)
+                .unwrap_or(0.0);
             let time_since_last_proc = _input.calc_data.time_total - last_proc;
             if time_since_last_proc >= 4.0 && _input.value > 0 {
                 let max_refund = _input.calc_data.base_mag - _input.calc_data.curr_mag;
                 let refund_amount = (_input.calc_data.base_mag / 4.0).ceil() as i32;
                 if max_refund > 0.0 {
                     _input
-                        .cached_data
-                        .insert("veist_stinger".to_string(), _input.calc_data.time_total);
+                        .sim_state
+                        .set_proc(Perks::VeistStinger, _input.calc_data.time_total);
                     let final_refund_ammount = clamp(refund_amount, 0, max_refund as i32);
                     return RefundResponse {
                         requirement: 1,
@@ -48,12 +44,12 @@ pub fn origin_perks() {
             } else {
                 RefundResponse::default()
             }
-        }),
+        },
     );
 
-    add_fmr(
+    map.add_fmr(
         Perks::VeistStinger,
-        Box::new(|_input: ModifierResponseInput| -> FiringModifierResponse {
+        |_input: ModifierResponseInput| -> FiringModifierResponse {
             FiringModifierResponse {
                 burst_delay_scale: if _input.calc_data.weapon_type == &WeaponType::BOW
                     && _input.value > 0
@@ -64,46 +60,46 @@ pub fn origin_perks() {
                 },
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::HakkeBreach,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let damage_mult = if _input.value > 0 { 0.3 } else { 0.0 };
             DamageModifierResponse {
                 impact_dmg_scale: 1.0 + damage_mult,
                 explosive_dmg_scale: 1.0 + damage_mult,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_rmr(
+    map.add_rmr(
         Perks::Alacrity,
-        Box::new(|_input: ModifierResponseInput| -> RangeModifierResponse {
+        |_input: ModifierResponseInput| -> RangeModifierResponse {
             let range_add = if _input.value > 0 { 20 } else { 0 };
             RangeModifierResponse {
                 range_stat_add: range_add,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::Alacrity,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             let reload_add = if _input.value > 0 { 50 } else { 0 };
             ReloadModifierResponse {
                 reload_stat_add: reload_add,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::Alacrity,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut map = HashMap::new();
             let range = if _input.value > 0 { 20 } else { 0 };
             let reload = if _input.value > 0 { 50 } else { 0 };
@@ -114,12 +110,12 @@ pub fn origin_perks() {
             map.insert(StatHashes::STABILITY.into(), stability);
             map.insert(StatHashes::AIM_ASSIST.into(), aim_assist);
             map
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::Ambush,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut map = HashMap::new();
             let range = if _input.is_enhanced { 30 } else { 20 };
             let handling = if _input.is_enhanced { 40 } else { 20 };
@@ -128,12 +124,12 @@ pub fn origin_perks() {
                 map.insert(StatHashes::HANDLING.into(), handling);
             }
             map
-        }),
+        },
     );
 
-    add_rmr(
+    map.add_rmr(
         Perks::Ambush,
-        Box::new(|_input: ModifierResponseInput| -> RangeModifierResponse {
+        |_input: ModifierResponseInput| -> RangeModifierResponse {
             let range_add = if _input.is_enhanced { 30 } else { 20 };
             if _input.calc_data.time_total < 2.0 && _input.value > 0 {
                 RangeModifierResponse {
@@ -143,12 +139,12 @@ pub fn origin_perks() {
             } else {
                 RangeModifierResponse::default()
             }
-        }),
+        },
     );
 
-    add_hmr(
+    map.add_hmr(
         Perks::Ambush,
-        Box::new(|_input: ModifierResponseInput| -> HandlingModifierResponse {
+        |_input: ModifierResponseInput| -> HandlingModifierResponse {
             let handling_add = if _input.is_enhanced { 40 } else { 20 };
             if _input.calc_data.time_total < 2.0 && _input.value > 0 {
                 HandlingModifierResponse {
@@ -158,12 +154,12 @@ pub fn origin_perks() {
             } else {
                 HandlingModifierResponse::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::Ambush,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             if _input.value == 0 || _input.pvp {
                 return DamageModifierResponse::default();
             }
@@ -178,12 +174,12 @@ pub fn origin_perks() {
                 explosive_dmg_scale: damage_mult,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_fmr(
+    map.add_fmr(
         Perks::Ambush,
-        Box::new(|_input: ModifierResponseInput| -> FiringModifierResponse {
+        |_input: ModifierResponseInput| -> FiringModifierResponse {
             FiringModifierResponse {
                 burst_delay_scale: if _input.calc_data.weapon_type == &WeaponType::BOW
                     && _input.value > 0
@@ -194,29 +190,27 @@ pub fn origin_perks() {
                 },
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_hmr(
+    map.add_hmr(
         Perks::HotSwap,
-        Box::new(
-            |_input: ModifierResponseInput| -> HandlingModifierResponse {
-                let handling_add = if _input.is_enhanced { 60 } else { 30 };
-                if _input.value > 0 {
-                    HandlingModifierResponse {
-                        stat_add: handling_add,
-                        ..Default::default()
-                    }
-                } else {
-                    HandlingModifierResponse::default()
+        |_input: ModifierResponseInput| -> HandlingModifierResponse {
+            let handling_add = if _input.is_enhanced { 60 } else { 30 };
+            if _input.value > 0 {
+                HandlingModifierResponse {
+                    stat_add: handling_add,
+                    ..Default::default()
                 }
-            },
-        ),
+            } else {
+                HandlingModifierResponse::default()
+            }
+        },
     );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::FluidDynamics,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             let reload_add = if _input.is_enhanced { 35 } else { 30 };
             if _input.calc_data.shots_fired_this_mag <= _input.calc_data.base_mag / 2.0 {
                 ReloadModifierResponse {
@@ -226,12 +220,12 @@ pub fn origin_perks() {
             } else {
                 ReloadModifierResponse::default()
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::FluidDynamics,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut map = HashMap::new();
             let reload = if _input.is_enhanced { 35 } else { 30 };
             let stability = if _input.is_enhanced { 25 } else { 20 };
@@ -242,12 +236,12 @@ pub fn origin_perks() {
                 map.insert(StatHashes::STABILITY.into(), stability);
             }
             map
-        }),
+        },
     );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::QuietMoment,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             if _input.value > 0 {
                 ReloadModifierResponse {
                     reload_stat_add: 40,
@@ -256,23 +250,23 @@ pub fn origin_perks() {
             } else {
                 ReloadModifierResponse::default()
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::QuietMoment,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut map = HashMap::new();
             if _input.value > 0 {
                 map.insert(StatHashes::RELOAD.into(), 40);
             }
             map
-        }),
+        },
     );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::BitterSpite,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             let val = clamp(_input.value, 0, 5) as i32;
             let mult = match val {
                 0 => 1.0,
@@ -287,22 +281,22 @@ pub fn origin_perks() {
                 reload_stat_add: val * 10,
                 reload_time_scale: mult,
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::BitterSpite,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut map = HashMap::new();
             let val = clamp(_input.value, 0, 5) as i32;
             map.insert(StatHashes::RELOAD.into(), val * 10);
             map
-        }),
+        },
     );
 
-    add_rmr(
+    map.add_rmr(
         Perks::RightHook,
-        Box::new(|_input: ModifierResponseInput| -> RangeModifierResponse {
+        |_input: ModifierResponseInput| -> RangeModifierResponse {
             let range_add = if _input.is_enhanced { 20 } else { 10 };
             if _input.value > 0 {
                 RangeModifierResponse {
@@ -312,12 +306,12 @@ pub fn origin_perks() {
             } else {
                 RangeModifierResponse::default()
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::RightHook,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut map = HashMap::new();
             let stat_bump = if _input.is_enhanced { 20 } else { 10 };
             if _input.value > 0 {
@@ -325,65 +319,59 @@ pub fn origin_perks() {
                 map.insert(StatHashes::RANGE.into(), stat_bump);
             }
             map
-        }),
+        },
     );
 
-    add_hmr(
+    map.add_hmr(
         Perks::SearchParty,
-        Box::new(
-            |_input: ModifierResponseInput| -> HandlingModifierResponse {
-                HandlingModifierResponse {
-                    ads_scale: 0.85,
-                    ..Default::default()
-                }
-            },
-        ),
+        |_input: ModifierResponseInput| -> HandlingModifierResponse {
+            HandlingModifierResponse {
+                ads_scale: 0.85,
+                ..Default::default()
+            }
+        },
     );
 
-    add_mmr(
+    map.add_mmr(
         Perks::RunnethOver,
-        Box::new(
-            |_input: ModifierResponseInput| -> MagazineModifierResponse {
-                let val = clamp(_input.value, 0, 5) as f64;
-                MagazineModifierResponse {
-                    magazine_scale: 1.0 + val * 0.1,
-                    ..Default::default()
-                }
-            },
-        ),
+        |_input: ModifierResponseInput| -> MagazineModifierResponse {
+            let val = clamp(_input.value, 0, 5) as f64;
+            MagazineModifierResponse {
+                magazine_scale: 1.0 + val * 0.1,
+                ..Default::default()
+            }
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::TexBalancedStock,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut map = HashMap::new();
             if _input.value > 0 {
                 map.insert(StatHashes::HANDLING.into(), 20);
                 map.insert(StatHashes::RELOAD.into(), 20);
             }
             map
-        }),
+        },
     );
 
-    add_hmr(
+    map.add_hmr(
         Perks::TexBalancedStock,
-        Box::new(
-            |_input: ModifierResponseInput| -> HandlingModifierResponse {
-                if _input.value > 0 {
-                    HandlingModifierResponse {
-                        stat_add: 50,
-                        ..Default::default()
-                    }
-                } else {
-                    HandlingModifierResponse::default()
+        |_input: ModifierResponseInput| -> HandlingModifierResponse {
+            if _input.value > 0 {
+                HandlingModifierResponse {
+                    stat_add: 50,
+                    ..Default::default()
                 }
-            },
-        ),
+            } else {
+                HandlingModifierResponse::default()
+            }
+        },
     );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::TexBalancedStock,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             if _input.value > 0 {
                 ReloadModifierResponse {
                     reload_stat_add: 20,
@@ -393,50 +381,48 @@ pub fn origin_perks() {
             } else {
                 ReloadModifierResponse::default()
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::SurosSynergy,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut out = HashMap::new();
             if _input.value > 0 {
                 out.insert(StatHashes::HANDLING.into(), 40);
             }
             out
-        }),
+        },
     );
 
-    add_hmr(
+    map.add_hmr(
         Perks::SurosSynergy,
-        Box::new(
-            |_input: ModifierResponseInput| -> HandlingModifierResponse {
-                if _input.value > 0 {
-                    HandlingModifierResponse {
-                        stat_add: 40,
-                        ..Default::default()
-                    }
-                } else {
-                    HandlingModifierResponse::default()
+        |_input: ModifierResponseInput| -> HandlingModifierResponse {
+            if _input.value > 0 {
+                HandlingModifierResponse {
+                    stat_add: 40,
+                    ..Default::default()
                 }
-            },
-        ),
+            } else {
+                HandlingModifierResponse::default()
+            }
+        },
     );
 
-    add_flmr(
+    map.add_flmr(
         Perks::SurosSynergy,
-        Box::new(|_input: ModifierResponseInput| -> FlinchModifierResponse {
+        |_input: ModifierResponseInput| -> FlinchModifierResponse {
             if _input.value > 0 {
                 FlinchModifierResponse { flinch_scale: 0.80 }
             } else {
                 FlinchModifierResponse::default()
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::HarmonicResonance,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut out = HashMap::new();
             if _input.value == 1 {
                 out.insert(StatHashes::HANDLING.into(), 10);
@@ -446,12 +432,12 @@ pub fn origin_perks() {
                 out.insert(StatHashes::HANDLING.into(), 20);
             }
             out
-        }),
+        },
     );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::HarmonicResonance,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             let stat_bump = if _input.value > 1 { 20 } else { 0 };
             if _input.value > 0 {
                 ReloadModifierResponse {
@@ -462,19 +448,17 @@ pub fn origin_perks() {
             } else {
                 ReloadModifierResponse::default()
             }
-        }),
+        },
     );
 
-    add_hmr(
+    map.add_hmr(
         Perks::HarmonicResonance,
-        Box::new(
-            |_input: ModifierResponseInput| -> HandlingModifierResponse {
-                let stat_bump = 10 * clamp(_input.value, 0, 2);
-                HandlingModifierResponse {
-                    stat_add: stat_bump as i32,
-                    ..Default::default()
-                }
-            },
-        ),
+        |_input: ModifierResponseInput| -> HandlingModifierResponse {
+            let stat_bump = 10 * clamp(_input.value, 0, 2);
+            HandlingModifierResponse {
+                stat_add: stat_bump as i32,
+                ..Default::default()
+            }
+        },
     );
 }
@@ -82,6 +82,8 @@ fn hash_to_perk_option_data(_hash: u32) -> Option<PerkOptionData> {
     match perk {
         //Meta perks
         Perks::BuiltIn => None,
+        //set on the pvp target, not the weapon
+        Perks::FrostArmor | Perks::WovenMail | Perks::ConcussiveDampener => None,
         Perks::RallyBarricade => Some(PerkOptionData::static_()),
         Perks::EmpRift => Some(PerkOptionData::static_()),
 
@@ -159,6 +161,7 @@ fn hash_to_perk_option_data(_hash: u32) -> Option<PerkOptionData> {
         Perks::TripleTap => Some(PerkOptionData::static_()),
         Perks::UnderPressure => Some(PerkOptionData::toggle()),
         Perks::PulseMonitor => Some(PerkOptionData::toggle()),
+        Perks::AutoLoadingHolster => Some(PerkOptionData::static_()),
 
         //season 2 | year 1
         //lmao bozo
@@ -176,11 +179,13 @@ fn hash_to_perk_option_data(_hash: u32) -> Option<PerkOptionData> {
         Perks::FeedingFrenzy => Some(PerkOptionData::stacking(5)),
         Perks::FourthTimesTheCharm => Some(PerkOptionData::static_()),
         Perks::RapidHit => Some(PerkOptionData::stacking(5)),
+        Perks::Dragonfly => Some(PerkOptionData::static_()),
 
         //season 5 | year 2
         Perks::ResevoirBurst => Some(PerkOptionData::toggle()),
         Perks::Surrounded => Some(PerkOptionData::toggle()),
         Perks::AirAssault => Some(PerkOptionData::stacking(2)),
+        Perks::WhirlwindBlade => Some(PerkOptionData::stacking(5)),
 
         //season 6 | year 2
         Perks::FiringLine => Some(PerkOptionData::toggle()),
@@ -234,6 +239,7 @@ fn hash_to_perk_option_data(_hash: u32) -> Option<PerkOptionData> {
         Perks::FireFly => Some(PerkOptionData::toggle()),
         Perks::DangerZone => Some(PerkOptionData::toggle()),
         Perks::TunnelVision => Some(PerkOptionData::toggle()),
+        Perks::RelentlessStrikes => Some(PerkOptionData::toggle()),
 
         //season 15 | year 4
         Perks::Encore => Some(PerkOptionData::stacking(4)),
@@ -345,6 +351,9 @@ fn hash_to_perk_option_data(_hash: u32) -> Option<PerkOptionData> {
         Perks::TargetingMod => Some(PerkOptionData::stacking(3)),
         Perks::UnflinchingMod => Some(PerkOptionData::stacking(3)),
         Perks::SurgeMod => Some(PerkOptionData::stacking(3)),
+        Perks::AntiBarrierMod => Some(PerkOptionData::static_()),
+        Perks::OverloadMod => Some(PerkOptionData::static_()),
+        Perks::UnstoppableMod => Some(PerkOptionData::static_()),
         Perks::OnYourMark => Some(PerkOptionData::stacking(3)),
         Perks::Frequency => Some(PerkOptionData::toggle()),
         Perks::Tempering => Some(PerkOptionData::toggle()),
@@ -0,0 +1,174 @@
+use crate::{d2_enums::Seconds, weapons::Weapon};
+
+use super::Perks;
+
+///Events during a fight that give a perk stacks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PerkTrigger {
+    Kill,
+    PrecisionHit,
+    Reload,
+    ///First reload after a kill
+    ReloadAfterKill,
+    Swap,
+    ///Fires once the fight has gone on this long
+    TimeInCombat(Seconds),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PerkStateRule {
+    pub trigger: PerkTrigger,
+    ///None lasts for the rest of the fight
+    pub duration: Option<Seconds>,
+    pub max_stacks: u32,
+    pub stacks_per_trigger: u32,
+}
+impl PerkStateRule {
+    fn new(_trigger: PerkTrigger, _duration: Option<Seconds>, _max_stacks: u32) -> Self {
+        PerkStateRule {
+            trigger: _trigger,
+            duration: _duration,
+            max_stacks: _max_stacks,
+            stacks_per_trigger: 1,
+        }
+    }
+}
+
+///How a perk gains and loses stacks over time, perks without a rule keep the value the user set
+pub fn perk_state_rule(_perk: Perks, _enhanced: bool) -> Option<PerkStateRule> {
+    match _perk {
+        Perks::Rampage => Some(PerkStateRule::new(
+            PerkTrigger::Kill,
+            Some(if _enhanced { 5.0 } else { 4.0 }),
+            3,
+        )),
+        Perks::KillClip => Some(PerkStateRule::new(
+            PerkTrigger::ReloadAfterKill,
+            Some(if _enhanced { 5.0 } else { 4.0 }),
+            1,
+        )),
+        Perks::FeedingFrenzy => Some(PerkStateRule::new(PerkTrigger::Kill, Some(3.5), 5)),
+        Perks::AdrenalineJunkie => Some(PerkStateRule::new(
+            PerkTrigger::Kill,
+            Some(if _enhanced { 6.0 } else { 4.5 }),
+            5,
+        )),
+        Perks::Frenzy => Some(PerkStateRule::new(PerkTrigger::TimeInCombat(12.0), None, 1)),
+        Perks::FireFly => Some(PerkStateRule::new(PerkTrigger::Kill, Some(5.0), 1)),
+        Perks::RapidHit => Some(PerkStateRule::new(PerkTrigger::PrecisionHit, Some(2.0), 5)),
+        //armed once the weapon is out, the refund keeps its own 4s cooldown timer
+        Perks::VeistStinger => Some(PerkStateRule::new(PerkTrigger::Swap, None, 1)),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PerkState {
+    hash: u32,
+    rule: PerkStateRule,
+    stacks: u32,
+    expires: Seconds,
+    triggered: bool,
+}
+impl PerkState {
+    fn activate(&mut self, _time: Seconds) {
+        self.stacks = (self.stacks + self.rule.stacks_per_trigger).min(self.rule.max_stacks);
+        self.expires = self
+            .rule
+            .duration
+            .map_or(f64::INFINITY, |duration| _time + duration);
+        self.triggered = true;
+    }
+}
+
+///Stacks of the weapons triggered perks over a simulated fight.
+///Stacks the user set on a perk count as gained right before the fight starts.
+#[derive(Debug, Clone, Default)]
+pub struct PerkStateMachine {
+    states: Vec<PerkState>,
+    kill_pending: bool,
+}
+impl PerkStateMachine {
+    pub fn new(_weapon: &Weapon) -> Self {
+        let mut states = Vec::new();
+        for perk in _weapon.perks.values() {
+            let rule = match perk_state_rule(Perks::from(perk.hash), perk.enhanced) {
+                Some(rule) => rule,
+                None => continue,
+            };
+            let stacks = perk.value.min(rule.max_stacks);
+            let expires = match (stacks, rule.duration) {
+                (0, _) => f64::NEG_INFINITY,
+                (_, Some(duration)) => duration,
+                (_, None) => f64::INFINITY,
+            };
+            states.push(PerkState {
+                hash: perk.hash,
+                rule,
+                stacks,
+                expires,
+                triggered: stacks > 0,
+            });
+        }
+        PerkStateMachine {
+            states,
+            kill_pending: false,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    pub fn stacks(&self, _hash: u32) -> Option<u32> {
+        self.states
+            .iter()
+            .find(|state| state.hash == _hash)
+            .map(|state| state.stacks)
+    }
+
+    pub fn trigger(&mut self, _trigger: PerkTrigger, _time: Seconds) {
+        match _trigger {
+            PerkTrigger::Kill => self.kill_pending = true,
+            PerkTrigger::Reload if self.kill_pending => {
+                self.kill_pending = false;
+                self.trigger(PerkTrigger::ReloadAfterKill, _time);
+            }
+            _ => (),
+        }
+        self.advance(_time);
+        for state in self.states.iter_mut() {
+            if state.rule.trigger == _trigger {
+                state.activate(_time);
+            }
+        }
+    }
+
+    ///Drops expired stacks and fires time in combat triggers
+    pub fn advance(&mut self, _time: Seconds) {
+        for state in self.states.iter_mut() {
+            if _time > state.expires {
+                state.stacks = 0;
+                state.expires = f64::NEG_INFINITY;
+            }
+            if let PerkTrigger::TimeInCombat(delay) = state.rule.trigger {
+                if _time >= delay && !state.triggered {
+                    state.activate(delay);
+                }
+            }
+        }
+    }
+
+    ///Writes the current stacks into the weapons perk values, true if any of them changed
+    pub fn apply(&self, _weapon: &mut Weapon) -> bool {
+        let mut changed = false;
+        for state in &self.states {
+            let current = _weapon.perks.get(&state.hash).map(|perk| perk.value);
+            if current.is_some_and(|value| value != state.stacks) {
+                _weapon.change_perk_val(state.hash, state.stacks);
+                changed = true;
+            }
+        }
+        changed
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use crate::d2_enums::Seconds;
+
+use super::Perks;
+
+///Buffs in the same category don't stack, only the strongest one applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuffCategory {
+    Empowering,
+    Surge,
+    Debuff,
+}
+
+///State the perk functions share over a calculation, sims keep one for the whole fight
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationState {
+    empowering: f64,
+    surge: f64,
+    debuff: f64,
+    ///Health the target has left over the 70 health of a guardian without shields
+    pub target_health: f64,
+    timers: HashMap<Perks, Seconds>,
+}
+impl Default for SimulationState {
+    fn default() -> Self {
+        SimulationState {
+            empowering: 1.0,
+            surge: 1.0,
+            debuff: 1.0,
+            target_health: 1.0,
+            timers: HashMap::new(),
+        }
+    }
+}
+impl SimulationState {
+    fn buff_mut(&mut self, _category: BuffCategory) -> &mut f64 {
+        match _category {
+            BuffCategory::Empowering => &mut self.empowering,
+            BuffCategory::Surge => &mut self.surge,
+            BuffCategory::Debuff => &mut self.debuff,
+        }
+    }
+
+    ///Strongest buff of the category applied since the last reset
+    pub fn buff(&self, _category: BuffCategory) -> f64 {
+        match _category {
+            BuffCategory::Empowering => self.empowering,
+            BuffCategory::Surge => self.surge,
+            BuffCategory::Debuff => self.debuff,
+        }
+    }
+
+    ///Applies a buff and returns the multiplier it adds on top of the ones already applied,
+    ///1.0 if a stronger buff of the same category is already up
+    pub fn stack_buff(&mut self, _category: BuffCategory, _buff: f64) -> f64 {
+        let current = self.buff_mut(_category);
+        if *current >= _buff {
+            1.0
+        } else {
+            let added = _buff / *current;
+            *current = _buff;
+            added
+        }
+    }
+
+    ///Clears the non stacking buffs, call once per shot before the modifiers are
+    ///evaluated so every shot gets the strongest buff again
+    pub fn reset_buffs(&mut self) {
+        self.empowering = 1.0;
+        self.surge = 1.0;
+        self.debuff = 1.0;
+    }
+
+    ///Last time a perk with a cooldown went off
+    pub fn last_proc(&self, _perk: Perks) -> Option<Seconds> {
+        self.timers.get(&_perk).copied()
+    }
+
+    pub fn set_proc(&mut self, _perk: Perks, _time: Seconds) {
+        self.timers.insert(_perk, _time);
+    }
+}
@@ -3,44 +3,43 @@ use std::collections::HashMap;
 use crate::d2_enums::{AmmoType, StatHashes, WeaponType};
 
 use super::{
-    add_dmr, add_epr, add_flmr, add_fmr, add_hmr, add_mmr, add_rmr, add_rr, add_rsmr, add_sbr,
-    add_vmr, clamp,
+    clamp,
     lib::{
         CalculationInput, DamageModifierResponse, ExplosivePercentResponse, ExtraDamageResponse,
         FiringModifierResponse, FlinchModifierResponse, HandlingModifierResponse,
         MagazineModifierResponse, RangeModifierResponse, RefundResponse, ReloadModifierResponse,
         ReloadOverrideResponse,
     },
-    ModifierResponseInput, Perks,
+    ModifierResponseInput, Perks, PersistentModifierResponses,
 };
 
-pub fn year_2_perks() {
-    add_sbr(
+pub fn year_2_perks(map: &mut PersistentModifierResponses) {
+    map.add_sbr(
         Perks::AirAssault,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut stats = HashMap::new();
             let ae_per_stack = if _input.is_enhanced { 35 } else { 30 };
             let ae = ae_per_stack * _input.value as i32;
             stats.insert(StatHashes::AIRBORNE.into(), ae);
             stats
-        }),
+        },
     );
 
-    add_fmr(
+    map.add_fmr(
         Perks::ArchersTempo,
-        Box::new(|_input: ModifierResponseInput| -> FiringModifierResponse {
+        |_input: ModifierResponseInput| -> FiringModifierResponse {
             FiringModifierResponse {
                 burst_delay_scale: if _input.value > 0 { 0.75 } else { 1.0 },
                 burst_delay_add: 0.0,
                 inner_burst_scale: 1.0,
                 burst_size_add: 0.0,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::ExplosiveHead,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             if _input.pvp {
                 DamageModifierResponse::default()
             } else {
@@ -50,27 +49,43 @@ pub fn year_2_perks() {
                     crit_scale: 1.0,
                 }
             }
-        }),
+        },
     );
 
-    add_epr(
+    map.add_epr(
         Perks::ExplosiveHead,
-        Box::new(
-            |_input: ModifierResponseInput| -> ExplosivePercentResponse {
-                ExplosivePercentResponse {
-                    percent: 0.5,
-                    delyed: if _input.pvp { 0.0 } else { 0.2 },
-                    retain_base_total: true,
-                }
-            },
-        ),
+        |_input: ModifierResponseInput| -> ExplosivePercentResponse {
+            ExplosivePercentResponse {
+                percent: 0.5,
+                delyed: if _input.pvp { 0.0 } else { 0.2 },
+                retain_base_total: true,
+            }
+        },
     );
 
-    add_rsmr(
+    map.add_kedr(
+        Perks::Dragonfly,
+        |_input: ModifierResponseInput| -> ExtraDamageResponse {
+            //precision kills explode, sims count every kill as a precision kill
+            let dmg = if _input.pvp { 45.0 } else { 105.0 };
+            ExtraDamageResponse {
+                additive_damage: dmg,
+                times_to_hit: 1,
+                increment_total_time: false,
+                time_for_additive_damage: 0.0,
+                hit_at_same_time: true,
+                is_dot: false,
+                weapon_scale: true,
+                crit_scale: false,
+                combatant_scale: true,
+            }
+        },
+    );
+
+    map.add_rsmr(
         Perks::FeedingFrenzy,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             let val = clamp(_input.value, 0, 5);
-            let duration = 3.5;
             let mut reload_mult = 1.0;
             let mut reload = 0;
             if val == 1 {
@@ -89,23 +104,18 @@ pub fn year_2_perks() {
                 reload = 100;
                 reload_mult = 0.8;
             };
-            if _input.calc_data.time_total > duration {
-                reload = 0;
-                reload_mult = 1.0;
-            };
             ReloadModifierResponse {
                 reload_stat_add: reload,
                 reload_time_scale: reload_mult,
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::FeedingFrenzy,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut stats = HashMap::new();
             let val = clamp(_input.value, 0, 5);
-            let duration = 3.5;
             let mut reload = 0;
             if val == 1 {
                 reload = 8;
@@ -118,17 +128,14 @@ pub fn year_2_perks() {
             } else if val == 5 {
                 reload = 100;
             };
-            if _input.calc_data.time_total > duration {
-                reload = 0;
-            };
             stats.insert(StatHashes::RELOAD.into(), reload);
             stats
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::FiringLine,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut crit_mult = 1.0;
             if _input.value > 0 {
                 crit_mult = 1.2;
@@ -138,24 +145,24 @@ pub fn year_2_perks() {
                 explosive_dmg_scale: 1.0,
                 impact_dmg_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_rr(
+    map.add_rr(
         Perks::FourthTimesTheCharm,
-        Box::new(|_input: ModifierResponseInput| -> RefundResponse {
+        |_input: ModifierResponseInput| -> RefundResponse {
             RefundResponse {
                 crit: true,
                 requirement: 4,
                 refund_mag: 2,
                 refund_reserves: 0,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::KillingTally,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let val = clamp(_input.value, 0, 3);
             let mut damage_mult = 0.1 * val as f64;
             if _input.pvp {
@@ -169,32 +176,30 @@ pub fn year_2_perks() {
                 explosive_dmg_scale: 1.0 + damage_mult,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_mmr(
+    map.add_mmr(
         Perks::OverFlow,
-        Box::new(
-            |_input: ModifierResponseInput| -> MagazineModifierResponse {
-                let mut mag_scale = if _input.value > 0 { 2.0 } else { 1.0 };
-                if _input.is_enhanced && _input.value > 0 {
-                    mag_scale *= 1.1;
-                };
-                if _input.calc_data.total_shots_fired > 0.0 {
-                    mag_scale = 1.0;
-                };
-                MagazineModifierResponse {
-                    magazine_stat_add: 0,
-                    magazine_scale: mag_scale,
-                    magazine_add: 0.0,
-                }
-            },
-        ),
+        |_input: ModifierResponseInput| -> MagazineModifierResponse {
+            let mut mag_scale = if _input.value > 0 { 2.0 } else { 1.0 };
+            if _input.is_enhanced && _input.value > 0 {
+                mag_scale *= 1.1;
+            };
+            if _input.calc_data.total_shots_fired > 0.0 {
+                mag_scale = 1.0;
+            };
+            MagazineModifierResponse {
+                magazine_stat_add: 0,
+                magazine_scale: mag_scale,
+                magazine_add: 0.0,
+            }
+        },
     );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::RapidHit,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             let values = vec![
                 (0, 1.0),
                 (5, 0.99),
@@ -212,12 +217,12 @@ pub fn year_2_perks() {
                 reload_stat_add: values[entry_to_get as usize].0,
                 reload_time_scale: values[entry_to_get as usize].1,
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::RapidHit,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let rel_values = vec![0, 5, 30, 35, 45, 60];
             let stab_values = vec![0, 2, 12, 14, 18, 25];
             let entry_to_get = clamp(
@@ -232,12 +237,12 @@ pub fn year_2_perks() {
                 stab_values[entry_to_get as usize],
             );
             stats
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::ResevoirBurst,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut damage_mult = 1.0;
             if _input.calc_data.curr_mag >= _input.calc_data.base_mag {
                 damage_mult = 1.25;
@@ -247,12 +252,12 @@ pub fn year_2_perks() {
                 explosive_dmg_scale: damage_mult,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::Surrounded,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut damage_mult = 1.0;
             if _input.value > 0 {
                 damage_mult = if *_input.calc_data.weapon_type == WeaponType::SWORD {
@@ -269,12 +274,24 @@ pub fn year_2_perks() {
                 explosive_dmg_scale: damage_mult,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
+        Perks::WhirlwindBlade,
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
+            let damage_mult = 1.0 + clamp(_input.value, 0, 5) as f64 * 0.04;
+            DamageModifierResponse {
+                impact_dmg_scale: damage_mult,
+                explosive_dmg_scale: damage_mult,
+                crit_scale: 1.0,
+            }
+        },
+    );
+
+    map.add_dmr(
         Perks::FullCourt,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut damage_mult = 1.0;
             if _input.value > 0 {
                 damage_mult = 1.25;
@@ -284,12 +301,12 @@ pub fn year_2_perks() {
                 explosive_dmg_scale: damage_mult,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::Swashbuckler,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let val = clamp(_input.value, 0, 5);
             let duration = if _input.is_enhanced { 6.0 } else { 4.5 };
             let mut dmg_boost = 0.067 * val as f64;
@@ -301,12 +318,12 @@ pub fn year_2_perks() {
                 explosive_dmg_scale: 1.0 + dmg_boost,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::MultikillClip,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let val = clamp(_input.value, 0, 5);
             let mut damage_mult = (1.0 / 6.0) * val as f64;
             if _input.calc_data.num_reloads > 0.0 {
@@ -317,12 +334,12 @@ pub fn year_2_perks() {
                 explosive_dmg_scale: 1.0 + damage_mult,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::ExplosiveLight,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let shots = if _input.is_enhanced { 7.0 } else { 6.0 };
             let shots_left = _input.value as f64 * shots - _input.calc_data.total_shots_fired;
             if shots_left <= 0.0 {
@@ -365,55 +382,53 @@ pub fn year_2_perks() {
                 impact_dmg_scale: 1.25,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::ExplosiveLight,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut out = HashMap::new();
             if _input.value > 0 {
                 out.insert(StatHashes::BLAST_RADIUS.into(), 100);
             };
             out
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::EyeOfTheStorm,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut out = HashMap::new();
             if _input.value > 0 {
                 out.insert(StatHashes::HANDLING.into(), 30);
             };
             out
-        }),
+        },
     );
 
-    add_hmr(
+    map.add_hmr(
         Perks::EyeOfTheStorm,
-        Box::new(
-            |_input: ModifierResponseInput| -> HandlingModifierResponse {
-                if _input.value > 0 {
-                    HandlingModifierResponse {
-                        stat_add: 30,
-                        ..Default::default()
-                    }
-                } else {
-                    HandlingModifierResponse::default()
+        |_input: ModifierResponseInput| -> HandlingModifierResponse {
+            if _input.value > 0 {
+                HandlingModifierResponse {
+                    stat_add: 30,
+                    ..Default::default()
                 }
-            },
-        ),
+            } else {
+                HandlingModifierResponse::default()
+            }
+        },
     );
 
-    add_flmr(
+    map.add_flmr(
         Perks::NoDistractions,
-        Box::new(|_input: ModifierResponseInput| -> FlinchModifierResponse {
+        |_input: ModifierResponseInput| -> FlinchModifierResponse {
             if _input.value > 0 {
                 FlinchModifierResponse { flinch_scale: 0.65 }
             } else {
                 FlinchModifierResponse::default()
             }
-        }),
+        },
     );
 }
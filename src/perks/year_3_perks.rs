@@ -6,32 +6,29 @@ use crate::{
 };
 
 use super::{
-    add_dmr, add_epr, add_fmr, add_hmr, add_mmr, add_rmr, add_rsmr, add_sbr, add_vmr,
     lib::{
         CalculationInput, DamageModifierResponse, ExtraDamageResponse, FiringModifierResponse,
         HandlingModifierResponse, MagazineModifierResponse, RangeModifierResponse, RefundResponse,
         ReloadModifierResponse,
     },
-    ModifierResponseInput, Perks,
+    ModifierResponseInput, Perks, PersistentModifierResponses,
 };
 
-pub fn year_3_perks() {
-    add_mmr(
+pub fn year_3_perks(map: &mut PersistentModifierResponses) {
+    map.add_mmr(
         Perks::ClownCartridge,
-        Box::new(
-            |_input: ModifierResponseInput| -> MagazineModifierResponse {
-                MagazineModifierResponse {
-                    magazine_add: 0.0,
-                    magazine_scale: 1.5,
-                    magazine_stat_add: 0,
-                }
-            },
-        ),
+        |_input: ModifierResponseInput| -> MagazineModifierResponse {
+            MagazineModifierResponse {
+                magazine_add: 0.0,
+                magazine_scale: 1.5,
+                magazine_stat_add: 0,
+            }
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::ElementalCapacitor,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut stats = HashMap::new();
             let ev = if _input.is_enhanced { 5 } else { 0 };
             if _input.value == 1 {
@@ -46,28 +43,26 @@ pub fn year_3_perks() {
                 stats.insert(StatHashes::AIRBORNE.into(), 20 + ev);
             };
             stats
-        }),
+        },
     );
 
-    add_hmr(
+    map.add_hmr(
         Perks::ElementalCapacitor,
-        Box::new(
-            |_input: ModifierResponseInput| -> HandlingModifierResponse {
-                let mut handling = 0;
-                if _input.value == 3 {
-                    handling = if _input.is_enhanced { 55 } else { 50 };
-                };
-                HandlingModifierResponse {
-                    stat_add: handling,
-                    ..Default::default()
-                }
-            },
-        ),
+        |_input: ModifierResponseInput| -> HandlingModifierResponse {
+            let mut handling = 0;
+            if _input.value == 3 {
+                handling = if _input.is_enhanced { 55 } else { 50 };
+            };
+            HandlingModifierResponse {
+                stat_add: handling,
+                ..Default::default()
+            }
+        },
     );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::ElementalCapacitor,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             let mut reload = 0;
             if _input.value == 2 {
                 reload = if _input.is_enhanced { 55 } else { 50 };
@@ -76,24 +71,24 @@ pub fn year_3_perks() {
                 reload_stat_add: reload,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::KillingWind,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut stats = HashMap::new();
             if _input.value > 0 {
                 stats.insert(StatHashes::HANDLING.into(), 40);
                 stats.insert(StatHashes::RANGE.into(), 20);
             };
             stats
-        }),
+        },
     );
 
-    add_rmr(
+    map.add_rmr(
         Perks::KillingWind,
-        Box::new(|_input: ModifierResponseInput| -> RangeModifierResponse {
+        |_input: ModifierResponseInput| -> RangeModifierResponse {
             if _input.value > 0 {
                 RangeModifierResponse {
                     range_stat_add: 20,
@@ -109,39 +104,37 @@ pub fn year_3_perks() {
                     range_hip_scale: 1.0,
                 }
             }
-        }),
+        },
     );
 
-    add_hmr(
+    map.add_hmr(
         Perks::KillingWind,
-        Box::new(
-            |_input: ModifierResponseInput| -> HandlingModifierResponse {
-                if _input.value > 0 {
-                    HandlingModifierResponse {
-                        stat_add: 40,
-                        ..Default::default()
-                    }
-                } else {
-                    HandlingModifierResponse::default()
+        |_input: ModifierResponseInput| -> HandlingModifierResponse {
+            if _input.value > 0 {
+                HandlingModifierResponse {
+                    stat_add: 40,
+                    ..Default::default()
                 }
-            },
-        ),
+            } else {
+                HandlingModifierResponse::default()
+            }
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::LastingImpression,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             DamageModifierResponse {
                 impact_dmg_scale: 1.0,
                 explosive_dmg_scale: 1.25,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::Vorpal,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let mut buff = 1.0;
             if *_input.calc_data.enemy_type == EnemyType::BOSS
                 || *_input.calc_data.enemy_type == EnemyType::MINIBOSS
@@ -161,6 +154,6 @@ pub fn year_3_perks() {
                 explosive_dmg_scale: buff,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 }
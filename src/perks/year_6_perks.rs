@@ -3,20 +3,20 @@ use std::collections::HashMap;
 use crate::d2_enums::{AmmoType, BungieHash, DamageType, StatBump, StatHashes, WeaponType};
 
 use super::{
-    add_dmr, add_epr, add_fmr, add_hmr, add_mmr, add_rmr, add_rsmr, add_sbr, add_vmr, clamp,
+    clamp,
     lib::{
         CalculationInput, DamageModifierResponse, ExplosivePercentResponse, ExtraDamageResponse,
         FiringModifierResponse, HandlingModifierResponse, InventoryModifierResponse,
         MagazineModifierResponse, RangeModifierResponse, RefundResponse, ReloadModifierResponse,
         VelocityModifierResponse,
     },
-    ModifierResponseInput, Perks, add_imr,
+    ModifierResponseInput, Perks, PersistentModifierResponses,
 };
 
-pub fn year_6_perks() {
-    add_sbr(
+pub fn year_6_perks(map: &mut PersistentModifierResponses) {
+    map.add_sbr(
         Perks::KeepAway,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut map = HashMap::new();
             let mut range_bonus = 0;
             let mut reload_bonus = 0;
@@ -27,34 +27,34 @@ pub fn year_6_perks() {
             map.insert(StatHashes::RANGE.into(), range_bonus);
             map.insert(StatHashes::RELOAD.into(), reload_bonus);
             map
-        }),
+        },
     );
 
-    add_rmr(
+    map.add_rmr(
         Perks::KeepAway,
-        Box::new(|_input: ModifierResponseInput| -> RangeModifierResponse {
+        |_input: ModifierResponseInput| -> RangeModifierResponse {
             let range_bonus = if _input.value > 0 { 10 } else { 0 };
             RangeModifierResponse {
                 range_stat_add: range_bonus,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::KeepAway,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             let reload_bonus = if _input.value > 0 { 30 } else { 0 };
             ReloadModifierResponse {
                 reload_stat_add: reload_bonus,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::FieldTested,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut map = HashMap::new();
             if _input.value > 4 {
                 map.insert(StatHashes::RANGE.into(), 20);
@@ -73,7 +73,7 @@ pub fn year_6_perks() {
                 map.insert(StatHashes::RANGE.into(), 3);
             }
             map
-        }),
+        },
     );
 
     // add_hmr(
@@ -89,9 +89,9 @@ pub fn year_6_perks() {
     //     ),
     // );
 
-    add_rsmr(
+    map.add_rsmr(
         Perks::FieldTested,
-        Box::new(|_input: ModifierResponseInput| -> ReloadModifierResponse {
+        |_input: ModifierResponseInput| -> ReloadModifierResponse {
             let reload_bump;
             if _input.value > 4 {
                 reload_bump = 55;
@@ -110,12 +110,12 @@ pub fn year_6_perks() {
                 reload_stat_add: reload_bump,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_rmr(
+    map.add_rmr(
         Perks::FieldTested,
-        Box::new(|_input: ModifierResponseInput| -> RangeModifierResponse {
+        |_input: ModifierResponseInput| -> RangeModifierResponse {
             let range_bump;
             if _input.value > 4 {
                 range_bump = 20;
@@ -134,12 +134,12 @@ pub fn year_6_perks() {
                 range_stat_add: range_bump,
                 ..Default::default()
             }
-        }),
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::ParacausalAffinity,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             if _input.value > 0 {
                 DamageModifierResponse {
                     explosive_dmg_scale: 1.2,
@@ -149,103 +149,95 @@ pub fn year_6_perks() {
             } else {
                 DamageModifierResponse::default()
             }
-        }),
+        },
     );
 
-    add_mmr(
+    map.add_mmr(
         Perks::EnviousAssasin,
-        Box::new(
-            |_input: ModifierResponseInput| -> MagazineModifierResponse {
-                let val = clamp(_input.value, 0, 15) as f64;
-                if _input.calc_data.total_shots_fired == 0.0 {
-                    let mut mag_mult = 1.0;
-                    if *_input.calc_data.ammo_type == AmmoType::PRIMARY {
-                        mag_mult += 0.1 * val;
-                    } else {
-                        mag_mult += 0.2 * val;
-                    };
-                    return MagazineModifierResponse {
-                        magazine_stat_add: 0,
-                        magazine_scale: clamp(mag_mult, 1.0, 2.5),
-                        magazine_add: 0.0,
-                    };
+        |_input: ModifierResponseInput| -> MagazineModifierResponse {
+            let val = clamp(_input.value, 0, 15) as f64;
+            if _input.calc_data.total_shots_fired == 0.0 {
+                let mut mag_mult = 1.0;
+                if *_input.calc_data.ammo_type == AmmoType::PRIMARY {
+                    mag_mult += 0.1 * val;
+                } else {
+                    mag_mult += 0.2 * val;
                 };
-                MagazineModifierResponse {
+                return MagazineModifierResponse {
                     magazine_stat_add: 0,
-                    magazine_scale: 1.0,
+                    magazine_scale: clamp(mag_mult, 1.0, 2.5),
                     magazine_add: 0.0,
-                }
-            },
-        ),
+                };
+            };
+            MagazineModifierResponse {
+                magazine_stat_add: 0,
+                magazine_scale: 1.0,
+                magazine_add: 0.0,
+            }
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::CollectiveAction,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             let dmg_boost = if _input.value > 0 { 1.2 } else { 1.0 };
             DamageModifierResponse {
                 impact_dmg_scale: dmg_boost,
                 explosive_dmg_scale: dmg_boost,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 
-    add_sbr(
+    map.add_sbr(
         Perks::Discord,
-        Box::new(|_input: ModifierResponseInput| -> HashMap<u32, i32> {
+        |_input: ModifierResponseInput| -> HashMap<u32, i32> {
             let mut map = HashMap::new();
             if _input.value > 0 {
                 map.insert(StatHashes::AIRBORNE.into(), 30);
             }
             map
-        }),
+        },
     );
 
-    add_hmr(
+    map.add_hmr(
         Perks::Discord,
-        Box::new(
-            |_input: ModifierResponseInput| -> HandlingModifierResponse {
-                let ads_mult = if _input.value > 0 { 0.75 } else { 1.0 };
-                HandlingModifierResponse {
-                    ads_scale: ads_mult,
-                    ..Default::default()
-                }
-            },
-        ),
+        |_input: ModifierResponseInput| -> HandlingModifierResponse {
+            let ads_mult = if _input.value > 0 { 0.75 } else { 1.0 };
+            HandlingModifierResponse {
+                ads_scale: ads_mult,
+                ..Default::default()
+            }
+        },
     );
 
-    add_mmr(
+    map.add_mmr(
         Perks::Bipod,
-        Box::new(
-            |_input: ModifierResponseInput| -> MagazineModifierResponse {
-                MagazineModifierResponse {
-                    magazine_scale: 2.0,
-                    ..Default::default()
-                }
-            },
-        ),
+        |_input: ModifierResponseInput| -> MagazineModifierResponse {
+            MagazineModifierResponse {
+                magazine_scale: 2.0,
+                ..Default::default()
+            }
+        },
     );
 
-    add_imr(Perks::Bipod,
-        Box::new(
-            |_input: ModifierResponseInput| -> InventoryModifierResponse {
-                InventoryModifierResponse {
-                    inv_scale: 1.75,
-                    ..Default::default()
-                }
-            },
-        ),
+    map.add_imr(Perks::Bipod,
+        |_input: ModifierResponseInput| -> InventoryModifierResponse {
+            InventoryModifierResponse {
+                inv_scale: 1.75,
+                ..Default::default()
+            }
+        },
     );
 
-    add_dmr(
+    map.add_dmr(
         Perks::Bipod,
-        Box::new(|_input: ModifierResponseInput| -> DamageModifierResponse {
+        |_input: ModifierResponseInput| -> DamageModifierResponse {
             DamageModifierResponse {
                 impact_dmg_scale: 0.6,
                 explosive_dmg_scale: 0.6,
                 crit_scale: 1.0,
             }
-        }),
+        },
     );
 }
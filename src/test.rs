@@ -1,10 +1,34 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use num_traits::{Float, Zero};
 
 use crate::{
+    activity::{damage_calc::DifficultyOptions, Activity, Player, PlayerBuff},
+    build_spec::{BuildSpec, PerkSpec},
+    calculator::Calculator,
     d2_enums::{AmmoType, DamageType, StatHashes, WeaponType},
-    weapons::{Stat, Weapon},
+    enemies::{enemy_data::EnemyCatalogue, Enemy, EnemyType, ResistPhase},
+    formula_database::{FormulaDatabase, CURRENT_PATCH},
+    formula_validator::{validate_formulas, IssueKind, Severity},
+    perks::{
+        get_damage_resist_modifier, get_stat_bumps,
+        lib::RefundResponse,
+        perk_state::{PerkStateMachine, PerkTrigger},
+        simulation_state::{BuffCategory, SimulationState},
+        Perk, Perks,
+    },
+    types::rs_types::RangeResponse,
+    weapons::{
+        accuracy_calc::{AccuracyModel, MonteCarloSettings, Percentiles, SeededRng},
+        dps_calc::{calc_refund, AddClearWave, StartingMagState},
+        falloff_calc::Engagement,
+        optimizer::{Objective, OptimizerInput},
+        patch_calc::PatchDiffCategory,
+        pvp_ttk_calc::{OvershieldType, PvpHealing, PvpTarget},
+        rotation_calc::{FiringPlan, Loadout, RotationResponse},
+        sword_calc::SwingType,
+        Stat, Weapon,
+    },
     PERS_DATA,
 };
 
@@ -18,6 +42,16 @@ fn cmp_floats_delta<T: Float + Zero>(a: T, b: T, delta: T) -> bool {
     (a - b).abs() < delta
 }
 
+fn perk(hash: Perks, value: u32) -> Perk {
+    Perk {
+        stat_buffs: HashMap::new(),
+        enhanced: false,
+        value,
+        hash: hash as u32,
+        raw_hash: hash as u32,
+    }
+}
+
 fn setup_pulse() {
     let vec = Vec::<u8>::from("bozo".to_string());
     let mut hash = 0;
@@ -28,6 +62,7 @@ fn setup_pulse() {
         }
     }
     let mut new_weapon = Weapon::generate_weapon(
+        &FormulaDatabase::default(),
         hash,                         //bozo as u32 :)
         13,          //pulse
         69420,       //test pulse
@@ -179,6 +214,7 @@ fn setup_bow() {
         }
     }
     let mut new_weapon = Weapon::generate_weapon(
+        &FormulaDatabase::default(),
         hash,                         //harm turned himslf into a u32! Funniest shit I've ever seen
         31,          //bow
         696969,      //test bow
@@ -308,3 +344,1155 @@ fn test_bow_firing_data() {
         );
     });
 }
+
+#[test]
+fn test_calculator_instances() {
+    let mut pulse_calc = Calculator::new();
+    let mut bow_calc = Calculator::new();
+    pulse_calc.set_weapon(0, 13, 69420, 1, 3373582085).unwrap();
+    bow_calc.set_weapon(0, 31, 696969, 2, 3949783978).unwrap();
+    assert!(pulse_calc.set_weapon(0, 0, 0, 1, 3373582085).is_err());
+    pulse_calc.set_weapon(0, 13, 69420, 1, 3373582085).unwrap();
+    assert_eq!(pulse_calc.weapon.weapon_type, WeaponType::PULSERIFLE);
+    assert_eq!(bow_calc.weapon.weapon_type, WeaponType::BOW);
+    let mut stats = HashMap::new();
+    stats.insert(StatHashes::HANDLING.into(), 50);
+    pulse_calc.set_stats(stats);
+    let response = pulse_calc.get_weapon_handling(false, true);
+    assert!(
+        cmp_floats(response.ads_time, 5.0),
+        "ads time: {}",
+        response.ads_time
+    );
+    assert!(!bow_calc
+        .get_stats()
+        .contains_key(&(StatHashes::HANDLING.into())));
+}
+
+#[test]
+fn test_perks_on_other_thread() {
+    let handle = std::thread::spawn(|| {
+        let mut calc = Calculator::new();
+        calc.set_weapon(0, 13, 69420, 1, 3373582085).unwrap();
+        let stats = get_stat_bumps(
+            vec![perk(Perks::ThreatDetector, 2)],
+            calc.weapon.static_calc_input(),
+            false,
+            &mut SimulationState::default(),
+        );
+        stats[0].get(&(StatHashes::HANDLING.into())).copied()
+    });
+    assert_eq!(handle.join().unwrap(), Some(100));
+}
+
+#[test]
+fn test_build_spec_round_trip() {
+    setup_pulse();
+    let mut calc = Calculator::new();
+    calc.weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    calc.add_perk(Perk {
+        stat_buffs: HashMap::from([(StatHashes::RANGE.into(), 10)]),
+        ..perk(Perks::ThreatDetector, 1)
+    });
+    calc.activity.rpl = 1750;
+    let build = calc.export_build();
+    assert_eq!(build.weapon.intrinsic_hash, 69420);
+    assert_eq!(build.weapon.damage_type, 3373582085);
+    assert_eq!(build.weapon.perks.len(), 1);
+
+    let from_json = BuildSpec::from_json(&build.to_json().unwrap()).unwrap();
+    let from_ron = BuildSpec::from_ron(&build.to_ron().unwrap()).unwrap();
+    assert_eq!(from_json, build);
+    assert_eq!(from_ron, build);
+
+    let mut loaded = Calculator::new();
+    loaded.load_build(&from_ron).unwrap();
+    assert_eq!(loaded.export_build(), build);
+    assert_eq!(loaded.activity.rpl, 1750);
+    let perk = &loaded.weapon.perks[&(Perks::ThreatDetector as u32)];
+    assert_eq!(perk.value, 1);
+    assert_eq!(
+        loaded.weapon.get_stats()[&StatHashes::RANGE.into()].val(),
+        calc.weapon.get_stats()[&StatHashes::RANGE.into()].val()
+    );
+}
+
+#[test]
+fn test_build_spec_version() {
+    let json = r#"{"version": 99, "weapon": {"weapon_type": 13, "intrinsic_hash": 69420}}"#;
+    let build = BuildSpec::from_json(json).unwrap();
+    assert!(build.to_weapon(&FormulaDatabase::default()).is_err());
+}
+
+#[test]
+fn test_optimizer_ranks_range_rolls() {
+    setup_pulse();
+    let mut calc = Calculator::new();
+    calc.weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    let range: u32 = StatHashes::RANGE.into();
+    let reload: u32 = StatHashes::RELOAD.into();
+    let barrel = |hash: u32, stat: u32| PerkSpec {
+        hash,
+        value: 0,
+        enhanced: false,
+        stat_buffs: BTreeMap::from([(stat, 10)]),
+    };
+    let input = OptimizerInput {
+        columns: vec![vec![barrel(1, reload), barrel(2, range)]],
+        masterworks: vec![BTreeMap::from([(reload, 10)]), BTreeMap::from([(range, 10)])],
+        objective: Objective::AdsFalloff { pvp: false },
+        top_n: 3,
+    };
+    let rolls = calc.optimize_perks(&input);
+    assert_eq!(rolls.len(), 3);
+    assert_eq!(rolls[0].perks[0].hash, 2);
+    assert_eq!(rolls[0].masterwork, BTreeMap::from([(range, 10)]));
+    assert!(cmp_floats(rolls[0].score, 1.0));
+    assert!(rolls[0].values[0] > rolls[2].values[0]);
+}
+
+#[test]
+fn test_ttk_damage_resist() {
+    setup_pulse();
+    let mut calc = Calculator::new();
+    calc.weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    let base = calc.get_weapon_pvp_ttk(&PvpTarget::default());
+    let woven_target = PvpTarget {
+        woven_mail: true,
+        ..Default::default()
+    };
+    let woven = calc.get_weapon_pvp_ttk(&woven_target);
+    let (base_body, woven_body) = (base.mixes.last().unwrap(), woven.mixes.last().unwrap());
+    assert!(woven_body.bodyshots > base_body.bodyshots);
+    assert!(woven_body.time_taken > base_body.time_taken);
+    //precision damage goes through woven mail
+    assert_eq!(woven.mixes[0].headshots, base.mixes[0].headshots);
+
+    let resist = get_damage_resist_modifier(
+        woven_target.resist_perks(),
+        &calc.weapon.static_calc_input(),
+        true,
+        &mut SimulationState::default(),
+    );
+    assert!(cmp_floats(resist.body_shot_resist, 0.55));
+    assert!(cmp_floats(resist.head_shot_resist, 1.0));
+
+    let frost = calc.get_weapon_pvp_ttk(&PvpTarget {
+        frost_armor: 5,
+        ..Default::default()
+    });
+    assert!(frost.mixes[0].time_taken >= base.mixes[0].time_taken);
+
+    //blast resists are skipped for weapons that don't deal blast damage
+    let dampened = PvpTarget {
+        concussive_dampener: true,
+        ..Default::default()
+    };
+    let pulse_resist = get_damage_resist_modifier(
+        dampened.resist_perks(),
+        &calc.weapon.static_calc_input(),
+        true,
+        &mut SimulationState::default(),
+    );
+    assert!(cmp_floats(pulse_resist.body_shot_resist, 1.0));
+    calc.set_weapon(0, 10, 216781713, 3, 3373582085).unwrap();
+    let rocket_resist = get_damage_resist_modifier(
+        dampened.resist_perks(),
+        &calc.weapon.static_calc_input(),
+        true,
+        &mut SimulationState::default(),
+    );
+    assert!(cmp_floats(rocket_resist.body_shot_resist, 0.9));
+}
+
+#[test]
+fn test_sword_swings() {
+    let mut calc = Calculator::new();
+    calc.set_weapon(0, 18, 4242, 3, 3373582085).unwrap();
+    calc.set_stats(HashMap::from([
+        (StatHashes::AMMO_CAPACITY.into(), 40),
+        (StatHashes::SWING_SPEED.into(), 50),
+        (StatHashes::GUARD_ENDURANCE.into(), 50),
+    ]));
+    let profiles = calc.get_swing_profiles();
+    assert_eq!(profiles.len(), 3);
+    let heavy = profiles
+        .iter()
+        .find(|profile| profile.swing_type == SwingType::Heavy)
+        .unwrap();
+    assert!(cmp_floats(heavy.damage, 200.0));
+    assert!(cmp_floats(heavy.swing_time, 1.0));
+    assert!(calc.get_misc_data(false, false).contains_key("shield_duration"));
+
+    let ammo = calc.get_weapon_ammo(false, false).mag_size;
+    let dps = calc.get_weapon_dps(false);
+    assert_eq!(dps.total_shots, ammo);
+    assert!(dps.total_damage > 0.0);
+
+    calc.add_perk(perk(Perks::RelentlessStrikes, 1));
+    assert!(calc.get_weapon_dps(false).total_shots > ammo);
+    calc.remove_perk(Perks::RelentlessStrikes as u32);
+
+    //a partly spent sword only swings what it has left
+    let start = StartingMagState {
+        mag: Some(3),
+        ..Default::default()
+    };
+    let partial = calc.get_weapon_dps_for_duration(60.0, &start, false);
+    assert_eq!(partial.total_shots, 3);
+    assert_eq!(partial.mag_left, 0);
+    let short = calc.get_weapon_dps_for_duration(2.5, &start, false);
+    assert_eq!(short.total_shots, 3);
+    let shorter = calc.get_weapon_dps_for_duration(1.5, &start, false);
+    assert_eq!((shorter.total_shots, shorter.mag_left), (2, 1));
+
+    //missed swings deal no damage
+    let settings = MonteCarloSettings {
+        trials: 20,
+        seed: 7,
+    };
+    let half = AccuracyModel {
+        hit_rate: 0.5,
+        ..Default::default()
+    };
+    let rolled = calc.get_weapon_accuracy_dps(&half, &settings, false);
+    assert!(rolled.dps.mean < rolled.perfect_dps);
+}
+
+#[test]
+fn test_melee_dps_and_ttk() {
+    let mut calc = Calculator::new();
+    calc.set_weapon(0, 33, 1316753551, 2, 3373582085).unwrap();
+    let swing_time = calc.weapon.calc_swing_profile(SwingType::Light).swing_time;
+    let melee_dps = calc.get_weapon_melee_dps(SwingType::Light, 10.0, false);
+    assert!(melee_dps.total_shots > 1);
+    assert!(melee_dps.total_time <= 10.0 + swing_time);
+    assert!(melee_dps.total_damage > 0.0);
+
+    let pve_melee = calc.get_weapon_pve_ttk().melee.unwrap();
+    assert!(pve_melee.killed);
+    assert_eq!(pve_melee.ammo_used, 0);
+    let pvp = calc.get_weapon_pvp_ttk(&PvpTarget::default());
+    assert!(!pvp.mixes.is_empty());
+    assert!(pvp.melee.unwrap().killed);
+
+    //swords only kill guardians with swings
+    calc.set_weapon(0, 18, 4242, 3, 3373582085).unwrap();
+    calc.set_stats(HashMap::from([(StatHashes::AMMO_CAPACITY.into(), 40)]));
+    let pvp = calc.get_weapon_pvp_ttk(&PvpTarget::default());
+    assert!(pvp.mixes.is_empty());
+    let heavy = pvp.melee.unwrap();
+    let ttk = calc.get_weapon_ttk(0.0);
+    assert_eq!(ttk[0].body_ttk.bodyshots, heavy.bodyshots);
+    assert!(cmp_floats(ttk[0].optimal_ttk.time_taken, heavy.time_taken));
+    assert!(ttk.last().unwrap().body_ttk.bodyshots >= heavy.bodyshots);
+}
+
+#[test]
+fn test_ability_damage_buffs() {
+    let mut calc = Calculator::new();
+    assert!(calc.set_ability(9999).is_err());
+    calc.set_ability(400).unwrap();
+    assert!(cmp_floats(calc.get_ability_damage(true).total, 40000.0));
+
+    for buff in [Perks::WellOfRadiance, Perks::Radiant, Perks::Weaken] {
+        calc.add_perk(perk(buff, 1));
+    }
+    //empowering buffs do not stack, debuffs stack with them
+    let damage = calc.get_ability_damage(true);
+    assert!(cmp_floats(damage.damage_mult, 1.25 * 1.075));
+    assert!(cmp_floats(damage.total, 40000.0 * 1.25 * 1.075));
+
+    let pve_damage = calc.get_ability_damage(false);
+    let activity_mult = calc.activity.get_pl_delta() * calc.activity.get_rpl_mult();
+    assert!(cmp_floats(pve_damage.damage_mult, 1.25 * 1.15 * activity_mult));
+    let dps = calc.get_ability_dps(false);
+    assert!(cmp_floats(dps.dps, pve_damage.total / 2.5));
+
+    //weapon specific buffs never reach abilities, surges only on the subclass element
+    let mut calc = Calculator::new();
+    calc.set_ability(400).unwrap();
+    for buff in [Perks::Gyrfalcon, Perks::MantleOfBattleHarmony] {
+        calc.add_perk(perk(buff, 1));
+    }
+    let mut player = calc.activity.player.clone();
+    player.state.subclass = DamageType::ARC;
+    calc.set_player(player.clone());
+    assert!(cmp_floats(calc.get_ability_damage(true).damage_mult, 1.06));
+    player.state.subclass = DamageType::SOLAR;
+    calc.set_player(player);
+    assert!(cmp_floats(calc.get_ability_damage(true).damage_mult, 1.0));
+}
+
+#[test]
+fn test_loadout_rotation() {
+    setup_pulse();
+    let pulse = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    setup_bow();
+    let bow = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    assert!(Loadout::new(Vec::new()).is_err());
+
+    let calc = Calculator::new();
+    let loadout = Loadout::new(vec![pulse.clone(), bow.clone()]).unwrap();
+    let greedy = calc.simulate_rotation(&loadout, &FiringPlan::Greedy, 30.0);
+    let contributions: f64 = greedy.weapons.iter().map(|w| w.total_damage).sum();
+    assert!(greedy.total_damage > 0.0);
+    assert!(cmp_floats(greedy.total_damage, contributions));
+    assert!(greedy.total_time <= 30.0);
+    assert!(greedy.timeline.windows(2).all(|hits| hits[0].time <= hits[1].time));
+
+    let plan = FiringPlan::Scripted(vec![0, 1, 0]);
+    let scripted = calc.simulate_rotation(&loadout, &plan, 120.0);
+    assert!(scripted.swaps >= 2);
+    let mut order: Vec<usize> = scripted.timeline.iter().map(|hit| hit.weapon).collect();
+    order.dedup();
+    assert_eq!(order[..3], [0, 1, 0]);
+
+    //the pulse is stowed for a whole bow magazine, long enough to reload itself
+    let mut auto_pulse = pulse;
+    auto_pulse.add_perk(perk(Perks::AutoLoadingHolster, 0));
+    let auto_loadout = Loadout::new(vec![auto_pulse, bow]).unwrap();
+    let auto_scripted = calc.simulate_rotation(&auto_loadout, &plan, 120.0);
+    let second_pulse_mag = |response: &RotationResponse| {
+        let bow_start = response.timeline.iter().position(|hit| hit.weapon == 1).unwrap();
+        response.timeline[bow_start..]
+            .iter()
+            .find(|hit| hit.weapon == 0)
+            .unwrap()
+            .time
+    };
+    assert!(second_pulse_mag(&auto_scripted) < second_pulse_mag(&scripted));
+}
+
+#[test]
+fn test_dps_for_duration() {
+    setup_pulse();
+    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    let enemy = Enemy::default();
+    let full = weapon.calc_dps(enemy.clone(), 1.0);
+    let timed = weapon.calc_dps_for_duration(enemy.clone(), 1.0, 10.0, &StartingMagState::default());
+    let first_ten: f64 = full
+        .time_damage_data
+        .iter()
+        .filter(|(time, _)| *time <= 10.0)
+        .map(|(_, damage)| damage)
+        .sum();
+    assert!(cmp_floats(timed.total_damage, first_ten));
+    assert!(cmp_floats(timed.dps, timed.total_damage / 10.0));
+    assert!(timed.time_damage_data.iter().all(|(time, _)| *time <= 10.0));
+    assert!(timed.mag_left + timed.reserves_left > 0);
+
+    //a double sized mag skips the first reload
+    let base_mag = weapon.calc_ammo_sizes(None, None, false).mag_size;
+    let preloaded = StartingMagState {
+        mag: Some(base_mag * 2),
+        reserves: Some(100),
+        ..Default::default()
+    };
+    let timed_preloaded = weapon.calc_dps_for_duration(enemy, 1.0, 10.0, &preloaded);
+    assert!(timed_preloaded.total_damage > timed.total_damage);
+    assert!(timed_preloaded.mag_left + timed_preloaded.reserves_left <= base_mag * 2 + 100);
+}
+
+#[test]
+fn test_formula_database_runtime_load() {
+    let json = include_str!("../build_resources/weapon_formulas.json");
+    let compiled = FormulaDatabase::default();
+    let loaded = FormulaDatabase::from_json(json).unwrap();
+    assert_eq!(loaded, compiled);
+    let bytes = loaded.to_bytes().unwrap();
+    assert_eq!(FormulaDatabase::from_bytes(&bytes).unwrap(), compiled);
+    assert!(FormulaDatabase::from_bytes(&bytes[..bytes.len() / 2]).is_err());
+
+    //a balance patch to the test pulse applies to the current weapon on swap
+    let mut patched: serde_json::Value = serde_json::from_str(json).unwrap();
+    let sub_fam = patched["Pulse Rifle"]["69420"]["subFam"]
+        .as_str()
+        .unwrap()
+        .to_owned();
+    let old_damage = patched["Pulse Rifle"]["subFam"][&sub_fam]["damage"]
+        .as_f64()
+        .unwrap();
+    patched["Pulse Rifle"]["subFam"][&sub_fam]["damage"] = (old_damage * 2.0).into();
+    let patched = FormulaDatabase::from_json(&patched.to_string()).unwrap();
+
+    let mut calc = Calculator::new();
+    calc.set_weapon(1, 13, 69420, 1, 3373582085).unwrap();
+    let stats_before = calc.get_stats().len();
+    calc.set_formula_database(patched);
+    assert!(cmp_floats(
+        calc.weapon.firing_data.damage,
+        old_damage * 2.0
+    ));
+    assert_eq!(calc.get_stats().len(), stats_before);
+
+    let bad = json.replacen("\"INDEX\"", "\"NOT_INDEX\"", 1);
+    assert!(FormulaDatabase::from_json(&bad).is_err());
+}
+
+#[test]
+fn test_formula_validator() {
+    let report = validate_formulas(include_str!("../build_resources/weapon_formulas.json"));
+    assert!(!report.has_errors(), "{:?}", report);
+
+    let broken = r#"{
+        "INDEX": {"13": "Pulse Rifle", "6": "Auto Rifle"},
+        "Pulse Rifle": {
+            "cat": {"main": {
+                "range": {"vpp_start": 0.1, "offset_start": 10, "vpp_end": 0.1, "offset_end": 20, "floor_percent": 0.5},
+                "reload": {"vpp": 0.02, "offset": 2.5},
+                "handling": {"ready": {"vpp": -0.003, "offset": 0.5}, "stow": {"vpp": -0.003, "offset": 0.5}, "ads": {"vpp": -0.002, "offset": 0.4}},
+                "combatant_scalars": {"minor": 1.2}
+            }},
+            "subFam": {"fast": {"damage": 12, "crit_mult": -80, "burst_delay": -2, "burst_size": 3}},
+            "magProf": {"small": {"mag": {"vpp": 0.2, "offset": 20}}},
+            "1": {"cat": "main", "subFam": "fast", "magProf": "small"},
+            "2": {"cat": "missing", "subFam": "fast", "magProf": "small"},
+            "2": {"cat": "main", "subFam": "fast"}
+        }
+    }"#;
+    let report = validate_formulas(broken);
+    assert!(report.has_errors());
+    let find = |kind: IssueKind, path: &str| {
+        report
+            .issues
+            .iter()
+            .find(|issue| issue.kind == kind && issue.path == path)
+            .map(|issue| issue.severity)
+    };
+    assert_eq!(find(IssueKind::MissingSection, "$['Auto Rifle']"), Some(Severity::Error));
+    assert_eq!(find(IssueKind::DuplicateKey, "$['Pulse Rifle']['2']"), Some(Severity::Error));
+    assert_eq!(
+        find(IssueKind::MissingMagProfile, "$['Pulse Rifle']['2'].magProf"),
+        Some(Severity::Error)
+    );
+    assert_eq!(
+        find(IssueKind::NonMonotonic, "$['Pulse Rifle'].cat.main.reload"),
+        Some(Severity::Warning)
+    );
+    assert_eq!(
+        find(IssueKind::ImpossibleValue, "$['Pulse Rifle'].subFam.fast.burst_delay"),
+        Some(Severity::Error)
+    );
+    assert_eq!(
+        find(IssueKind::ImpossibleValue, "$['Pulse Rifle'].subFam.fast.crit_mult"),
+        Some(Severity::Error)
+    );
+    assert_eq!(
+        find(IssueKind::MissingField, "$['Pulse Rifle'].subFam.fast.inner_burst_delay"),
+        None
+    );
+    assert_eq!(validate_formulas("{").issues[0].kind, IssueKind::InvalidJson);
+}
+
+#[test]
+fn test_patch_history_diff() {
+    let json = include_str!("../build_resources/weapon_formulas.json");
+    let mut old_patch: serde_json::Value = serde_json::from_str(json).unwrap();
+    let sub_fam = old_patch["Pulse Rifle"]["69420"]["subFam"]
+        .as_str()
+        .unwrap()
+        .to_owned();
+    let new_damage = old_patch["Pulse Rifle"]["subFam"][&sub_fam]["damage"]
+        .as_f64()
+        .unwrap();
+    old_patch["Pulse Rifle"]["subFam"][&sub_fam]["damage"] = (new_damage - 2.0).into();
+    let mut calc = Calculator::new();
+    calc.add_formula_patch(
+        "season_20",
+        FormulaDatabase::from_json(&old_patch.to_string()).unwrap(),
+    );
+    calc.set_weapon(1, 13, 69420, 1, 3373582085).unwrap();
+
+    let diff = calc
+        .diff_formula_patches("season_20", CURRENT_PATCH, false)
+        .unwrap();
+    assert!(diff.changed(PatchDiffCategory::Firing));
+    assert!(!diff.changed(PatchDiffCategory::Range));
+    assert!(!diff.changed(PatchDiffCategory::Reload));
+    let impact = diff
+        .changes
+        .iter()
+        .find(|change| change.field == "pve_impact_damage")
+        .unwrap();
+    assert!(impact.new > impact.old);
+    assert!(calc
+        .diff_formula_patches(CURRENT_PATCH, CURRENT_PATCH, false)
+        .unwrap()
+        .changes
+        .is_empty());
+    assert!(calc
+        .diff_formula_patches("season_1", CURRENT_PATCH, false)
+        .is_err());
+
+    calc.use_formula_patch("season_20").unwrap();
+    assert!(cmp_floats(calc.weapon.firing_data.damage, new_damage - 2.0));
+    calc.use_formula_patch(CURRENT_PATCH).unwrap();
+    assert!(cmp_floats(calc.weapon.firing_data.damage, new_damage));
+}
+
+//made up archetypes, only here to drive the sims
+const TEST_ENEMIES: &str = r#"[
+    {"id": 1, "source": "test fixture", "name": "Test Minor", "health": 140.0, "type_": "MINOR"},
+    {"id": 2, "source": "test fixture", "name": "Test Barrier", "health": 3800.0,
+        "type_": "CHAMPION", "champion": {"champion_type": "BARRIER", "unstunned_resistance": 0.3,
+        "barrier_health": 1400.0, "stun_vulnerability": 0.25, "vulnerable_duration": 5.0}},
+    {"id": 3, "source": "test fixture", "name": "Test Overload", "health": 3500.0,
+        "type_": "CHAMPION", "champion": {"champion_type": "OVERLOAD", "unstunned_resistance": 0.3,
+        "regen_per_second": 0.03, "regen_delay": 3.0, "stun_duration": 6.0}}
+]"#;
+
+#[test]
+fn test_enemy_catalogue() {
+    let mut calc = Calculator::new();
+    assert!(calc.set_enemy_archetype(1).is_err());
+    calc.set_enemy_catalogue(EnemyCatalogue::from_json(TEST_ENEMIES).unwrap());
+    assert!(calc.set_enemy_archetype(9999).is_err());
+    calc.set_enemy_archetype(1).unwrap();
+    assert_eq!(calc.enemy.name, "Test Minor");
+    assert_eq!(calc.enemy.type_, EnemyType::MINOR);
+    assert_eq!(calc.enemy_catalogue.list_enemies().len(), 3);
+
+    //values without a source or duplicate ids are rejected
+    let unsourced = r#"[{"id": 1, "source": "", "name": "Guess", "health": 100.0}]"#;
+    assert!(EnemyCatalogue::from_json(unsourced).is_err());
+    let duplicate = r#"[
+        {"id": 1, "source": "a", "health": 1.0},
+        {"id": 1, "source": "b", "health": 2.0}
+    ]"#;
+    assert!(EnemyCatalogue::from_json(duplicate).is_err());
+}
+
+#[test]
+fn test_pve_ttk() {
+    let mut calc = Calculator::new();
+    calc.set_weapon(1, 13, 69420, 1, 3373582085).unwrap();
+    calc.set_enemy_catalogue(EnemyCatalogue::from_json(TEST_ENEMIES).unwrap());
+    calc.set_enemy_archetype(1).unwrap();
+
+    //being under power makes the enemy tankier, more so on master
+    let at_power = calc.enemy.get_adjusted_health(&calc.activity);
+    calc.activity.player.pl = calc.activity.rpl - 30;
+    let under_power = calc.enemy.get_adjusted_health(&calc.activity);
+    calc.activity.difficulty = DifficultyOptions::MASTER;
+    assert!(under_power > at_power);
+    assert!(calc.enemy.get_adjusted_health(&calc.activity) > under_power);
+    calc.activity = Activity::default();
+
+    let response = calc.get_weapon_pve_ttk();
+    assert_eq!(response.kills.len(), 5);
+    assert!(response.kills.iter().all(|kill| kill.killed));
+    let all_precision = &response.kills[0];
+    let all_body = &response.kills[4];
+    assert_eq!(all_precision.body_shots, 0);
+    assert_eq!(all_body.precision_shots, 0);
+    assert!(all_precision.precision_shots <= all_body.body_shots);
+    assert!(all_precision.time_taken <= all_body.time_taken);
+
+    //a resist phase halfway down takes more shots to push through
+    calc.enemy.resist_phases = vec![ResistPhase {
+        health_fraction: 0.5,
+        damage_resistance: 0.5,
+    }];
+    let phased = calc.get_weapon_pve_ttk();
+    let phased_body = &phased.kills[4];
+    assert!(phased_body.body_shots > all_body.body_shots);
+    let phase_start = phased_body.phase_times[0].unwrap();
+    assert!(phase_start > 0.0 && phase_start <= phased_body.time_taken);
+}
+
+#[test]
+fn test_champion_mechanics() {
+    let mut calc = Calculator::new();
+    calc.set_weapon(1, 13, 69420, 1, 3373582085).unwrap();
+    calc.activity.difficulty = DifficultyOptions::MASTER;
+    calc.set_enemy_catalogue(EnemyCatalogue::from_json(TEST_ENEMIES).unwrap());
+
+    //a barrier can't be broken without anti barrier
+    calc.set_enemy_archetype(2).unwrap();
+    let no_mod = calc.get_weapon_pve_ttk();
+    assert!(no_mod
+        .kills
+        .iter()
+        .all(|kill| !kill.killed && kill.stuns == 0));
+    calc.add_perk(perk(Perks::AntiBarrierMod, 1));
+    assert!(calc.weapon.champion_mods().anti_barrier);
+    let anti_barrier = calc.get_weapon_pve_ttk();
+    assert!(anti_barrier
+        .kills
+        .iter()
+        .all(|kill| kill.killed && kill.stuns == 1));
+
+    //stunning an overload removes its resistance and stops regeneration
+    calc.set_enemy_archetype(3).unwrap();
+    calc.enemy.health = 500.0;
+    let unstunned_dps = calc.get_weapon_dps(false);
+    let unstunned = calc.get_weapon_pve_ttk();
+    calc.add_perk(perk(Perks::OverloadMod, 1));
+    assert!(calc.get_weapon_dps(false).total_damage > unstunned_dps.total_damage);
+    let stunned = calc.get_weapon_pve_ttk();
+    assert!(unstunned.kills[0].killed && stunned.kills[0].killed);
+    assert!(stunned.kills[0].stuns > 0);
+    assert!(stunned.kills[0].time_taken < unstunned.kills[0].time_taken);
+}
+
+#[test]
+fn test_difficulty_options() {
+    let mut activity = Activity {
+        difficulty: DifficultyOptions::NORMAL,
+        ..Default::default()
+    };
+    let normal = activity.get_pl_delta();
+    activity.difficulty = DifficultyOptions::MASTER;
+    let master = activity.get_pl_delta();
+    assert!(master < normal);
+
+    let deltas = [
+        0.0, -10.0, -20.0, -30.0, -40.0, -50.0, -60.0, -70.0, -80.0, -90.0, -99.0,
+    ];
+    let values = [
+        1.0, 0.78, 0.66, 0.5914, 0.5405, 0.5, 0.475, 0.46, 0.44, 0.42, 0.418,
+    ];
+    activity.difficulty = DifficultyOptions::custom(&deltas, &values, 50).unwrap();
+    assert!(cmp_floats(activity.get_pl_delta(), normal));
+    activity.difficulty = DifficultyOptions::custom(&deltas, &values, -20).unwrap();
+    let capped = activity.get_pl_delta();
+    assert!(cmp_floats(capped, 1.006736_f64.powi(-20) * 0.66));
+    assert!(DifficultyOptions::custom(&deltas, &values[..5], 0).is_err());
+    assert!(DifficultyOptions::custom(&[], &[], 0).is_err());
+}
+
+#[test]
+fn test_pl_delta_breakpoints() {
+    //hand built tables so the expected values can be worked out on paper, the keys are
+    //given out of order to check they get sorted before interpolating
+    let table = vec![(0.0, 1.0), (20.0, 1.4), (-40.0, 0.2), (-20.0, 0.6)];
+    let pl_delta = |difficulty: DifficultyOptions, delta: i32| {
+        Activity {
+            difficulty,
+            player: Player {
+                pl: (1600 + delta) as u32,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .get_pl_delta()
+    };
+    //player power delta, table value, delta the weapon scaling is applied at
+    let breakpoints = [
+        //on a key
+        (0, 1.0, 0),
+        (-20, 0.6, -20),
+        //halfway and a quarter of the way between keys
+        (-10, 0.8, -10),
+        (10, 1.2, 10),
+        (-30, 0.4, -30),
+        (-35, 0.3, -35),
+        //past either end holds the end value
+        (-60, 0.2, -60),
+        (35, 1.4, 35),
+    ];
+    for (delta, value, weapon_delta) in breakpoints {
+        let expected = 1.006736_f64.powi(weapon_delta) * value;
+        let actual = pl_delta(DifficultyOptions::Custom(table.clone(), 50), delta);
+        assert!(
+            cmp_floats(actual, expected),
+            "{}: {} != {}",
+            delta,
+            actual,
+            expected
+        );
+    }
+    //the cap is applied before looking up the table
+    let capped = pl_delta(DifficultyOptions::Custom(table.clone(), 5), 35);
+    assert!(cmp_floats(capped, 1.006736_f64.powi(5) * 1.1));
+    let capped = pl_delta(DifficultyOptions::Custom(table.clone(), -25), 0);
+    assert!(cmp_floats(capped, 1.006736_f64.powi(-25) * 0.5));
+    //a single key is flat
+    let flat = pl_delta(DifficultyOptions::Custom(vec![(0.0, 0.5)], 50), -15);
+    assert!(cmp_floats(flat, 1.006736_f64.powi(-15) * 0.5));
+    //too far under power does no damage whatever the table says
+    let dead = pl_delta(DifficultyOptions::Custom(table.clone(), 50), -100);
+    assert_eq!(dead, 0.0);
+
+    let activity = Activity {
+        difficulty: DifficultyOptions::Custom(table, 10),
+        ..Default::default()
+    };
+    let curve = activity.get_pl_delta_curve();
+    assert_eq!(curve.len(), 110);
+    assert_eq!(curve.first().unwrap().0, -99);
+    assert_eq!(curve.last().unwrap().0, 10);
+    assert!(curve.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    let (delta, value) = curve[(-10 + 99) as usize];
+    assert_eq!(delta, -10);
+    assert!(cmp_floats(value, 1.006736_f64.powi(-10) * 0.8));
+}
+
+#[test]
+fn test_player_state() {
+    let mut calc = Calculator::new();
+    calc.set_weapon(1, 13, 69420, 1, 151347233).unwrap();
+    let base_dps = calc.get_weapon_dps(false).total_damage;
+    let base_ttk = calc.get_weapon_ttk(0.0);
+
+    //buffs on the player apply without being added to the weapon
+    let mut player = calc.activity.player.clone();
+    player.state.buffs = vec![PlayerBuff::Radiant];
+    calc.set_player(player.clone());
+    //buffs are reset before every shot so the whole sim is buffed, not only the first shot
+    let radiant_dps = calc.get_weapon_dps(false).total_damage;
+    assert!(cmp_floats_delta(radiant_dps / base_dps, 1.25, 1e-6));
+    let radiant_ttk = calc.get_weapon_ttk(0.0);
+    assert!(radiant_ttk
+        .iter()
+        .zip(base_ttk.iter())
+        .all(|(buffed, base)| buffed.optimal_ttk.time_taken <= base.optimal_ttk.time_taken));
+    //the same buff added as a perk doesn't stack with the players
+    calc.add_perk(perk(Perks::Radiant, 1));
+    let stacked_dps = calc.get_weapon_dps(false).total_damage;
+    assert!(cmp_floats(stacked_dps, radiant_dps));
+    calc.remove_perk(Perks::Radiant as u32);
+
+    //a new weapon keeps the players build
+    calc.set_weapon(1, 13, 69420, 1, 151347233).unwrap();
+    assert_eq!(calc.weapon.player, player.state);
+
+    //wrathweavers needs the wearer on stasis
+    player.state.buffs.clear();
+    player.state.subclass = DamageType::SOLAR;
+    calc.set_player(player.clone());
+    calc.add_perk(perk(Perks::BallindorseWrathweavers, 1));
+    let solar_dps = calc.get_weapon_dps(false).total_damage;
+    assert!(cmp_floats(solar_dps, base_dps));
+    player.state.subclass = DamageType::STASIS;
+    calc.set_player(player.clone());
+    let wrath_dps = calc.get_weapon_dps(false).total_damage;
+    assert!(cmp_floats_delta(wrath_dps / base_dps, 1.15, 1e-6));
+
+    //mirror matchups use the players own armor stats
+    calc.remove_perk(Perks::BallindorseWrathweavers as u32);
+    player.state.resilience = 100;
+    player.state.recovery = 45;
+    calc.set_player(player);
+    let target = PvpTarget::from_player(&calc.activity.player.state);
+    assert_eq!((target.resilience, target.recovery), (10, 4));
+    let mirror = calc.get_weapon_mirror_ttk();
+    assert!(cmp_floats(mirror.health, target.health()));
+    let low_res = calc.get_weapon_pvp_ttk(&PvpTarget::default());
+    assert!(mirror.mixes.last().unwrap().time_taken >= low_res.mixes.last().unwrap().time_taken);
+
+    //subclass perks need their fragment equipped once the player lists fragments
+    let mut calc = Calculator::new();
+    calc.set_weapon(1, 13, 69420, 1, 3373582085).unwrap();
+    let base_reload = calc.get_weapon_reload(true, false).reload_time;
+    calc.add_perk(perk(Perks::Frequency, 1));
+    let frequency_reload = calc.get_weapon_reload(true, false).reload_time;
+    assert!(frequency_reload < base_reload);
+    let mut player = calc.activity.player.clone();
+    player.state.fragments = vec![Perks::Tempering as u32];
+    calc.set_player(player.clone());
+    let unequipped = calc.get_weapon_reload(true, false).reload_time;
+    assert!(cmp_floats(unequipped, base_reload));
+    player.state.fragments.push(Perks::Frequency as u32);
+    calc.set_player(player);
+    let equipped = calc.get_weapon_reload(true, false).reload_time;
+    assert!(cmp_floats(equipped, frequency_reload));
+}
+
+#[test]
+fn test_pvp_target() {
+    let mut calc = Calculator::new();
+    calc.set_weapon(1, 13, 69420, 1, 3373582085).unwrap();
+    let base = calc.get_weapon_pvp_ttk(&PvpTarget::default());
+    let old = &calc.get_weapon_ttk(0.0)[0];
+    let all_crit = &base.mixes[0];
+    let all_body = base.mixes.last().unwrap();
+    assert_eq!(all_crit.bodyshots, 0);
+    assert_eq!(all_body.headshots, 0);
+    assert_eq!(all_body.bodyshots, old.body_ttk.bodyshots);
+    assert!(cmp_floats(all_body.time_taken, old.body_ttk.time_taken));
+    for pair in base.mixes.windows(2) {
+        assert!(pair[0].killed && pair[1].killed);
+        assert_eq!(pair[1].bodyshots, pair[0].bodyshots + 1);
+        assert!(pair[0].headshots + pair[0].bodyshots <= pair[1].headshots + pair[1].bodyshots);
+    }
+
+    let shielded = calc.get_weapon_pvp_ttk(&PvpTarget {
+        overshield_type: OvershieldType::PERSISTENT,
+        overshield: 60.0,
+        ..Default::default()
+    });
+    assert!(cmp_floats(shielded.health, base.health + 60.0));
+    assert!(shielded.mixes.last().unwrap().bodyshots > all_body.bodyshots);
+
+    let healing = calc.get_weapon_pvp_ttk(&PvpTarget {
+        healing: PvpHealing::DEVOUR,
+        ..Default::default()
+    });
+    assert!(healing.mixes.last().unwrap().time_taken >= all_body.time_taken);
+
+    //the overshield breaks just before the reload so health is low while reloading
+    let mag = calc.get_weapon_ammo(false, true).mag_size;
+    let per_shot = base.health / all_body.bodyshots as f64;
+    let tank = PvpTarget {
+        overshield_type: OvershieldType::PERSISTENT,
+        overshield: per_shot * (mag - 5) as f64,
+        recovery_delay: 20.0,
+        ..Default::default()
+    };
+    let no_regen = calc.get_weapon_pvp_ttk(&tank);
+    let regen = calc.get_weapon_pvp_ttk(&PvpTarget {
+        recovery_delay: 1.0,
+        ..tank
+    });
+    let no_regen_mix = no_regen.mixes.last().unwrap();
+    let regen_mix = regen.mixes.last().unwrap();
+    assert!(no_regen_mix.killed && regen_mix.killed);
+    assert_eq!(no_regen_mix.reloads, 1);
+    assert!(!no_regen_mix.regen_reset);
+    assert!(regen_mix.regen_reset);
+    assert!(regen_mix.bodyshots > no_regen_mix.bodyshots);
+}
+
+#[test]
+fn test_perk_state_machine() {
+    setup_pulse();
+    let mut weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    weapon.add_perk(perk(Perks::Rampage, 3));
+    weapon.add_perk(perk(Perks::KillClip, 0));
+    weapon.add_perk(perk(Perks::Frenzy, 0));
+    let rampage = Perks::Rampage as u32;
+    let kill_clip = Perks::KillClip as u32;
+    let frenzy = Perks::Frenzy as u32;
+
+    //pre-stacked perks wear off after their duration
+    let mut states = PerkStateMachine::new(&weapon);
+    assert_eq!(states.stacks(rampage), Some(3));
+    states.advance(4.5);
+    assert_eq!(states.stacks(rampage), Some(0));
+
+    //kills stack rampage up to its limit and refresh it
+    for time in [5.0, 6.0, 7.0, 8.0] {
+        states.trigger(PerkTrigger::Kill, time);
+    }
+    assert_eq!(states.stacks(rampage), Some(3));
+    states.advance(11.9);
+    assert_eq!(states.stacks(rampage), Some(3));
+    states.advance(12.1);
+    assert_eq!(states.stacks(rampage), Some(0));
+
+    //kill clip needs a reload after the kill, frenzy turns on after 12 seconds
+    assert_eq!(states.stacks(kill_clip), Some(0));
+    assert_eq!(states.stacks(frenzy), Some(1));
+    let mut states = PerkStateMachine::new(&weapon);
+    states.trigger(PerkTrigger::Reload, 1.0);
+    assert_eq!(states.stacks(kill_clip), Some(0));
+    states.trigger(PerkTrigger::Kill, 2.0);
+    states.trigger(PerkTrigger::Reload, 3.0);
+    assert_eq!(states.stacks(kill_clip), Some(1));
+    states.advance(7.5);
+    assert_eq!(states.stacks(kill_clip), Some(0));
+    assert_eq!(states.stacks(frenzy), Some(0));
+
+    //the dps sim keeps kill triggered perks up when adds die
+    weapon.remove_perk(kill_clip);
+    weapon.remove_perk(frenzy);
+    let enemy = Enemy::default();
+    let no_kills =
+        weapon.calc_dps_for_duration(enemy.clone(), 1.0, 10.0, &StartingMagState::default());
+    let with_kills = weapon.calc_dps_for_duration(
+        enemy,
+        1.0,
+        10.0,
+        &StartingMagState {
+            kill_interval: Some(1.0),
+            ..Default::default()
+        },
+    );
+    assert!(with_kills.total_damage > no_kills.total_damage);
+    let last_hit = no_kills.time_damage_data.last().unwrap().1;
+    assert!(with_kills.time_damage_data.last().unwrap().1 > last_hit);
+
+    //precision hits stack rapid hit, veist stinger is armed on swap
+    weapon.add_perk(perk(Perks::RapidHit, 0));
+    weapon.add_perk(perk(Perks::VeistStinger, 0));
+    let mut states = PerkStateMachine::new(&weapon);
+    states.trigger(PerkTrigger::Swap, 0.0);
+    assert_eq!(states.stacks(Perks::VeistStinger as u32), Some(1));
+    states.trigger(PerkTrigger::PrecisionHit, 0.5);
+    states.trigger(PerkTrigger::PrecisionHit, 1.0);
+    assert_eq!(states.stacks(Perks::RapidHit as u32), Some(2));
+    states.advance(3.5);
+    assert_eq!(states.stacks(Perks::RapidHit as u32), Some(0));
+
+    //swords drop pre-stacked perks once they wear off too
+    let mut calc = Calculator::new();
+    calc.set_weapon(0, 18, 4242, 3, 3373582085).unwrap();
+    calc.set_stats(HashMap::from([(StatHashes::AMMO_CAPACITY.into(), 40)]));
+    calc.add_perk(perk(Perks::Rampage, 3));
+    let swings = calc.get_weapon_dps(false).time_damage_data;
+    let (late_time, late_hit) = *swings.last().unwrap();
+    assert!(late_time > 5.0);
+    assert!(swings[0].1 > late_hit);
+}
+
+#[test]
+fn test_simulation_state() {
+    let mut state = SimulationState::default();
+    assert!(cmp_floats(state.buff(BuffCategory::Empowering), 1.0));
+
+    //only the strongest buff of a category applies, the weaker one adds nothing
+    assert!(cmp_floats(
+        state.stack_buff(BuffCategory::Empowering, 1.1),
+        1.1
+    ));
+    assert!(cmp_floats(
+        state.stack_buff(BuffCategory::Empowering, 1.25),
+        1.25 / 1.1
+    ));
+    assert!(cmp_floats(
+        state.stack_buff(BuffCategory::Empowering, 1.2),
+        1.0
+    ));
+    assert!(cmp_floats(state.buff(BuffCategory::Empowering), 1.25));
+
+    //categories stack with each other
+    assert!(cmp_floats(
+        state.stack_buff(BuffCategory::Surge, 1.22),
+        1.22
+    ));
+    assert!(cmp_floats(state.stack_buff(BuffCategory::Debuff, 1.3), 1.3));
+    state.reset_buffs();
+    for category in [
+        BuffCategory::Empowering,
+        BuffCategory::Surge,
+        BuffCategory::Debuff,
+    ] {
+        assert!(cmp_floats(state.buff(category), 1.0));
+    }
+
+    //cooldown timers are kept per perk and survive a buff reset
+    assert_eq!(state.last_proc(Perks::VeistStinger), None);
+    state.set_proc(Perks::VeistStinger, 2.5);
+    state.reset_buffs();
+    assert_eq!(state.last_proc(Perks::VeistStinger), Some(2.5));
+    assert_eq!(state.last_proc(Perks::Rampage), None);
+
+    //radiant and well share the empowering category so only one applies
+    let mut calc = Calculator::new();
+    calc.set_weapon(1, 13, 69420, 1, 3373582085).unwrap();
+    let base = calc.get_weapon_pve_ttk();
+    let mut player = calc.activity.player.clone();
+    player.state.buffs = vec![PlayerBuff::Radiant];
+    calc.set_player(player.clone());
+    let radiant = calc.get_weapon_pve_ttk();
+    player.state.buffs = vec![PlayerBuff::Radiant, PlayerBuff::WellOfRadiance];
+    calc.set_player(player);
+    let both = calc.get_weapon_pve_ttk();
+    assert!(radiant.kills[0].time_taken <= base.kills[0].time_taken);
+    assert_eq!(
+        both.kills[0].precision_shots,
+        radiant.kills[0].precision_shots
+    );
+}
+
+#[test]
+fn test_add_clear() {
+    setup_pulse();
+    let mut weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    let first_hit = weapon.calc_dps(Enemy::default(), 1.0).time_damage_data[0].1;
+    //three hits to kill, two once rampage is up
+    let enemy = Enemy {
+        health: first_hit * 2.05,
+        ..Default::default()
+    };
+    let wave = AddClearWave {
+        count: 6,
+        retarget_time: 0.0,
+    };
+    let base = weapon.calc_add_clear(enemy.clone(), 1.0, &wave);
+    assert_eq!(base.kill_times.len(), 6);
+    assert_eq!(base.time_to_clear, base.kill_times.last().copied());
+    let third_hit = base.dps.time_damage_data[2].0;
+    assert!(cmp_floats(base.kill_times[0], third_hit));
+    assert!(base.kill_times.windows(2).all(|pair| pair[0] <= pair[1]));
+
+    //retargeting pushes every kill after the first one back
+    let slow_wave = AddClearWave {
+        count: 6,
+        retarget_time: 0.5,
+    };
+    let slow = weapon.calc_add_clear(enemy.clone(), 1.0, &slow_wave);
+    assert!(slow.time_to_clear.unwrap() > base.time_to_clear.unwrap() + 2.0);
+
+    //kills stack rampage which speeds up the rest of the wave
+    weapon.add_perk(perk(Perks::Rampage, 0));
+    let rampage = weapon.calc_add_clear(enemy.clone(), 1.0, &wave);
+    assert!(cmp_floats(rampage.kill_times[0], base.kill_times[0]));
+    assert!(rampage.time_to_clear.unwrap() < base.time_to_clear.unwrap());
+    assert!(rampage.dps.total_shots < base.dps.total_shots);
+
+    //on kill explosions are part of the damage
+    weapon.add_perk(perk(Perks::Dragonfly, 0));
+    let dragonfly = weapon.calc_add_clear(enemy.clone(), 1.0, &wave);
+    assert!(dragonfly.dps.time_damage_data.len() > dragonfly.dps.total_shots as usize);
+    //the last kill has nothing left to explode on
+    let single = AddClearWave {
+        count: 1,
+        retarget_time: 0.0,
+    };
+    let lone = weapon.calc_add_clear(enemy.clone(), 1.0, &single).dps;
+    assert_eq!(lone.time_damage_data.len(), lone.total_shots as usize);
+    assert!(dragonfly.time_to_clear.unwrap() <= rampage.time_to_clear.unwrap());
+
+    //running dry leaves the wave standing
+    let huge_wave = AddClearWave {
+        count: 100000,
+        retarget_time: 0.0,
+    };
+    let dry = weapon.calc_add_clear(enemy, 1.0, &huge_wave);
+    assert!(dry.time_to_clear.is_none());
+    assert!(dry.kill_times.len() < 100000);
+}
+
+#[test]
+fn test_accuracy_model() {
+    setup_pulse();
+    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    let enemy = Enemy::default();
+    let settings = MonteCarloSettings {
+        trials: 20,
+        seed: 7,
+    };
+
+    let mut first = SeededRng::new(7);
+    let mut second = SeededRng::new(7);
+    assert_eq!(first.next_u64(), second.next_u64());
+    let samples: Vec<f64> = (1..=11).map(|i| i as f64).collect();
+    let percentiles = Percentiles::from_samples(&samples);
+    assert!(cmp_floats(percentiles.mean, 6.0));
+    assert!(cmp_floats(percentiles.p10, 2.0));
+    assert!(cmp_floats(percentiles.p90, 10.0));
+
+    //hit chance drops over the falloff range
+    let range = weapon.calc_range_falloff(None, None, false);
+    let far = AccuracyModel {
+        hit_rate: 0.8,
+        distance: range.ads_falloff_end + 1.0,
+        range_penalty: 0.5,
+        ..Default::default()
+    };
+    assert!(cmp_floats(far.hit_chance(&range), 0.4));
+
+    //perfect play matches the normal dps calc
+    let perfect = weapon.calc_accuracy_dps(&enemy, 1.0, &AccuracyModel::default(), &settings);
+    assert!(cmp_floats(perfect.dps.mean, perfect.perfect_dps));
+    assert!(cmp_floats(perfect.dps.p10, perfect.dps.p90));
+
+    let sloppy = AccuracyModel {
+        hit_rate: 0.6,
+        precision_ratio: 0.5,
+        spread: 0.1,
+        ..Default::default()
+    };
+    let rolled = weapon.calc_accuracy_dps(&enemy, 1.0, &sloppy, &settings);
+    assert!(rolled.dps.mean < perfect.perfect_dps);
+    assert!(rolled.dps.p10 <= rolled.dps.p50 && rolled.dps.p50 <= rolled.dps.p90);
+    let repeat = weapon.calc_accuracy_dps(&enemy, 1.0, &sloppy, &settings);
+    assert_eq!(rolled.dps, repeat.dps);
+
+    let target = PvpTarget::default();
+    let perfect_ttk = weapon.calc_accuracy_ttk(&target, &AccuracyModel::default(), &settings);
+    let all_heads = weapon.calc_pvp_ttk(&target).mixes[0].time_taken;
+    assert!(cmp_floats(perfect_ttk.ttk.mean, all_heads));
+    assert!(perfect_ttk.perfect_ttk <= all_heads);
+    let rolled_ttk = weapon.calc_accuracy_ttk(&target, &sloppy, &settings);
+    assert!(rolled_ttk.ttk.mean > perfect_ttk.ttk.mean);
+    assert!(cmp_floats(rolled_ttk.kill_rate, 1.0));
+
+    //precision only refunds need a precision hit
+    let triple_tap = || {
+        vec![RefundResponse {
+            crit: true,
+            requirement: 3,
+            refund_mag: 1,
+            refund_reserves: 0,
+        }]
+    };
+    assert_eq!(calc_refund(3, 2, false, triple_tap()), (0, 0));
+    assert_eq!(calc_refund(4, 3, true, triple_tap()), (1, 0));
+    assert_eq!(calc_refund(3, 2, true, triple_tap()), (0, 0));
+}
+
+#[test]
+fn test_distance_falloff() {
+    setup_pulse();
+    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    let enemy = Enemy::default();
+    let at = |distance: f64, ads: bool| Engagement { distance, ads };
+
+    let range = RangeResponse {
+        hip_falloff_start: 10.0,
+        hip_falloff_end: 20.0,
+        ads_falloff_start: 20.0,
+        ads_falloff_end: 40.0,
+        floor_percent: 0.5,
+        timestamp: 0,
+    };
+    assert!(cmp_floats(at(10.0, true).damage_mult(&range), 1.0));
+    assert!(cmp_floats(at(30.0, true).damage_mult(&range), 0.75));
+    assert!(cmp_floats(at(50.0, true).damage_mult(&range), 0.5));
+    assert!(cmp_floats(at(15.0, false).damage_mult(&range), 0.75));
+    //no falloff at all
+    let flat = RangeResponse {
+        floor_percent: 0.0,
+        ..Default::default()
+    };
+    assert!(cmp_floats(at(50.0, true).damage_mult(&flat), 1.0));
+
+    //point blank is the same as the normal calcs
+    let close = weapon.calc_dps_at_distance(enemy.clone(), 1.0, &at(0.0, true));
+    let normal = weapon.calc_dps(enemy.clone(), 1.0);
+    assert!(cmp_floats(close.total_damage, normal.total_damage));
+    let far = weapon.calc_dps_at_distance(enemy, 1.0, &at(80.0, true));
+    assert!(far.total_damage < close.total_damage);
+    let close_ttk = weapon.calc_ttk_at_distance(0.0, &at(0.0, true));
+    let normal_ttk = weapon.calc_ttk(0.0);
+    assert_eq!(
+        close_ttk[0].optimal_ttk.headshots,
+        normal_ttk[0].optimal_ttk.headshots
+    );
+    let far_ttk = weapon.calc_ttk_at_distance(0.0, &at(80.0, true));
+    assert!(far_ttk[0].body_ttk.bodyshots > close_ttk[0].body_ttk.bodyshots);
+
+    let target = PvpTarget::default();
+    let curve = weapon.calc_ttk_curve(&target, true);
+    assert_eq!(curve.points.len(), 81);
+    let point_blank = weapon.calc_pvp_ttk(&target).mixes[0].clone();
+    assert!(cmp_floats(
+        curve.points[0].optimal_ttk.unwrap(),
+        point_blank.time_taken
+    ));
+    assert!(!curve.breakpoints.is_empty());
+    for pair in curve.points.windows(2) {
+        assert!(pair[1].optimal_shots >= pair[0].optimal_shots || pair[1].optimal_shots == 0);
+        let lost = pair[1].optimal_shots > pair[0].optimal_shots;
+        assert_eq!(lost, curve.breakpoints.contains(&pair[1].distance));
+    }
+}
+
+#[test]
+fn test_charge_delay_matches_ttk() {
+    let mut calc = Calculator::new();
+    calc.set_weapon(0, 11, 656200654, 2, 3373582085).unwrap();
+    let fusion = calc.get_weapon_dps(false);
+    //vex mythoclast skips the charge in the dps sim like it does in the ttk calcs
+    let vex_mythoclast = 4289226715;
+    calc.set_weapon(vex_mythoclast, 11, 656200654, 2, 3373582085)
+        .unwrap();
+    assert!(cmp_floats(calc.weapon.charge_delay_scale(), 1.0));
+    let vex = calc.get_weapon_dps(false);
+    assert_eq!(fusion.total_shots, vex.total_shots);
+    assert!(vex.total_time < fusion.total_time);
+}
@@ -345,14 +345,26 @@ pub enum JsDifficultyOptions {
     NORMAL = 1,
     RAID = 2,
     MASTER = 3,
+    CUSTOM = 4,
 }
-impl Into<DifficultyOptions> for JsDifficultyOptions {
-    fn into(self) -> DifficultyOptions {
-        match self {
+impl JsDifficultyOptions {
+    ///The custom table is only read for CUSTOM, which also takes the override cap as its own
+    pub fn into_difficulty(
+        self,
+        _custom_deltas: Option<Vec<f64>>,
+        _custom_values: Option<Vec<f64>>,
+        _cap: i32,
+    ) -> Result<DifficultyOptions, String> {
+        Ok(match self {
             JsDifficultyOptions::NORMAL => DifficultyOptions::NORMAL,
             JsDifficultyOptions::RAID => DifficultyOptions::RAID,
             JsDifficultyOptions::MASTER => DifficultyOptions::MASTER,
-        }
+            JsDifficultyOptions::CUSTOM => DifficultyOptions::custom(
+                &_custom_deltas.unwrap_or_default(),
+                &_custom_values.unwrap_or_default(),
+                _cap,
+            )?,
+        })
     }
 }
 
@@ -4,10 +4,22 @@ use pyo3::{prelude::*, types::PyDict};
 use std::collections::HashMap;
 
 use crate::{
-    activity::{damage_calc::DifficultyOptions, Activity, Player, PlayerClass},
-    enemies::{Enemy, EnemyType},
+    abilities::ability_calc::{AbilityDamageResponse, AbilityDpsResponse},
+    activity::{
+        damage_calc::DifficultyOptions, Activity, Player, PlayerBuff, PlayerClass, PlayerState,
+    },
+    d2_enums::DamageType,
+    enemies::{champion::ChampionProfile, Enemy, EnemyType, ResistPhase},
     perks::Perk,
-    weapons::ttk_calc::ResillienceSummary,
+    weapons::{
+        accuracy_calc::{AccuracyDpsResponse, AccuracyModel, AccuracyTtkResponse, Percentiles},
+        dps_calc::{AddClearResponse, DurationDpsResponse},
+        falloff_calc::{TtkCurveResponse, TtkDistancePoint},
+        patch_calc::PatchDiff,
+        pve_ttk_calc::{PveKillData, PveTtkResponse},
+        pvp_ttk_calc::{OvershieldType, PvpHealing, PvpShotMix, PvpTarget, PvpTtkResponse},
+        ttk_calc::ResillienceSummary,
+    },
 };
 
 use super::rs_types::{
@@ -53,6 +65,7 @@ impl Into<Perk> for PyPerk {
             stat_buffs: self.stat_buffs,
             enhanced: self.enhanced,
             value: self.value,
+            raw_hash: self.hash,
             hash: self.hash,
         }
     }
@@ -219,21 +232,197 @@ impl From<DpsResponse> for PyDpsResponse {
     }
 }
 
+#[derive(Debug, Clone, Default)]
+#[pyclass(name = "DurationDpsResponse")]
+pub struct PyDurationDpsResponse {
+    #[pyo3(get)]
+    pub time_damage_data: Vec<(f64, f64)>,
+    #[pyo3(get)]
+    pub total_damage: f64,
+    #[pyo3(get)]
+    pub total_shots: i32,
+    #[pyo3(get)]
+    pub duration: f64,
+    #[pyo3(get)]
+    pub dps: f64,
+    #[pyo3(get)]
+    pub mag_left: i32,
+    #[pyo3(get)]
+    pub reserves_left: i32,
+}
+#[pymethods]
+impl PyDurationDpsResponse {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "DurationDpsResponse(total_damage={}, total_shots={}, duration={}, dps={}, mag_left={}, reserves_left={})",
+            self.total_damage, self.total_shots, self.duration, self.dps, self.mag_left, self.reserves_left
+        ))
+    }
+}
+impl From<DurationDpsResponse> for PyDurationDpsResponse {
+    fn from(r: DurationDpsResponse) -> Self {
+        PyDurationDpsResponse {
+            time_damage_data: r.time_damage_data,
+            total_damage: r.total_damage,
+            total_shots: r.total_shots,
+            duration: r.duration,
+            dps: r.dps,
+            mag_left: r.mag_left,
+            reserves_left: r.reserves_left,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[pyclass(name = "AddClearResponse")]
+pub struct PyAddClearResponse {
+    #[pyo3(get)]
+    pub dps: PyDpsResponse,
+    #[pyo3(get)]
+    pub kill_times: Vec<f64>,
+    #[pyo3(get)]
+    pub time_to_clear: Option<f64>,
+}
+#[pymethods]
+impl PyAddClearResponse {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "AddClearResponse(kill_times={:?}, time_to_clear={:?})",
+            self.kill_times, self.time_to_clear
+        ))
+    }
+}
+impl From<AddClearResponse> for PyAddClearResponse {
+    fn from(r: AddClearResponse) -> Self {
+        PyAddClearResponse {
+            dps: r.dps.into(),
+            kill_times: r.kill_times,
+            time_to_clear: r.time_to_clear,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[pyclass(name = "PatchDiff")]
+pub struct PyPatchDiff {
+    #[pyo3(get)]
+    pub weapon_hash: u32,
+    #[pyo3(get)]
+    pub old_patch: String,
+    #[pyo3(get)]
+    pub new_patch: String,
+    ///(category, field, old, new)
+    #[pyo3(get)]
+    pub changes: Vec<(String, String, f64, f64)>,
+}
+#[pymethods]
+impl PyPatchDiff {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "PatchDiff(weapon_hash={}, old_patch={}, new_patch={}, changes={:?})",
+            self.weapon_hash, self.old_patch, self.new_patch, self.changes
+        ))
+    }
+}
+impl From<PatchDiff> for PyPatchDiff {
+    fn from(r: PatchDiff) -> Self {
+        PyPatchDiff {
+            weapon_hash: r.weapon_hash,
+            old_patch: r.old_patch,
+            new_patch: r.new_patch,
+            changes: r
+                .changes
+                .into_iter()
+                .map(|change| {
+                    (
+                        format!("{:?}", change.category),
+                        change.field,
+                        change.old,
+                        change.new,
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[pyclass(name = "AbilityDamageResponse")]
+pub struct PyAbilityDamageResponse {
+    #[pyo3(get)]
+    pub impact: f64,
+    #[pyo3(get)]
+    pub secondary: f64,
+    #[pyo3(get)]
+    pub total: f64,
+    #[pyo3(get)]
+    pub hits: u32,
+    #[pyo3(get)]
+    pub damage_mult: f64,
+}
+#[pymethods]
+impl PyAbilityDamageResponse {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "AbilityDamageResponse(impact={}, secondary={}, total={}, hits={}, damage_mult={})",
+            self.impact, self.secondary, self.total, self.hits, self.damage_mult
+        ))
+    }
+}
+impl From<AbilityDamageResponse> for PyAbilityDamageResponse {
+    fn from(r: AbilityDamageResponse) -> Self {
+        PyAbilityDamageResponse {
+            impact: r.impact,
+            secondary: r.secondary,
+            total: r.total,
+            hits: r.hits,
+            damage_mult: r.damage_mult,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[pyclass(name = "AbilityDpsResponse")]
+pub struct PyAbilityDpsResponse {
+    #[pyo3(get)]
+    pub total_damage: f64,
+    #[pyo3(get)]
+    pub total_time: f64,
+    #[pyo3(get)]
+    pub dps: f64,
+}
+#[pymethods]
+impl PyAbilityDpsResponse {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "AbilityDpsResponse(total_damage={}, total_time={}, dps={})",
+            self.total_damage, self.total_time, self.dps
+        ))
+    }
+}
+impl From<AbilityDpsResponse> for PyAbilityDpsResponse {
+    fn from(r: AbilityDpsResponse) -> Self {
+        PyAbilityDpsResponse {
+            total_damage: r.total_damage,
+            total_time: r.total_time,
+            dps: r.dps,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[pyclass(name = "OptimalKillData")]
 pub struct PyOptimalKillData {
     headshots: i32,
     bodyshots: i32,
     time_taken: f64,
-    //defines how far away this ttk is achievalbe if all hits ar crits
-    achievable_range: f64,
 }
 #[pymethods]
 impl PyOptimalKillData {
     fn __repr__(&self) -> PyResult<String> {
         Ok(format!(
-            "OptimalKillData(headshots={}, bodyshots={}, time_taken={}, all_crit_range={})",
-            self.headshots, self.bodyshots, self.time_taken, self.achievable_range
+            "OptimalKillData(headshots={}, bodyshots={}, time_taken={})",
+            self.headshots, self.bodyshots, self.time_taken
         ))
     }
 }
@@ -282,12 +471,480 @@ impl From<ResillienceSummary> for PyResillienceSummary {
                 headshots: r.optimal_ttk.headshots,
                 bodyshots: r.optimal_ttk.bodyshots,
                 time_taken: r.optimal_ttk.time_taken,
-                achievable_range: r.optimal_ttk.achievable_range,
             },
         }
     }
 }
 
+#[derive(Debug, Clone, Default)]
+#[pyclass(name = "PveKillData")]
+pub struct PyPveKillData {
+    #[pyo3(get)]
+    pub precision_ratio: f64,
+    #[pyo3(get)]
+    pub precision_shots: i32,
+    #[pyo3(get)]
+    pub body_shots: i32,
+    #[pyo3(get)]
+    pub ammo_used: i32,
+    #[pyo3(get)]
+    pub reloads: i32,
+    #[pyo3(get)]
+    pub time_taken: f64,
+    #[pyo3(get)]
+    pub killed: bool,
+    #[pyo3(get)]
+    pub phase_times: Vec<Option<f64>>,
+    #[pyo3(get)]
+    pub stuns: u32,
+}
+#[pymethods]
+impl PyPveKillData {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "PveKillData(precision_ratio={}, precision_shots={}, body_shots={}, ammo_used={}, reloads={}, time_taken={}, killed={}, phase_times={:?}, stuns={})",
+            self.precision_ratio,
+            self.precision_shots,
+            self.body_shots,
+            self.ammo_used,
+            self.reloads,
+            self.time_taken,
+            self.killed,
+            self.phase_times,
+            self.stuns
+        ))
+    }
+}
+impl From<PveKillData> for PyPveKillData {
+    fn from(r: PveKillData) -> Self {
+        PyPveKillData {
+            precision_ratio: r.precision_ratio,
+            precision_shots: r.precision_shots,
+            body_shots: r.body_shots,
+            ammo_used: r.ammo_used,
+            reloads: r.reloads,
+            time_taken: r.time_taken,
+            killed: r.killed,
+            phase_times: r.phase_times,
+            stuns: r.stuns,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[pyclass(name = "PveTtkResponse")]
+pub struct PyPveTtkResponse {
+    #[pyo3(get)]
+    pub enemy: String,
+    #[pyo3(get)]
+    pub health: f64,
+    #[pyo3(get)]
+    pub adjusted_health: f64,
+    #[pyo3(get)]
+    pub kills: Vec<PyPveKillData>,
+    #[pyo3(get)]
+    pub melee: Option<PyPveKillData>,
+}
+#[pymethods]
+impl PyPveTtkResponse {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "PveTtkResponse(enemy={}, health={}, adjusted_health={}, kills={:?}, melee={:?})",
+            self.enemy, self.health, self.adjusted_health, self.kills, self.melee
+        ))
+    }
+}
+impl From<PveTtkResponse> for PyPveTtkResponse {
+    fn from(r: PveTtkResponse) -> Self {
+        PyPveTtkResponse {
+            enemy: r.enemy,
+            health: r.health,
+            adjusted_health: r.adjusted_health,
+            kills: r.kills.into_iter().map(|kill| kill.into()).collect(),
+            melee: r.melee.map(|kill| kill.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[pyclass(name = "PvpShotMix")]
+pub struct PyPvpShotMix {
+    #[pyo3(get)]
+    pub headshots: i32,
+    #[pyo3(get)]
+    pub bodyshots: i32,
+    #[pyo3(get)]
+    pub time_taken: f64,
+    #[pyo3(get)]
+    pub reloads: i32,
+    #[pyo3(get)]
+    pub killed: bool,
+    #[pyo3(get)]
+    pub regen_reset: bool,
+}
+#[pymethods]
+impl PyPvpShotMix {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "PvpShotMix(headshots={}, bodyshots={}, time_taken={}, reloads={}, killed={}, regen_reset={})",
+            self.headshots,
+            self.bodyshots,
+            self.time_taken,
+            self.reloads,
+            self.killed,
+            self.regen_reset
+        ))
+    }
+}
+impl From<PvpShotMix> for PyPvpShotMix {
+    fn from(r: PvpShotMix) -> Self {
+        PyPvpShotMix {
+            headshots: r.headshots,
+            bodyshots: r.bodyshots,
+            time_taken: r.time_taken,
+            reloads: r.reloads,
+            killed: r.killed,
+            regen_reset: r.regen_reset,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[pyclass(name = "PvpTtkResponse")]
+pub struct PyPvpTtkResponse {
+    #[pyo3(get)]
+    pub health: f64,
+    #[pyo3(get)]
+    pub mixes: Vec<PyPvpShotMix>,
+    #[pyo3(get)]
+    pub melee: Option<PyPvpShotMix>,
+}
+#[pymethods]
+impl PyPvpTtkResponse {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "PvpTtkResponse(health={}, mixes={:?}, melee={:?})",
+            self.health, self.mixes, self.melee
+        ))
+    }
+}
+impl From<PvpTtkResponse> for PyPvpTtkResponse {
+    fn from(r: PvpTtkResponse) -> Self {
+        PyPvpTtkResponse {
+            health: r.health,
+            mixes: r.mixes.into_iter().map(|mix| mix.into()).collect(),
+            melee: r.melee.map(|mix| mix.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[pyclass(name = "PvpTarget")]
+pub struct PyPvpTarget {
+    #[pyo3(get, set)]
+    pub resilience: usize,
+    #[pyo3(get, set)]
+    pub overshield_type: PyOvershieldType,
+    #[pyo3(get, set)]
+    pub overshield: f64,
+    #[pyo3(get, set)]
+    pub overshield_decay: f64,
+    #[pyo3(get, set)]
+    pub healing: PyPvpHealing,
+    #[pyo3(get, set)]
+    pub recovery: usize,
+    #[pyo3(get, set)]
+    pub recovery_delay: f64,
+    #[pyo3(get, set)]
+    pub frost_armor: u32,
+    #[pyo3(get, set)]
+    pub woven_mail: bool,
+    #[pyo3(get, set)]
+    pub concussive_dampener: bool,
+}
+#[pymethods]
+impl PyPvpTarget {
+    #[new]
+    pub fn new(
+        _resilience: usize,
+        _overshield_type: Option<PyOvershieldType>,
+        _overshield: Option<f64>,
+        _healing: Option<PyPvpHealing>,
+        _recovery: Option<usize>,
+    ) -> Self {
+        PyPvpTarget {
+            resilience: _resilience,
+            overshield_type: _overshield_type.unwrap_or_default(),
+            overshield: _overshield.unwrap_or_default(),
+            healing: _healing.unwrap_or_default(),
+            recovery: _recovery.unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+    #[pyo3(name = "default")]
+    #[staticmethod]
+    fn py_default() -> Self {
+        PyPvpTarget::default()
+    }
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "PvpTarget(resilience={}, overshield_type={:?}, overshield={}, overshield_decay={}, healing={:?}, recovery={}, recovery_delay={}, frost_armor={}, woven_mail={}, concussive_dampener={})",
+            self.resilience,
+            self.overshield_type,
+            self.overshield,
+            self.overshield_decay,
+            self.healing,
+            self.recovery,
+            self.recovery_delay,
+            self.frost_armor,
+            self.woven_mail,
+            self.concussive_dampener
+        ))
+    }
+}
+impl Default for PyPvpTarget {
+    fn default() -> Self {
+        PvpTarget::default().into()
+    }
+}
+impl From<PvpTarget> for PyPvpTarget {
+    fn from(t: PvpTarget) -> Self {
+        PyPvpTarget {
+            resilience: t.resilience,
+            overshield_type: t.overshield_type.into(),
+            overshield: t.overshield,
+            overshield_decay: t.overshield_decay,
+            healing: t.healing.into(),
+            recovery: t.recovery,
+            recovery_delay: t.recovery_delay,
+            frost_armor: t.frost_armor,
+            woven_mail: t.woven_mail,
+            concussive_dampener: t.concussive_dampener,
+        }
+    }
+}
+impl From<PyPvpTarget> for PvpTarget {
+    fn from(t: PyPvpTarget) -> Self {
+        PvpTarget {
+            resilience: t.resilience,
+            overshield_type: t.overshield_type.into(),
+            overshield: t.overshield,
+            overshield_decay: t.overshield_decay,
+            healing: t.healing.into(),
+            recovery: t.recovery,
+            recovery_delay: t.recovery_delay,
+            frost_armor: t.frost_armor,
+            woven_mail: t.woven_mail,
+            concussive_dampener: t.concussive_dampener,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[pyclass(name = "AccuracyModel")]
+pub struct PyAccuracyModel {
+    #[pyo3(get, set)]
+    pub hit_rate: f64,
+    #[pyo3(get, set)]
+    pub precision_ratio: f64,
+    #[pyo3(get, set)]
+    pub spread: f64,
+    #[pyo3(get, set)]
+    pub distance: f64,
+    #[pyo3(get, set)]
+    pub range_penalty: f64,
+}
+#[pymethods]
+impl PyAccuracyModel {
+    #[new]
+    pub fn new(
+        _hit_rate: f64,
+        _precision_ratio: f64,
+        _spread: Option<f64>,
+        _distance: Option<f64>,
+        _range_penalty: Option<f64>,
+    ) -> Self {
+        PyAccuracyModel {
+            hit_rate: _hit_rate,
+            precision_ratio: _precision_ratio,
+            spread: _spread.unwrap_or_default(),
+            distance: _distance.unwrap_or_default(),
+            range_penalty: _range_penalty.unwrap_or_default(),
+        }
+    }
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "AccuracyModel(hit_rate={}, precision_ratio={}, spread={}, distance={}, range_penalty={})",
+            self.hit_rate, self.precision_ratio, self.spread, self.distance, self.range_penalty
+        ))
+    }
+}
+impl From<PyAccuracyModel> for AccuracyModel {
+    fn from(m: PyAccuracyModel) -> Self {
+        AccuracyModel {
+            hit_rate: m.hit_rate,
+            precision_ratio: m.precision_ratio,
+            spread: m.spread,
+            distance: m.distance,
+            range_penalty: m.range_penalty,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[pyclass(name = "Percentiles")]
+pub struct PyPercentiles {
+    #[pyo3(get)]
+    pub mean: f64,
+    #[pyo3(get)]
+    pub p10: f64,
+    #[pyo3(get)]
+    pub p50: f64,
+    #[pyo3(get)]
+    pub p90: f64,
+}
+#[pymethods]
+impl PyPercentiles {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "Percentiles(mean={}, p10={}, p50={}, p90={})",
+            self.mean, self.p10, self.p50, self.p90
+        ))
+    }
+}
+impl From<Percentiles> for PyPercentiles {
+    fn from(p: Percentiles) -> Self {
+        PyPercentiles {
+            mean: p.mean,
+            p10: p.p10,
+            p50: p.p50,
+            p90: p.p90,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[pyclass(name = "AccuracyDpsResponse")]
+pub struct PyAccuracyDpsResponse {
+    #[pyo3(get)]
+    pub perfect_dps: f64,
+    #[pyo3(get)]
+    pub dps: PyPercentiles,
+    #[pyo3(get)]
+    pub hit_chance: f64,
+}
+#[pymethods]
+impl PyAccuracyDpsResponse {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "AccuracyDpsResponse(perfect_dps={}, dps={:?}, hit_chance={})",
+            self.perfect_dps, self.dps, self.hit_chance
+        ))
+    }
+}
+impl From<AccuracyDpsResponse> for PyAccuracyDpsResponse {
+    fn from(r: AccuracyDpsResponse) -> Self {
+        PyAccuracyDpsResponse {
+            perfect_dps: r.perfect_dps,
+            dps: r.dps.into(),
+            hit_chance: r.hit_chance,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[pyclass(name = "AccuracyTtkResponse")]
+pub struct PyAccuracyTtkResponse {
+    #[pyo3(get)]
+    pub perfect_ttk: f64,
+    #[pyo3(get)]
+    pub ttk: PyPercentiles,
+    #[pyo3(get)]
+    pub kill_rate: f64,
+    #[pyo3(get)]
+    pub hit_chance: f64,
+}
+#[pymethods]
+impl PyAccuracyTtkResponse {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "AccuracyTtkResponse(perfect_ttk={}, ttk={:?}, kill_rate={}, hit_chance={})",
+            self.perfect_ttk, self.ttk, self.kill_rate, self.hit_chance
+        ))
+    }
+}
+impl From<AccuracyTtkResponse> for PyAccuracyTtkResponse {
+    fn from(r: AccuracyTtkResponse) -> Self {
+        PyAccuracyTtkResponse {
+            perfect_ttk: r.perfect_ttk,
+            ttk: r.ttk.into(),
+            kill_rate: r.kill_rate,
+            hit_chance: r.hit_chance,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[pyclass(name = "TtkDistancePoint")]
+pub struct PyTtkDistancePoint {
+    #[pyo3(get)]
+    pub distance: f64,
+    #[pyo3(get)]
+    pub optimal_shots: i32,
+    #[pyo3(get)]
+    pub optimal_ttk: Option<f64>,
+    #[pyo3(get)]
+    pub body_shots: i32,
+    #[pyo3(get)]
+    pub body_ttk: Option<f64>,
+}
+#[pymethods]
+impl PyTtkDistancePoint {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "TtkDistancePoint(distance={}, optimal_shots={}, optimal_ttk={:?}, body_shots={}, body_ttk={:?})",
+            self.distance, self.optimal_shots, self.optimal_ttk, self.body_shots, self.body_ttk
+        ))
+    }
+}
+impl From<TtkDistancePoint> for PyTtkDistancePoint {
+    fn from(r: TtkDistancePoint) -> Self {
+        PyTtkDistancePoint {
+            distance: r.distance,
+            optimal_shots: r.optimal_shots,
+            optimal_ttk: r.optimal_ttk,
+            body_shots: r.body_shots,
+            body_ttk: r.body_ttk,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[pyclass(name = "TtkCurveResponse")]
+pub struct PyTtkCurveResponse {
+    #[pyo3(get)]
+    pub points: Vec<PyTtkDistancePoint>,
+    #[pyo3(get)]
+    pub breakpoints: Vec<f64>,
+}
+#[pymethods]
+impl PyTtkCurveResponse {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!(
+            "TtkCurveResponse(points={:?}, breakpoints={:?})",
+            self.points, self.breakpoints
+        ))
+    }
+}
+impl From<TtkCurveResponse> for PyTtkCurveResponse {
+    fn from(r: TtkCurveResponse) -> Self {
+        PyTtkCurveResponse {
+            points: r.points.into_iter().map(|point| point.into()).collect(),
+            breakpoints: r.breakpoints,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 #[pyclass(name = "Activity")]
 pub struct PyActivity {
@@ -299,37 +956,63 @@ pub struct PyActivity {
     pub rpl: u32,
     #[pyo3(get, set)]
     pub cap: i32,
+    ///Pairs of power delta and damage multiplier, only read for a CUSTOM difficulty
+    #[pyo3(get, set)]
+    pub custom_table: Vec<(f64, f64)>,
 }
 #[pymethods]
 impl PyActivity {
     #[new]
-    pub fn new(_name: String, _difficulty: PyDifficultyOptions, _rpl: u32, _cap: i32) -> Self {
+    pub fn new(
+        _name: String,
+        _difficulty: PyDifficultyOptions,
+        _rpl: u32,
+        _cap: i32,
+        _custom_table: Option<Vec<(f64, f64)>>,
+    ) -> Self {
         PyActivity {
             name: _name,
             difficulty: _difficulty,
             rpl: _rpl,
             cap: _cap,
+            custom_table: _custom_table.unwrap_or_default(),
         }
     }
     fn __repr__(&self) -> PyResult<String> {
         Ok(format!(
-            "Activity(name={}, difficulty={:?}, rpl={}, cap={})",
-            self.name, self.difficulty, self.rpl, self.cap
+            "Activity(name={}, difficulty={:?}, rpl={}, cap={}, custom_table={:?})",
+            self.name, self.difficulty, self.rpl, self.cap, self.custom_table
         ))
     }
 }
+impl PyActivity {
+    ///A CUSTOM difficulty uses the activity cap as its own
+    pub fn difficulty_options(&self) -> PyResult<DifficultyOptions> {
+        Ok(match self.difficulty {
+            PyDifficultyOptions::NORMAL => DifficultyOptions::NORMAL,
+            PyDifficultyOptions::RAID => DifficultyOptions::RAID,
+            PyDifficultyOptions::MASTER => DifficultyOptions::MASTER,
+            PyDifficultyOptions::CUSTOM => {
+                let (deltas, values): (Vec<f64>, Vec<f64>) =
+                    self.custom_table.iter().copied().unzip();
+                DifficultyOptions::custom(&deltas, &values, self.cap)
+                    .map_err(pyo3::exceptions::PyValueError::new_err)?
+            }
+        })
+    }
+}
 impl From<Activity> for PyActivity {
     fn from(a: Activity) -> Self {
-        let diff = match a.difficulty {
-            DifficultyOptions::NORMAL => PyDifficultyOptions::NORMAL,
-            DifficultyOptions::RAID => PyDifficultyOptions::RAID,
-            DifficultyOptions::MASTER => PyDifficultyOptions::MASTER,
+        let custom_table = match &a.difficulty {
+            DifficultyOptions::Custom(pairs, _) => pairs.clone(),
+            _ => Vec::new(),
         };
         PyActivity {
             name: a.name,
-            difficulty: diff,
+            difficulty: a.difficulty.into(),
             rpl: a.rpl,
             cap: a.cap,
+            custom_table,
         }
     }
 }
@@ -341,6 +1024,21 @@ pub struct PyPlayer {
     pub powerl_level: u32,
     #[pyo3(get, set)]
     pub class: PyPlayerClass,
+    ///Damage type hash of the subclass, 0 if unknown
+    #[pyo3(get, set)]
+    pub subclass: u32,
+    #[pyo3(get, set)]
+    pub aspects: Vec<u32>,
+    #[pyo3(get, set)]
+    pub fragments: Vec<u32>,
+    #[pyo3(get, set)]
+    pub resilience: i32,
+    #[pyo3(get, set)]
+    pub mobility: i32,
+    #[pyo3(get, set)]
+    pub recovery: i32,
+    #[pyo3(get, set)]
+    pub buffs: Vec<PyPlayerBuff>,
 }
 #[pymethods]
 impl PyPlayer {
@@ -349,6 +1047,7 @@ impl PyPlayer {
         PyPlayer {
             powerl_level: _power_level,
             class: _class,
+            ..Default::default()
         }
     }
     #[pyo3(name = "default")]
@@ -358,8 +1057,14 @@ impl PyPlayer {
     }
     fn __repr__(&self) -> PyResult<String> {
         Ok(format!(
-            "Player(power_level={}, class={:?})",
-            self.powerl_level, self.class
+            "Player(power_level={}, class={:?}, subclass={}, resilience={}, mobility={}, recovery={}, buffs={:?})",
+            self.powerl_level,
+            self.class,
+            self.subclass,
+            self.resilience,
+            self.mobility,
+            self.recovery,
+            self.buffs
         ))
     }
 }
@@ -368,6 +1073,15 @@ impl Into<Player> for PyPlayer {
         Player {
             pl: self.powerl_level,
             class: self.class.into(),
+            state: PlayerState {
+                subclass: DamageType::from(self.subclass),
+                aspects: self.aspects,
+                fragments: self.fragments,
+                resilience: self.resilience,
+                mobility: self.mobility,
+                recovery: self.recovery,
+                buffs: self.buffs.into_iter().map(|buff| buff.into()).collect(),
+            },
         }
     }
 }
@@ -376,6 +1090,13 @@ impl From<Player> for PyPlayer {
         PyPlayer {
             powerl_level: p.pl,
             class: p.class.into(),
+            subclass: p.state.subclass.into(),
+            aspects: p.state.aspects,
+            fragments: p.state.fragments,
+            resilience: p.state.resilience,
+            mobility: p.state.mobility,
+            recovery: p.state.recovery,
+            buffs: p.state.buffs.into_iter().map(|buff| buff.into()).collect(),
         }
     }
 }
@@ -383,6 +1104,8 @@ impl From<Player> for PyPlayer {
 #[derive(Debug, Clone, Default)]
 #[pyclass(name = "Enemy")]
 pub struct PyEnemy {
+    #[pyo3(get, set)]
+    pub name: String,
     #[pyo3(get, set)]
     pub health: f64,
     #[pyo3(get, set)]
@@ -393,6 +1116,11 @@ pub struct PyEnemy {
     pub type_: PyEnemyType,
     #[pyo3(get, set)]
     pub tier: u8,
+    ///(health_fraction, damage_resistance)
+    #[pyo3(get, set)]
+    pub resist_phases: Vec<(f64, f64)>,
+    //kept so catalogue champions survive a round trip through python
+    pub champion: Option<ChampionProfile>,
 }
 #[pymethods]
 impl PyEnemy {
@@ -410,6 +1138,7 @@ impl PyEnemy {
             damage_resistance: _damage_resistance,
             type_: _type_,
             tier: _tier,
+            ..Default::default()
         }
     }
     #[pyo3(name = "default")]
@@ -419,30 +1148,53 @@ impl PyEnemy {
     }
     fn __repr__(&self) -> PyResult<String> {
         Ok(format!(
-            "Enemy(health={}, damage={}, damage_resistance={}, type_={:?}, tier={})",
-            self.health, self.damage, self.damage_resistance, self.type_, self.tier
+            "Enemy(name={}, health={}, damage={}, damage_resistance={}, type_={:?}, tier={}, resist_phases={:?})",
+            self.name,
+            self.health,
+            self.damage,
+            self.damage_resistance,
+            self.type_,
+            self.tier,
+            self.resist_phases
         ))
     }
 }
 impl From<Enemy> for PyEnemy {
     fn from(e: Enemy) -> Self {
         PyEnemy {
+            name: e.name,
             health: e.health,
             damage: e.damage,
             damage_resistance: e.damage_resistance,
             type_: e.type_.into(),
             tier: e.tier,
+            resist_phases: e
+                .resist_phases
+                .into_iter()
+                .map(|phase| (phase.health_fraction, phase.damage_resistance))
+                .collect(),
+            champion: e.champion,
         }
     }
 }
 impl Into<Enemy> for PyEnemy {
     fn into(self) -> Enemy {
         Enemy {
+            name: self.name,
             health: self.health,
             damage: self.damage,
             damage_resistance: self.damage_resistance,
             type_: self.type_.into(),
             tier: self.tier,
+            resist_phases: self
+                .resist_phases
+                .into_iter()
+                .map(|(health_fraction, damage_resistance)| ResistPhase {
+                    health_fraction,
+                    damage_resistance,
+                })
+                .collect(),
+            champion: self.champion,
         }
     }
 }
@@ -454,6 +1206,7 @@ pub enum PyDifficultyOptions {
     NORMAL = 1,
     RAID = 2,
     MASTER = 3,
+    CUSTOM = 4,
 }
 #[pymethods]
 impl PyDifficultyOptions {
@@ -469,6 +1222,7 @@ impl PyDifficultyOptions {
                 PyDifficultyOptions::NORMAL => "NORMAL",
                 PyDifficultyOptions::RAID => "RAID",
                 PyDifficultyOptions::MASTER => "MASTER",
+                PyDifficultyOptions::CUSTOM => "CUSTOM",
             }
         ))
     }
@@ -478,21 +1232,13 @@ impl Default for PyDifficultyOptions {
         PyDifficultyOptions::NORMAL
     }
 }
-impl Into<DifficultyOptions> for PyDifficultyOptions {
-    fn into(self) -> DifficultyOptions {
-        match self {
-            PyDifficultyOptions::NORMAL => DifficultyOptions::NORMAL,
-            PyDifficultyOptions::RAID => DifficultyOptions::RAID,
-            PyDifficultyOptions::MASTER => DifficultyOptions::MASTER,
-        }
-    }
-}
 impl From<DifficultyOptions> for PyDifficultyOptions {
     fn from(d: DifficultyOptions) -> Self {
         match d {
             DifficultyOptions::NORMAL => PyDifficultyOptions::NORMAL,
             DifficultyOptions::RAID => PyDifficultyOptions::RAID,
             DifficultyOptions::MASTER => PyDifficultyOptions::MASTER,
+            DifficultyOptions::Custom(..) => PyDifficultyOptions::CUSTOM,
         }
     }
 }
@@ -550,6 +1296,50 @@ impl From<PlayerClass> for PyPlayerClass {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[pyclass(name = "PlayerBuff")]
+pub enum PyPlayerBuff {
+    Radiant,
+    WellOfRadiance,
+    EmpoweringRift,
+    WardOfDawn,
+    BannerShield,
+    NobleRounds,
+    Weaken,
+}
+#[pymethods]
+impl PyPlayerBuff {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("PlayerBuff({:?})", PlayerBuff::from(*self)))
+    }
+}
+impl From<PyPlayerBuff> for PlayerBuff {
+    fn from(b: PyPlayerBuff) -> Self {
+        match b {
+            PyPlayerBuff::Radiant => PlayerBuff::Radiant,
+            PyPlayerBuff::WellOfRadiance => PlayerBuff::WellOfRadiance,
+            PyPlayerBuff::EmpoweringRift => PlayerBuff::EmpoweringRift,
+            PyPlayerBuff::WardOfDawn => PlayerBuff::WardOfDawn,
+            PyPlayerBuff::BannerShield => PlayerBuff::BannerShield,
+            PyPlayerBuff::NobleRounds => PlayerBuff::NobleRounds,
+            PyPlayerBuff::Weaken => PlayerBuff::Weaken,
+        }
+    }
+}
+impl From<PlayerBuff> for PyPlayerBuff {
+    fn from(b: PlayerBuff) -> Self {
+        match b {
+            PlayerBuff::Radiant => PyPlayerBuff::Radiant,
+            PlayerBuff::WellOfRadiance => PyPlayerBuff::WellOfRadiance,
+            PlayerBuff::EmpoweringRift => PyPlayerBuff::EmpoweringRift,
+            PlayerBuff::WardOfDawn => PyPlayerBuff::WardOfDawn,
+            PlayerBuff::BannerShield => PyPlayerBuff::BannerShield,
+            PlayerBuff::NobleRounds => PyPlayerBuff::NobleRounds,
+            PlayerBuff::Weaken => PyPlayerBuff::Weaken,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[pyclass(name = "EnemyType")]
 pub enum PyEnemyType {
@@ -618,3 +1408,72 @@ impl From<EnemyType> for PyEnemyType {
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[pyclass(name = "OvershieldType")]
+pub enum PyOvershieldType {
+    #[default]
+    NONE,
+    PERSISTENT,
+    DECAYING,
+}
+#[pymethods]
+impl PyOvershieldType {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("OvershieldType({:?})", OvershieldType::from(*self)))
+    }
+}
+impl From<PyOvershieldType> for OvershieldType {
+    fn from(o: PyOvershieldType) -> Self {
+        match o {
+            PyOvershieldType::NONE => OvershieldType::NONE,
+            PyOvershieldType::PERSISTENT => OvershieldType::PERSISTENT,
+            PyOvershieldType::DECAYING => OvershieldType::DECAYING,
+        }
+    }
+}
+impl From<OvershieldType> for PyOvershieldType {
+    fn from(o: OvershieldType) -> Self {
+        match o {
+            OvershieldType::NONE => PyOvershieldType::NONE,
+            OvershieldType::PERSISTENT => PyOvershieldType::PERSISTENT,
+            OvershieldType::DECAYING => PyOvershieldType::DECAYING,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[pyclass(name = "PvpHealing")]
+pub enum PyPvpHealing {
+    #[default]
+    NONE,
+    RESTORATION,
+    AMPLIFIED,
+    DEVOUR,
+}
+#[pymethods]
+impl PyPvpHealing {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("PvpHealing({:?})", PvpHealing::from(*self)))
+    }
+}
+impl From<PyPvpHealing> for PvpHealing {
+    fn from(h: PyPvpHealing) -> Self {
+        match h {
+            PyPvpHealing::NONE => PvpHealing::NONE,
+            PyPvpHealing::RESTORATION => PvpHealing::RESTORATION,
+            PyPvpHealing::AMPLIFIED => PvpHealing::AMPLIFIED,
+            PyPvpHealing::DEVOUR => PvpHealing::DEVOUR,
+        }
+    }
+}
+impl From<PvpHealing> for PyPvpHealing {
+    fn from(h: PvpHealing) -> Self {
+        match h {
+            PvpHealing::NONE => PyPvpHealing::NONE,
+            PvpHealing::RESTORATION => PyPvpHealing::RESTORATION,
+            PvpHealing::AMPLIFIED => PyPvpHealing::AMPLIFIED,
+            PvpHealing::DEVOUR => PyPvpHealing::DEVOUR,
+        }
+    }
+}
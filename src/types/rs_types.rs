@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::enemies::EnemyType;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DataPointers {
     pub h: usize,
     pub r: usize,
@@ -12,12 +12,13 @@ pub struct DataPointers {
     pub s: usize,
     pub f: usize,
     pub a: usize,
+    pub m: usize,
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WeaponPath(pub u32, pub u32);
 
-#[derive(Debug, Clone, Default, Copy, Serialize)]
+#[derive(Debug, Clone, Default, Copy, PartialEq, Serialize, Deserialize)]
 pub struct FiringData {
     pub damage: f64,
     pub crit_mult: f64,
@@ -29,7 +30,20 @@ pub struct FiringData {
     pub timestamp: u64,
 }
 
-#[derive(Debug, Clone, Serialize)]
+///Swing data for swords and glaive melees, delays are in seconds
+#[derive(Debug, Clone, Default, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MeleeFormula {
+    pub light_damage: f64,
+    pub light_delay: f64,
+    pub heavy_damage: f64,
+    pub heavy_delay: f64,
+    pub guard_damage: f64,
+    pub guard_delay: f64,
+    pub ammo_per_heavy: i32,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DamageMods {
     pub pve: f64,
     pub minor: f64,
@@ -69,7 +83,7 @@ impl DamageMods {
     }
 }
 
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct RangeFormula {
     pub start: StatQuadraticFormula,
     pub end: StatQuadraticFormula,
@@ -79,7 +93,7 @@ pub struct RangeFormula {
 }
 
 //even if just linear use this
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct StatQuadraticFormula {
     pub evpp: f64,
     pub vpp: f64,
@@ -91,14 +105,14 @@ impl StatQuadraticFormula {
     }
 }
 
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct ReloadFormula {
     pub reload_data: StatQuadraticFormula,
     pub ammo_percent: f64,
     pub timestamp: u64,
 }
 
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct HandlingFormula {
     pub ready: StatQuadraticFormula,
     pub stow: StatQuadraticFormula,
@@ -106,7 +120,7 @@ pub struct HandlingFormula {
     pub timestamp: u64,
 }
 
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct AmmoFormula {
     pub mag: StatQuadraticFormula,
     pub round_to: i32,
@@ -114,7 +128,7 @@ pub struct AmmoFormula {
     pub timestamp: u64,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct RangeResponse {
     pub hip_falloff_start: f64,
     pub hip_falloff_end: f64,
@@ -124,7 +138,7 @@ pub struct RangeResponse {
     pub timestamp: u64,
 }
 
-#[derive(Debug, Clone, Default, Copy)]
+#[derive(Debug, Clone, Default, Copy, Serialize)]
 pub struct HandlingResponse {
     pub ready_time: f64,
     pub stow_time: f64,
@@ -132,21 +146,21 @@ pub struct HandlingResponse {
     pub timestamp: u64,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct AmmoResponse {
     pub mag_size: i32,
     pub reserve_size: i32,
     pub timestamp: u64,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct ReloadResponse {
     pub reload_time: f64,
     pub ammo_time: f64,
     pub timestamp: u64,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct DpsResponse {
     pub dps_per_mag: Vec<f64>,
     pub time_damage_data: Vec<(f64, f64)>,
@@ -166,6 +180,9 @@ impl DpsResponse {
     }
     pub fn get_dps_over_time(&self) -> Vec<(f64, f64)> {
         let dps_data = &self.time_damage_data;
+        if dps_data.is_empty() {
+            return Vec::new();
+        }
         let mut damage_so_far = dps_data[0].1;
         let mut dps_lst = Vec::new();
         for hit in dps_data {
@@ -178,7 +195,7 @@ impl DpsResponse {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct FiringResponse {
     pub pvp_impact_damage: f64,
     pub pvp_explosion_damage: f64,
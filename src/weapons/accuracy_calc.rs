@@ -0,0 +1,283 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{d2_enums::Seconds, enemies::Enemy, types::rs_types::RangeResponse};
+
+use super::{
+    dps_calc::rolled_dps_calc,
+    pvp_ttk_calc::{calc_pvp_ttk, simulate_rolled, PvpTarget, MAX_SHOTS},
+    Weapon,
+};
+
+///How well the player lands their shots, the default is perfect play
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccuracyModel {
+    ///Chance a shot hits before damage falloff starts
+    pub hit_rate: f64,
+    ///Chance a hit lands on a precision spot
+    pub precision_ratio: f64,
+    ///How far hit rate and precision ratio swing from one fight to the next
+    pub spread: f64,
+    ///Distance to the target in meters
+    pub distance: f64,
+    ///Share of the hit rate lost going from the start to the end of ads falloff
+    pub range_penalty: f64,
+}
+impl Default for AccuracyModel {
+    fn default() -> Self {
+        AccuracyModel {
+            hit_rate: 1.0,
+            precision_ratio: 1.0,
+            spread: 0.0,
+            distance: 0.0,
+            range_penalty: 0.0,
+        }
+    }
+}
+impl AccuracyModel {
+    ///Hit rate at the models distance
+    pub fn hit_chance(&self, _range: &RangeResponse) -> f64 {
+        let start = _range.ads_falloff_start;
+        let end = _range.ads_falloff_end;
+        let past_start = if self.distance <= start {
+            0.0
+        } else if end > start {
+            ((self.distance - start) / (end - start)).min(1.0)
+        } else {
+            1.0
+        };
+        (self.hit_rate * (1.0 - self.range_penalty * past_start)).clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MonteCarloSettings {
+    ///Fights simulated, every one rolls its own accuracy within the spread
+    pub trials: u32,
+    ///Same seed gives the same results
+    pub seed: u64,
+}
+impl Default for MonteCarloSettings {
+    fn default() -> Self {
+        MonteCarloSettings {
+            trials: 100,
+            seed: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Percentiles {
+    pub mean: f64,
+    pub p10: f64,
+    pub p50: f64,
+    pub p90: f64,
+}
+impl Percentiles {
+    pub fn from_samples(_samples: &[f64]) -> Self {
+        if _samples.is_empty() {
+            return Percentiles::default();
+        }
+        let mut sorted = _samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let at = |percentile: f64| {
+            let index = ((sorted.len() - 1) as f64 * percentile).round() as usize;
+            sorted[index]
+        };
+        Percentiles {
+            mean: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            p10: at(0.1),
+            p50: at(0.5),
+            p90: at(0.9),
+        }
+    }
+    pub fn scale(&mut self, _mult: f64) {
+        self.mean *= _mult;
+        self.p10 *= _mult;
+        self.p50 *= _mult;
+        self.p90 *= _mult;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShotOutcome {
+    Miss,
+    Body,
+    Precision,
+}
+
+///Splitmix64, small enough to keep results the same on every platform
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: u64,
+}
+impl SeededRng {
+    pub fn new(_seed: u64) -> Self {
+        SeededRng { state: _seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    ///Uniform in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+///Rolls every shot of a single simulated fight
+#[derive(Debug, Clone)]
+pub struct ShotRoller {
+    hit_chance: f64,
+    precision_ratio: f64,
+    rng: SeededRng,
+}
+impl ShotRoller {
+    ///Draws the accuracy of one fight from within the models spread
+    pub fn new(_model: &AccuracyModel, _hit_chance: f64, _rng: &mut SeededRng) -> Self {
+        let mut swing = |value: f64| {
+            let offset = (_rng.next_f64() * 2.0 - 1.0) * _model.spread;
+            (value + offset).clamp(0.0, 1.0)
+        };
+        let hit_chance = swing(_hit_chance);
+        let precision_ratio = swing(_model.precision_ratio);
+        ShotRoller {
+            hit_chance,
+            precision_ratio,
+            rng: SeededRng::new(_rng.next_u64()),
+        }
+    }
+
+    pub fn roll(&mut self) -> ShotOutcome {
+        if self.rng.next_f64() >= self.hit_chance {
+            ShotOutcome::Miss
+        } else if self.rng.next_f64() < self.precision_ratio {
+            ShotOutcome::Precision
+        } else {
+            ShotOutcome::Body
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AccuracyDpsResponse {
+    ///Dps over the whole reserve when every shot is a precision hit
+    pub perfect_dps: f64,
+    pub dps: Percentiles,
+    ///Hit rate at the models distance before the spread
+    pub hit_chance: f64,
+}
+impl AccuracyDpsResponse {
+    pub fn apply_rpl(&mut self, _rpl: f64) {
+        self.perfect_dps *= _rpl;
+        self.dps.scale(_rpl);
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AccuracyTtkResponse {
+    ///Fastest kill of `calc_pvp_ttk` where every shot lands
+    pub perfect_ttk: Seconds,
+    ///Time taken in the fights that ended in a kill
+    pub ttk: Percentiles,
+    ///Share of fights that ended in a kill
+    pub kill_rate: f64,
+    ///Hit rate at the models distance before the spread
+    pub hit_chance: f64,
+}
+
+fn dps_of(_total_damage: f64, _total_time: f64) -> f64 {
+    if _total_time > 0.0 {
+        _total_damage / _total_time
+    } else {
+        0.0
+    }
+}
+
+///Dps of every simulated fight against the enemy with shots rolled from the model
+pub fn calc_accuracy_dps(
+    _weapon: &Weapon,
+    _enemy: &Enemy,
+    _pl_dmg_mult: f64,
+    _model: &AccuracyModel,
+    _settings: &MonteCarloSettings,
+) -> AccuracyDpsResponse {
+    let hit_chance = _model.hit_chance(&_weapon.calc_range_falloff(None, None, false));
+    let perfect = _weapon.calc_dps(_enemy.clone(), _pl_dmg_mult);
+    let mut rng = SeededRng::new(_settings.seed);
+    let samples: Vec<f64> = (0.._settings.trials)
+        .map(|_| {
+            let mut roller = ShotRoller::new(_model, hit_chance, &mut rng);
+            let response = rolled_dps_calc(_weapon, _enemy, _pl_dmg_mult, &mut roller);
+            dps_of(response.total_damage, response.total_time)
+        })
+        .collect();
+    AccuracyDpsResponse {
+        perfect_dps: dps_of(perfect.total_damage, perfect.total_time),
+        dps: Percentiles::from_samples(&samples),
+        hit_chance,
+    }
+}
+
+///Time to kill the guardian in every simulated fight with shots rolled from the model
+pub fn calc_accuracy_ttk(
+    _weapon: &Weapon,
+    _target: &PvpTarget,
+    _model: &AccuracyModel,
+    _settings: &MonteCarloSettings,
+) -> AccuracyTtkResponse {
+    let hit_chance = _model.hit_chance(&_weapon.calc_range_falloff(None, None, true));
+    let perfect_ttk = calc_pvp_ttk(_weapon, _target)
+        .mixes
+        .iter()
+        .filter(|mix| mix.killed)
+        .map(|mix| mix.time_taken)
+        .min_by(|a, b| a.total_cmp(b))
+        .unwrap_or(0.0);
+    let mut rng = SeededRng::new(_settings.seed);
+    let mut kill_times = Vec::new();
+    for _ in 0.._settings.trials {
+        let mut roller = ShotRoller::new(_model, hit_chance, &mut rng);
+        //misses need room on top of the shots a perfect kill takes
+        let mix = simulate_rolled(_weapon, _target, MAX_SHOTS * 4, &mut roller);
+        if mix.killed {
+            kill_times.push(mix.time_taken);
+        }
+    }
+    AccuracyTtkResponse {
+        perfect_ttk,
+        ttk: Percentiles::from_samples(&kill_times),
+        kill_rate: if _settings.trials > 0 {
+            kill_times.len() as f64 / _settings.trials as f64
+        } else {
+            0.0
+        },
+        hit_chance,
+    }
+}
+
+impl Weapon {
+    pub fn calc_accuracy_dps(
+        &self,
+        _enemy: &Enemy,
+        _pl_dmg_mult: f64,
+        _model: &AccuracyModel,
+        _settings: &MonteCarloSettings,
+    ) -> AccuracyDpsResponse {
+        calc_accuracy_dps(self, _enemy, _pl_dmg_mult, _model, _settings)
+    }
+    pub fn calc_accuracy_ttk(
+        &self,
+        _target: &PvpTarget,
+        _model: &AccuracyModel,
+        _settings: &MonteCarloSettings,
+    ) -> AccuracyTtkResponse {
+        calc_accuracy_ttk(self, _target, _model, _settings)
+    }
+}
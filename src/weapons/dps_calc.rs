@@ -1,20 +1,43 @@
 use std::collections::HashMap;
 use std::{cell::RefCell, rc::Rc};
 
-use super::Weapon;
-use crate::d2_enums::{AmmoType, WeaponType};
-use crate::enemies::Enemy;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    accuracy_calc::{ShotOutcome, ShotRoller},
+    falloff_calc::Engagement,
+    sword_calc::{melee_sim, MeleeSimOptions, SwingType},
+    Weapon,
+};
+use crate::d2_enums::{AmmoType, Seconds, WeaponType};
+use crate::enemies::{champion::ChampionState, Enemy};
 use crate::perks::lib::{
     CalculationInput, ExtraDamageResponse, RefundResponse, ReloadOverrideResponse,
 };
+use crate::perks::perk_state::{PerkStateMachine, PerkTrigger};
+use crate::perks::simulation_state::SimulationState;
 use crate::perks::*;
 use crate::types::rs_types::DpsResponse;
 
 //first entry in tuple is refund to mag, second is too reserves
-pub fn calc_refund(_shots_hit_this_mag: i32, _refunds: Vec<RefundResponse>) -> (i32, i32) {
+//precision only refunds count precision hits and need the current hit to be one
+pub fn calc_refund(
+    _shots_hit_this_mag: i32,
+    _crits_this_mag: i32,
+    _crit: bool,
+    _refunds: Vec<RefundResponse>,
+) -> (i32, i32) {
     let mut refund_ammount = (0, 0);
     for refund in _refunds {
-        if _shots_hit_this_mag % refund.requirement == 0 {
+        let hits = if refund.crit {
+            if !_crit {
+                continue;
+            }
+            _crits_this_mag
+        } else {
+            _shots_hit_this_mag
+        };
+        if hits % refund.requirement == 0 {
             refund_ammount.0 += refund.refund_mag;
             refund_ammount.1 += refund.refund_reserves;
         }
@@ -114,9 +137,346 @@ pub fn calc_extra_dmg(
     }
 }
 
+///Time span of a single magazine within a dps timeline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MagazineSegment {
+    pub start: f64,
+    pub end: f64,
+    ///Reload that follows the magazine, not included in `end`
+    pub reload_time: f64,
+}
+
+///Ammo and perk state the weapon starts a damage phase with
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StartingMagState {
+    ///Rounds in the first magazine, can go over the magazine size for pre-loaded mags
+    pub mag: Option<i32>,
+    ///Ammo outside the first magazine
+    pub reserves: Option<i32>,
+    ///Perk values set before the first shot, used for pre-stacked perks
+    pub perk_values: HashMap<u32, u32>,
+    ///Time between kills of other enemies during the phase, feeds kill triggered perks
+    pub kill_interval: Option<Seconds>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DurationDpsResponse {
+    pub time_damage_data: Vec<(f64, f64)>,
+    pub total_damage: f64,
+    pub total_shots: i32,
+    pub duration: Seconds,
+    ///Damage spread over the whole duration, including time spent empty
+    pub dps: f64,
+    pub mag_left: i32,
+    pub reserves_left: i32,
+}
+
+impl DurationDpsResponse {
+    pub fn apply_rpl(&mut self, rpl: f64) {
+        for (_, damage) in self.time_damage_data.iter_mut() {
+            *damage *= rpl;
+        }
+        self.total_damage *= rpl;
+        self.dps *= rpl;
+    }
+}
+
+///A group of identical enemies killed one after another
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AddClearWave {
+    pub count: u32,
+    ///Time spent moving to the next enemy after a kill
+    pub retarget_time: Seconds,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AddClearResponse {
+    pub dps: DpsResponse,
+    ///When each enemy of the wave died
+    pub kill_times: Vec<Seconds>,
+    ///None when the weapon runs out of ammo before the wave is cleared
+    pub time_to_clear: Option<Seconds>,
+}
+impl AddClearResponse {
+    pub fn apply_rpl(&mut self, rpl: f64) {
+        self.dps.apply_rpl(rpl);
+    }
+}
+
+struct WaveState<'a> {
+    enemy: &'a Enemy,
+    enemies_left: u32,
+    health_left: f64,
+    kill_times: Vec<Seconds>,
+}
+impl<'a> WaveState<'a> {
+    fn new(_enemy: &'a Enemy, _wave: &AddClearWave) -> Self {
+        WaveState {
+            enemy: _enemy,
+            enemies_left: _wave.count,
+            health_left: _enemy.health,
+            kill_times: Vec::new(),
+        }
+    }
+
+    fn cleared(&self) -> bool {
+        self.enemies_left == 0
+    }
+
+    ///Damages the current target, true if it died
+    fn hit(&mut self, _time: Seconds, _damage: f64) -> bool {
+        if self.cleared() {
+            return false;
+        }
+        let resist = self
+            .enemy
+            .get_damage_resistance(self.health_left / self.enemy.health);
+        self.health_left -= _damage * (1.0 - resist);
+        if self.health_left > 0.0 {
+            return false;
+        }
+        //overkill damage is wasted
+        self.kill_times.push(_time);
+        self.enemies_left -= 1;
+        self.health_left = self.enemy.health;
+        true
+    }
+}
+
+//optional parts of a dps sim, the default is a single target at point blank
+//with every shot a precision hit
+#[derive(Default)]
+struct SimOptions<'a> {
+    wave: Option<&'a AddClearWave>,
+    roller: Option<&'a mut ShotRoller>,
+    engagement: Option<&'a Engagement>,
+}
+
+struct DpsSimResult {
+    response: DpsResponse,
+    segments: Vec<MagazineSegment>,
+    mag_left: i32,
+    reserves_left: i32,
+    kill_times: Vec<Seconds>,
+}
+
 pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> DpsResponse {
-    let weapon = Rc::new(_weapon.clone());
-    let stats = weapon.stats.clone();
+    dps_sim(
+        _weapon,
+        _enemy,
+        _pl_dmg_mult,
+        None,
+        &StartingMagState::default(),
+        SimOptions::default(),
+    )
+    .response
+}
+
+///Same as `complex_dps_calc` with impact damage reduced by falloff at the engagement distance
+pub fn calc_dps_at_distance(
+    _weapon: Weapon,
+    _enemy: Enemy,
+    _pl_dmg_mult: f64,
+    _engagement: &Engagement,
+) -> DpsResponse {
+    dps_sim(
+        _weapon,
+        _enemy,
+        _pl_dmg_mult,
+        None,
+        &StartingMagState::default(),
+        SimOptions {
+            engagement: Some(_engagement),
+            ..Default::default()
+        },
+    )
+    .response
+}
+
+///Dps over the whole reserve with every shot rolled, a miss deals no damage
+pub(crate) fn rolled_dps_calc(
+    _weapon: &Weapon,
+    _enemy: &Enemy,
+    _pl_dmg_mult: f64,
+    _roller: &mut ShotRoller,
+) -> DpsResponse {
+    dps_sim(
+        _weapon.clone(),
+        _enemy.clone(),
+        _pl_dmg_mult,
+        None,
+        &StartingMagState::default(),
+        SimOptions {
+            roller: Some(_roller),
+            ..Default::default()
+        },
+    )
+    .response
+}
+
+///Same as `complex_dps_calc` but also returns where every magazine starts and ends
+pub fn complex_dps_calc_segments(
+    _weapon: Weapon,
+    _enemy: Enemy,
+    _pl_dmg_mult: f64,
+) -> (DpsResponse, Vec<MagazineSegment>) {
+    let result = dps_sim(
+        _weapon,
+        _enemy,
+        _pl_dmg_mult,
+        None,
+        &StartingMagState::default(),
+        SimOptions::default(),
+    );
+    (result.response, result.segments)
+}
+
+///Fires the weapon for a fixed length damage phase, hits after the cutoff are dropped
+pub fn calc_dps_for_duration(
+    _weapon: Weapon,
+    _enemy: Enemy,
+    _pl_dmg_mult: f64,
+    _seconds: Seconds,
+    _starting_mag_state: &StartingMagState,
+) -> DurationDpsResponse {
+    let result = dps_sim(
+        _weapon,
+        _enemy,
+        _pl_dmg_mult,
+        Some(_seconds),
+        _starting_mag_state,
+        SimOptions::default(),
+    );
+    let total_damage = result.response.total_damage;
+    DurationDpsResponse {
+        time_damage_data: result.response.time_damage_data,
+        total_damage,
+        total_shots: result.response.total_shots,
+        duration: _seconds,
+        dps: if _seconds > 0.0 {
+            total_damage / _seconds
+        } else {
+            0.0
+        },
+        mag_left: result.mag_left,
+        reserves_left: result.reserves_left,
+    }
+}
+
+///Fires at a wave of enemies until all of them are dead or the weapon runs dry,
+///kills feed kill triggered perks and on kill damage hits the next enemy
+pub fn calc_add_clear(
+    _weapon: Weapon,
+    _enemy: Enemy,
+    _pl_dmg_mult: f64,
+    _wave: &AddClearWave,
+) -> AddClearResponse {
+    let result = dps_sim(
+        _weapon,
+        _enemy,
+        _pl_dmg_mult,
+        None,
+        &StartingMagState::default(),
+        SimOptions {
+            wave: Some(_wave),
+            ..Default::default()
+        },
+    );
+    let time_to_clear = if result.kill_times.len() as u32 >= _wave.count {
+        Some(result.kill_times.last().copied().unwrap_or(0.0))
+    } else {
+        None
+    };
+    AddClearResponse {
+        dps: result.response,
+        kill_times: result.kill_times,
+        time_to_clear,
+    }
+}
+
+fn truncate_response(_response: &mut DpsResponse, _time_limit: Seconds) {
+    _response
+        .time_damage_data
+        .retain(|(time, _)| *time <= _time_limit);
+    _response.total_damage = _response.time_damage_data.iter().map(|(_, dmg)| dmg).sum();
+    _response.total_time = _response.total_time.min(_time_limit);
+}
+
+fn dps_sim(
+    _weapon: Weapon,
+    _enemy: Enemy,
+    _pl_dmg_mult: f64,
+    _time_limit: Option<Seconds>,
+    _start: &StartingMagState,
+    _options: SimOptions,
+) -> DpsSimResult {
+    let SimOptions {
+        wave: _wave,
+        roller: _roller,
+        engagement,
+    } = _options;
+    let mut _weapon = _weapon;
+    for (hash, value) in &_start.perk_values {
+        _weapon.change_perk_val(*hash, *value);
+    }
+    if _weapon.weapon_type == WeaponType::SWORD {
+        //a swords whole supply is its magazine, starting reserves are added on top
+        let starting_ammo = (_start.mag.is_some() || _start.reserves.is_some()).then(|| {
+            let full = _weapon.calc_ammo_sizes(None, None, false).mag_size;
+            _start.mag.unwrap_or(full) + _start.reserves.unwrap_or(0)
+        });
+        let options = MeleeSimOptions {
+            starting_ammo,
+            time_limit: _time_limit,
+            roller: _roller,
+            engagement,
+        };
+        let result = melee_sim(&_weapon, &_enemy, _pl_dmg_mult, SwingType::Heavy, options);
+        let mut response = result.response;
+        let mut mag_left = result.ammo_left;
+        //swords have no perk events, the wave only tracks the kills
+        let mut kill_times = Vec::new();
+        if let Some(wave) = _wave {
+            let mut wave_state = WaveState::new(&_enemy, wave);
+            for (time, dmg) in &response.time_damage_data {
+                wave_state.hit(*time, *dmg);
+            }
+            if let Some(clear_time) = wave_state
+                .kill_times
+                .last()
+                .filter(|_| wave_state.cleared())
+            {
+                //swings after the clear are never made
+                let swings_left = response
+                    .time_damage_data
+                    .iter()
+                    .filter(|(time, _)| time > clear_time)
+                    .count() as i32;
+                mag_left += swings_left * _weapon.melee_formula.ammo_per_heavy;
+                truncate_response(&mut response, *clear_time);
+                response.total_shots -= swings_left;
+            }
+            kill_times = wave_state.kill_times;
+        }
+        if let Some(limit) = _time_limit {
+            truncate_response(&mut response, limit);
+        }
+        let segment = MagazineSegment {
+            start: 0.0,
+            end: response.total_time,
+            reload_time: 0.0,
+        };
+        return DpsSimResult {
+            response,
+            segments: vec![segment],
+            mag_left,
+            reserves_left: 0,
+            kill_times,
+        };
+    }
+    let mut weapon = _weapon.clone();
     let weapon_type = weapon.weapon_type.clone();
     let ammo_type = weapon.ammo_type.clone();
 
@@ -130,7 +490,15 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
     let maximum_shots = if base_mag * 5 < 15 { 15 } else { base_mag * 5 };
 
     let firing_settings = _weapon.firing_data.clone();
-    let perks = weapon.list_perks();
+    let mut perks = weapon.list_perks();
+    let mut perk_states = PerkStateMachine::new(&weapon);
+    perk_states.trigger(PerkTrigger::Swap, 0.0);
+    let kill_interval = _start.kill_interval.filter(|interval| *interval > 0.0);
+    let mut next_kill = kill_interval.unwrap_or(f64::INFINITY);
+    let mut wave = _wave.map(|wave| WaveState::new(&_enemy, wave));
+    let mut roller = _roller;
+    //every enemy of a wave gets the shots a single target would
+    let wave_shot_mult = _wave.map_or(1, |wave| wave.count.max(1) as i32);
 
     let burst_size = firing_settings.burst_size as f64;
     let burst_delay = firing_settings.burst_delay;
@@ -146,7 +514,13 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
     let mut total_shots_hit = 0_i32;
     let mut num_reloads = 0_i32;
 
-    let mut pers_calc_data: HashMap<String, f64> = HashMap::new();
+    let mut pers_calc_data = SimulationState::default();
+    //there is no health here so barriers never go up
+    let champion_mods = weapon.champion_mods();
+    let mut champion = _enemy
+        .champion
+        .map(|profile| ChampionState::new(profile, champion_mods.stuns(profile.champion_type)));
+    let mut segments: Vec<MagazineSegment> = Vec::new();
 
     let mut reserve = weapon
         .calc_ammo_sizes(
@@ -155,18 +529,36 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
             false,
         )
         .reserve_size;
+    match (_start.mag, _start.reserves) {
+        (Some(start_mag), Some(reserves)) => reserve = start_mag + reserves,
+        (None, Some(reserves)) => reserve = base_mag + reserves,
+        _ => (),
+    }
+    let time_limit = _time_limit.unwrap_or(f64::INFINITY);
+    let mut mag = 0;
+    let mut mag_loaded = 0;
+    let mut cut_mid_mag = false;
 
     #[allow(unused_mut)]
-    while reserve > 0 {
+    while reserve > 0 && total_time < time_limit {
+        if wave.as_ref().is_some_and(|wave| wave.cleared()) {
+            break;
+        }
         let mut shots_this_mag = 0;
+        let mut hits_this_mag = 0;
+        let mut crits_this_mag = 0;
         //MAGAZINE/////////////////////
         let mag_calc_input = weapon.sparse_calc_input(total_shots_fired, total_time);
-        let mut mag = weapon
+        mag = weapon
             .calc_ammo_sizes(Some(mag_calc_input), Some(&mut pers_calc_data), false)
             .mag_size;
+        if let (Some(start_mag), 0) = (_start.mag, num_reloads) {
+            mag = start_mag;
+        }
         if mag > reserve {
             mag = reserve
         }
+        mag_loaded = mag;
         ///////////////////////////////
 
         //HANDLING/////////////////////
@@ -176,7 +568,18 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
             weapon.calc_handling_times(Some(handling_calc_input), Some(&mut pers_calc_data), false);
         ///////////////////////////////
         let mut start_time = total_time.clone();
-        while mag > 0 {
+        while mag > 0 && total_time < time_limit {
+            //PERK STATE///////////////////
+            while let Some(interval) = kill_interval.filter(|_| next_kill <= total_time) {
+                perk_states.trigger(PerkTrigger::Kill, next_kill);
+                next_kill += interval;
+            }
+            perk_states.advance(total_time);
+            if perk_states.apply(&mut weapon) {
+                perks = weapon.list_perks();
+            }
+            ///////////////////////////////
+
             //DMG MODIFIERS////////////////
             let before_shot_input_data = CalculationInput {
                 intrinsic_hash: weapon.intrinsic_hash,
@@ -186,7 +589,7 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
                 curr_mag: mag as f64,
                 ammo_type: &ammo_type,
                 weapon_type: &weapon_type,
-                stats: &stats,
+                stats: &weapon.stats,
                 perk_value_map: &weapon.perk_value_map_update(),
                 enemy_type: &_enemy.type_,
                 shots_fired_this_mag: shots_this_mag as f64,
@@ -199,7 +602,11 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
                 handling_data: handling_data,
                 num_reloads: num_reloads as f64,
                 has_overshield: false,
+                player: &weapon.player,
             };
+            //same as the ttk loop, without it only the first shot got the strongest buff
+            //and every later shot of a radiant or well sim did base damage
+            pers_calc_data.reset_buffs();
             let dmg_mods = get_dmg_modifier(
                 perks.clone(),
                 &before_shot_input_data,
@@ -217,12 +624,35 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
             );
             ///////////////////////////////
 
+            //falloff only scales impact damage
+            let falloff_mult = engagement.map_or(1.0, |engagement| {
+                let range = weapon.calc_range_falloff(
+                    Some(before_shot_input_data.clone()),
+                    Some(&mut pers_calc_data),
+                    false,
+                );
+                engagement.damage_mult(&range)
+            });
+
+            //without a roller every shot is a precision hit
+            let outcome = roller
+                .as_deref_mut()
+                .map_or(ShotOutcome::Precision, |roller| roller.roll());
+            let landed = outcome != ShotOutcome::Miss;
+            let impact_mult = if outcome == ShotOutcome::Precision {
+                crit_mult * dmg_mods.crit_scale
+            } else {
+                1.0
+            };
             let dmg = {
-                ((impact_dmg * dmg_mods.impact_dmg_scale) * (crit_mult * dmg_mods.crit_scale)
+                ((impact_dmg * dmg_mods.impact_dmg_scale * falloff_mult) * impact_mult
                     + (explosion_dmg * dmg_mods.explosive_dmg_scale))
                     * _pl_dmg_mult
                     * weapon.damage_mods.get_mod(&_enemy.type_)
                     * weapon.damage_mods.pve
+                    * champion
+                        .as_mut()
+                        .map_or(1.0, |state| state.damage_scale(total_time))
             };
 
             let shot_burst_delay =
@@ -234,13 +664,17 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
             //     total_time += shot_burst_delay*0.5;
             // }
 
+            let hits_before_shot = time_damage_data.len();
             if firing_settings.one_ammo && burst_size > 1.0 {
                 total_shots_fired += 1;
                 shots_this_mag += 1;
-                total_shots_hit += shot_burst_size as i32;
-                total_damage += dmg * shot_burst_size;
-                for i in 0..shot_burst_size as i32 {
-                    time_damage_data.push((total_time + shot_inner_burst_delay * i as f64, dmg));
+                if landed {
+                    total_shots_hit += shot_burst_size as i32;
+                    total_damage += dmg * shot_burst_size;
+                    for i in 0..shot_burst_size as i32 {
+                        time_damage_data
+                            .push((total_time + shot_inner_burst_delay * i as f64, dmg));
+                    }
                 }
                 total_time += inner_burst_delay * (shot_burst_size - 1.0);
             } else {
@@ -251,32 +685,46 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
                 };
                 total_shots_fired += 1;
                 shots_this_mag += 1;
-                total_shots_hit += 1;
-                if inner_burst_delay == 0.0 {
-                    total_damage += dmg * burst_size;
-                    time_damage_data.push((total_time, dmg * burst_size));
-                } else {
-                    total_damage += dmg;
-                    time_damage_data.push((total_time, dmg));
+                if landed {
+                    total_shots_hit += 1;
+                    if inner_burst_delay == 0.0 {
+                        total_damage += dmg * burst_size;
+                        time_damage_data.push((total_time, dmg * burst_size));
+                    } else {
+                        total_damage += dmg;
+                        time_damage_data.push((total_time, dmg));
+                    }
                 }
                 if total_shots_fired > 0 {
                     total_time += spec_delay;
                 }
             }
             mag -= 1;
+            if outcome == ShotOutcome::Precision {
+                perk_states.trigger(PerkTrigger::PrecisionHit, total_time);
+                crits_this_mag += 1;
+            }
 
             //REFUNDS//////////////////////
-            let mut refund_calc_input = weapon.sparse_calc_input(total_shots_fired, total_time);
-            refund_calc_input.shots_fired_this_mag = shots_this_mag as f64;
-            let refunds = get_refund_modifier(
-                perks.clone(),
-                &refund_calc_input,
-                false,
-                &mut pers_calc_data,
-            );
-            let ammo_to_refund = calc_refund(shots_this_mag, refunds);
-            mag += ammo_to_refund.0;
-            reserve += ammo_to_refund.1;
+            if landed {
+                hits_this_mag += 1;
+                let mut refund_calc_input = weapon.sparse_calc_input(total_shots_fired, total_time);
+                refund_calc_input.shots_fired_this_mag = shots_this_mag as f64;
+                let refunds = get_refund_modifier(
+                    perks.clone(),
+                    &refund_calc_input,
+                    false,
+                    &mut pers_calc_data,
+                );
+                let ammo_to_refund = calc_refund(
+                    hits_this_mag,
+                    crits_this_mag,
+                    outcome == ShotOutcome::Precision,
+                    refunds,
+                );
+                mag += ammo_to_refund.0;
+                reserve += ammo_to_refund.1;
+            }
             ///////////////////////////////
 
             //COMPLEX CALC PRECURSOR//////
@@ -288,7 +736,7 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
                 curr_mag: mag as f64,
                 ammo_type: &ammo_type,
                 weapon_type: &weapon_type,
-                stats: &stats,
+                stats: &weapon.stats,
                 perk_value_map: &weapon.perk_value_map_update(),
                 enemy_type: &_enemy.type_,
                 shots_fired_this_mag: shots_this_mag as f64,
@@ -301,29 +749,72 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
                 handling_data: handling_data,
                 num_reloads: num_reloads as f64,
                 has_overshield: false,
+                player: &weapon.player,
             };
             ///////////////////////////////
 
             //EXTRA DMG////////////////////
-            let extra_dmg_responses = get_extra_damage(
-                perks.clone(),
-                &after_shot_input_data,
-                false,
-                &mut pers_calc_data,
-            );
-            let buffs = ExtraDamageBuffInfo {
-                pl_buff: _pl_dmg_mult,
-                impact_buff: dmg_mods.impact_dmg_scale,
-                explosive_buff: dmg_mods.explosive_dmg_scale,
-                pve_buff: weapon.damage_mods.pve,
-                crit_buff: crit_mult * dmg_mods.crit_scale,
-                combatant_buff: weapon.damage_mods.get_mod(&_enemy.type_),
-            };
-            let tmp_out_data = calc_extra_dmg(total_time, extra_dmg_responses, buffs);
-            total_damage += tmp_out_data.extra_dmg;
-            total_time += tmp_out_data.extra_time;
-            total_shots_hit += tmp_out_data.extra_hits;
-            time_damage_data.extend(tmp_out_data.extra_time_dmg);
+            if landed {
+                let extra_dmg_responses = get_extra_damage(
+                    perks.clone(),
+                    &after_shot_input_data,
+                    false,
+                    &mut pers_calc_data,
+                );
+                let buffs = ExtraDamageBuffInfo {
+                    pl_buff: _pl_dmg_mult,
+                    impact_buff: dmg_mods.impact_dmg_scale,
+                    explosive_buff: dmg_mods.explosive_dmg_scale,
+                    pve_buff: weapon.damage_mods.pve,
+                    crit_buff: crit_mult * dmg_mods.crit_scale,
+                    combatant_buff: weapon.damage_mods.get_mod(&_enemy.type_),
+                };
+                let tmp_out_data = calc_extra_dmg(total_time, extra_dmg_responses, buffs);
+                total_damage += tmp_out_data.extra_dmg;
+                total_time += tmp_out_data.extra_time;
+                total_shots_hit += tmp_out_data.extra_hits;
+                time_damage_data.extend(tmp_out_data.extra_time_dmg);
+            }
+            ///////////////////////////////
+
+            //WAVE/////////////////////////
+            if let Some(wave) = wave.as_mut() {
+                //kills from on kill damage don't set off more on kill damage
+                let shot_hits_end = time_damage_data.len();
+                let mut hit = hits_before_shot;
+                while hit < time_damage_data.len() && !wave.cleared() {
+                    let (hit_time, hit_dmg) = time_damage_data[hit];
+                    hit += 1;
+                    if !wave.hit(hit_time, hit_dmg) {
+                        continue;
+                    }
+                    perk_states.trigger(PerkTrigger::Kill, hit_time);
+                    //the last kill leaves nothing for on kill damage to hit
+                    if wave.cleared() || hit > shot_hits_end {
+                        continue;
+                    }
+                    total_time += _wave.map_or(0.0, |wave| wave.retarget_time);
+                    //on kill damage lands on the next enemy
+                    let kill_dmg_responses = get_kill_extra_damage(
+                        perks.clone(),
+                        &after_shot_input_data,
+                        false,
+                        &mut pers_calc_data,
+                    );
+                    let kill_buffs = ExtraDamageBuffInfo {
+                        pl_buff: _pl_dmg_mult,
+                        impact_buff: dmg_mods.impact_dmg_scale,
+                        explosive_buff: dmg_mods.explosive_dmg_scale,
+                        pve_buff: weapon.damage_mods.pve,
+                        crit_buff: crit_mult * dmg_mods.crit_scale,
+                        combatant_buff: weapon.damage_mods.get_mod(&_enemy.type_),
+                    };
+                    let kill_dmg = calc_extra_dmg(hit_time, kill_dmg_responses, kill_buffs);
+                    total_damage += kill_dmg.extra_dmg;
+                    total_shots_hit += kill_dmg.extra_hits;
+                    time_damage_data.extend(kill_dmg.extra_time_dmg);
+                }
+            }
             ///////////////////////////////
 
             //RELOAD OVERRIDE//////////////
@@ -363,22 +854,24 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
             //     }
             // }
             if mag != 0 {
-                if weapon.weapon_type == WeaponType::FUSIONRIFLE {
-                    total_time += shot_burst_delay * 0.45
-                } else if weapon.weapon_type == WeaponType::LINEARFUSIONRIFLE {
-                    total_time += shot_burst_delay * 0.95
-                }
+                total_time += shot_burst_delay * (weapon.charge_delay_scale() - 1.0);
             }
             ///////////////////////////////
-            if weapon.ammo_type == AmmoType::PRIMARY {
-                if total_shots_fired > maximum_shots {
-                    reserve = 0;
-                    break;
-                }
-            } else {
-                if total_shots_fired > base_mag * 8 + 20 {
-                    reserve = 0;
-                    break;
+            if wave.as_ref().is_some_and(|wave| wave.cleared()) {
+                break;
+            }
+            //a time limit already bounds the sim
+            if _time_limit.is_none() {
+                if weapon.ammo_type == AmmoType::PRIMARY {
+                    if total_shots_fired > maximum_shots * wave_shot_mult {
+                        reserve = 0;
+                        break;
+                    }
+                } else {
+                    if total_shots_fired > (base_mag * 8 + 20) * wave_shot_mult {
+                        reserve = 0;
+                        break;
+                    }
                 }
             }
             if reserve <= 0 {
@@ -386,8 +879,17 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
             }
         }
 
-        reserve -= base_mag;
         dps_per_mag.push(total_damage / total_time);
+        if total_time >= time_limit || wave.as_ref().is_some_and(|wave| wave.cleared()) {
+            cut_mid_mag = true;
+            break;
+        }
+        reserve -= if num_reloads == 0 && _start.mag.is_some() {
+            mag_loaded
+        } else {
+            base_mag
+        };
+        let mag_end = total_time;
 
         //RELOAD///////////////////////
         let reload_input_data = CalculationInput {
@@ -398,7 +900,7 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
             curr_mag: mag as f64,
             ammo_type: &ammo_type,
             weapon_type: &weapon_type,
-            stats: &stats,
+            stats: &weapon.stats,
             perk_value_map: &weapon.perk_value_map_update(),
             enemy_type: &_enemy.type_,
             shots_fired_this_mag: shots_this_mag as f64,
@@ -411,20 +913,48 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
             handling_data,
             num_reloads: num_reloads as f64,
             has_overshield: false,
+            player: &weapon.player,
         };
         let reload_responses =
             weapon.calc_reload_time(Some(reload_input_data), Some(&mut pers_calc_data), false);
         total_time += reload_responses.reload_time;
+        perk_states.trigger(PerkTrigger::Reload, total_time);
+        segments.push(MagazineSegment {
+            start: start_time,
+            end: mag_end,
+            reload_time: reload_responses.reload_time,
+        });
         ///////////////////////////////
         num_reloads += 1;
     }
     //sort time_damage_data by time
     time_damage_data.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
-    DpsResponse {
+    let mut response = DpsResponse {
         dps_per_mag,
         time_damage_data,
         total_damage,
         total_time,
         total_shots: total_shots_fired,
+    };
+    if let Some(limit) = _time_limit {
+        truncate_response(&mut response, limit);
+    }
+    let kill_times = wave.map_or_else(Vec::new, |wave| wave.kill_times);
+    //cut off mid magazine the loaded rounds are still part of reserve,
+    //cut off during a reload the next magazine is ready
+    let (mag_left, reserves_left) = if reserve <= 0 {
+        (0, 0)
+    } else if cut_mid_mag {
+        (mag.max(0), (reserve - mag_loaded).max(0))
+    } else {
+        let next_mag = base_mag.min(reserve);
+        (next_mag, reserve - next_mag)
+    };
+    DpsSimResult {
+        response,
+        segments,
+        mag_left,
+        reserves_left,
+        kill_times,
     }
 }
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{d2_enums::Seconds, types::rs_types::RangeResponse};
+
+use super::{
+    pvp_ttk_calc::{calc_pvp_ttk_at_distance, PvpShotMix, PvpTarget},
+    Weapon,
+};
+
+pub(crate) const CURVE_MAX_DISTANCE: u32 = 80;
+
+///Where the target is and how the player is aiming at it
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Engagement {
+    ///Distance to the target in meters
+    pub distance: f64,
+    ///Uses the ads falloff instead of the hipfire one
+    pub ads: bool,
+}
+impl Default for Engagement {
+    fn default() -> Self {
+        Engagement {
+            distance: 0.0,
+            ads: true,
+        }
+    }
+}
+impl Engagement {
+    ///Impact damage multiplier at this distance, full damage until falloff starts
+    ///then linearly down to the floor where it ends
+    pub fn damage_mult(&self, _range: &RangeResponse) -> f64 {
+        let (start, end) = if self.ads {
+            (_range.ads_falloff_start, _range.ads_falloff_end)
+        } else {
+            (_range.hip_falloff_start, _range.hip_falloff_end)
+        };
+        //weapons without falloff have both ends at 0
+        if end <= 0.0 || self.distance <= start {
+            1.0
+        } else if self.distance >= end {
+            _range.floor_percent
+        } else {
+            let past_start = (self.distance - start) / (end - start);
+            1.0 - (1.0 - _range.floor_percent) * past_start
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct TtkDistancePoint {
+    pub distance: f64,
+    ///Shots of the fastest mix, 0 if nothing kills
+    pub optimal_shots: i32,
+    pub optimal_ttk: Option<Seconds>,
+    ///Shots when every hit is a bodyshot, 0 if it doesn't kill
+    pub body_shots: i32,
+    pub body_ttk: Option<Seconds>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TtkCurveResponse {
+    ///One point every meter from 0 to 80
+    pub points: Vec<TtkDistancePoint>,
+    ///Distances where the optimal shots to kill goes up
+    pub breakpoints: Vec<f64>,
+}
+
+fn shots_and_time(_mix: Option<&PvpShotMix>) -> (i32, Option<Seconds>) {
+    match _mix.filter(|mix| mix.killed) {
+        Some(mix) => (mix.headshots + mix.bodyshots, Some(mix.time_taken)),
+        None => (0, None),
+    }
+}
+
+///Pvp time to kill every meter from point blank to 80m
+pub fn calc_ttk_curve(_weapon: &Weapon, _target: &PvpTarget, _ads: bool) -> TtkCurveResponse {
+    let mut response = TtkCurveResponse::default();
+    for meter in 0..=CURVE_MAX_DISTANCE {
+        let engagement = Engagement {
+            distance: meter as f64,
+            ads: _ads,
+        };
+        let mixes = calc_pvp_ttk_at_distance(_weapon, _target, &engagement).mixes;
+        let optimal = mixes
+            .iter()
+            .filter(|mix| mix.killed)
+            .min_by(|a, b| a.time_taken.total_cmp(&b.time_taken));
+        let body = mixes.iter().find(|mix| mix.headshots == 0);
+        let (optimal_shots, optimal_ttk) = shots_and_time(optimal);
+        let (body_shots, body_ttk) = shots_and_time(body);
+        let lost_breakpoint = response.points.last().is_some_and(|last| {
+            last.optimal_shots > 0 && (optimal_shots > last.optimal_shots || optimal_shots == 0)
+        });
+        if lost_breakpoint {
+            response.breakpoints.push(engagement.distance);
+        }
+        response.points.push(TtkDistancePoint {
+            distance: engagement.distance,
+            optimal_shots,
+            optimal_ttk,
+            body_shots,
+            body_ttk,
+        });
+    }
+    response
+}
+
+impl Weapon {
+    pub fn calc_ttk_curve(&self, _target: &PvpTarget, _ads: bool) -> TtkCurveResponse {
+        calc_ttk_curve(self, _target, _ads)
+    }
+}
@@ -1,6 +1,14 @@
+pub mod accuracy_calc;
 pub mod dps_calc;
+pub mod falloff_calc;
+pub mod optimizer;
+pub mod patch_calc;
+pub mod pve_ttk_calc;
+pub mod pvp_ttk_calc;
 pub mod reserve_calc;
+pub mod rotation_calc;
 pub mod stat_calc;
+pub mod sword_calc;
 pub mod ttk_calc;
 pub mod weapon_constructor;
 
@@ -8,17 +16,26 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::d2_enums::{AmmoType, DamageType, StatHashes, WeaponType};
+use crate::activity::PlayerState;
+use crate::d2_enums::{AmmoType, DamageType, Seconds, StatHashes, WeaponType};
 use crate::enemies::Enemy;
 use crate::perks::{
-    get_magazine_modifier, get_reserve_modifier, get_stat_bumps, lib::CalculationInput, Perk,
+    get_champion_mods, get_magazine_modifier, get_reserve_modifier, get_stat_bumps,
+    lib::{CalculationInput, ChampionModResponse},
+    simulation_state::SimulationState,
+    Perk,
 };
 
 use crate::types::rs_types::{
-    AmmoFormula, DamageMods, DpsResponse, FiringData, HandlingFormula, RangeFormula, ReloadFormula,
+    AmmoFormula, DamageMods, DpsResponse, FiringData, HandlingFormula, MeleeFormula, RangeFormula,
+    ReloadFormula,
 };
 
-use self::dps_calc::complex_dps_calc;
+use self::dps_calc::{
+    calc_add_clear, calc_dps_at_distance, calc_dps_for_duration, complex_dps_calc,
+    AddClearResponse, AddClearWave, DurationDpsResponse, StartingMagState,
+};
+use self::falloff_calc::Engagement;
 
 #[derive(Debug, Clone)]
 pub struct PsuedoWeapon {}
@@ -70,10 +87,15 @@ pub struct Weapon {
     pub ammo_formula: AmmoFormula,
     pub handling_formula: HandlingFormula,
     pub reload_formula: ReloadFormula,
+    pub melee_formula: MeleeFormula,
 
     pub weapon_type: WeaponType,
     pub damage_type: DamageType,
     pub ammo_type: AmmoType,
+
+    ///Build of the player using the weapon, kept here so every calculation input can see it
+    #[serde(skip)]
+    pub player: PlayerState,
 }
 impl Weapon {
     pub fn add_perk(&mut self, _perk: Perk) {
@@ -87,11 +109,17 @@ impl Weapon {
     pub fn list_perk_ids(&self) -> Vec<u32> {
         self.perks.keys().cloned().collect()
     }
+    ///Perks on the weapon followed by the players buffs that aren't already on it
     pub fn list_perks(&self) -> Vec<Perk> {
         let mut perk_list: Vec<Perk> = Vec::new();
         for (_key, perk) in &self.perks {
             perk_list.push(perk.clone());
         }
+        for buff in self.player.buff_perks() {
+            if !self.perks.contains_key(&buff.hash) {
+                perk_list.push(buff);
+            }
+        }
         perk_list
     }
     pub fn perk_value_map_update(&self) -> HashMap<u32, u32> {
@@ -108,6 +136,10 @@ impl Weapon {
         }
         self.update_stats();
     }
+    pub fn set_player_state(&mut self, _state: PlayerState) {
+        self.player = _state;
+        self.update_stats();
+    }
     pub fn get_stats(&mut self) -> HashMap<u32, Stat> {
         self.update_stats();
         self.stats.clone()
@@ -126,6 +158,7 @@ impl Weapon {
         self.ammo_formula = AmmoFormula::default();
         self.handling_formula = HandlingFormula::default();
         self.reload_formula = ReloadFormula::default();
+        self.melee_formula = MeleeFormula::default();
     }
 
     pub fn static_calc_input(&self) -> CalculationInput {
@@ -138,6 +171,7 @@ impl Weapon {
             &self.ammo_type,
             &self.damage_type,
             self.firing_data.crit_mult,
+            &self.player,
         )
     }
 
@@ -155,6 +189,7 @@ impl Weapon {
             self.calc_ammo_sizes(None, None, false).mag_size,
             _total_shots_fired,
             _total_time,
+            &self.player,
         )
     }
     pub fn pvp_calc_input(
@@ -177,6 +212,7 @@ impl Weapon {
             base_mag,
             _overshield,
             self.calc_handling_times(None, None, true),
+            &self.player,
         );
         tmp.time_this_mag = _total_time;
         tmp.time_total = _total_time;
@@ -185,6 +221,10 @@ impl Weapon {
         tmp.total_shots_hit = _total_shots_hit;
         tmp
     }
+    ///Champion mods granted by the weapons perks
+    pub fn champion_mods(&self) -> ChampionModResponse {
+        get_champion_mods(self.list_perks(), &self.static_calc_input(), false)
+    }
     pub fn update_stats(&mut self) {
         self.perk_value_map = self.perk_value_map_update();
         let input = CalculationInput::construct_static(
@@ -196,8 +236,14 @@ impl Weapon {
             &self.ammo_type,
             &self.damage_type,
             self.firing_data.crit_mult,
+            &self.player,
+        );
+        let inter_var = get_stat_bumps(
+            self.list_perks(),
+            input,
+            false,
+            &mut SimulationState::default(),
         );
-        let inter_var = get_stat_bumps(self.list_perks(), input, false, &mut HashMap::new());
         let dynamic_stats = &inter_var[0];
         let static_stats = &inter_var[1];
         for (key, stat) in &mut self.stats {
@@ -214,6 +260,37 @@ impl Weapon {
     pub fn calc_dps(&self, _enemy: Enemy, _pl_dmg_mult: f64) -> DpsResponse {
         complex_dps_calc(self.clone(), _enemy, _pl_dmg_mult)
     }
+    pub fn calc_dps_for_duration(
+        &self,
+        _enemy: Enemy,
+        _pl_dmg_mult: f64,
+        _seconds: Seconds,
+        _starting_mag_state: &StartingMagState,
+    ) -> DurationDpsResponse {
+        calc_dps_for_duration(
+            self.clone(),
+            _enemy,
+            _pl_dmg_mult,
+            _seconds,
+            _starting_mag_state,
+        )
+    }
+    pub fn calc_add_clear(
+        &self,
+        _enemy: Enemy,
+        _pl_dmg_mult: f64,
+        _wave: &AddClearWave,
+    ) -> AddClearResponse {
+        calc_add_clear(self.clone(), _enemy, _pl_dmg_mult, _wave)
+    }
+    pub fn calc_dps_at_distance(
+        &self,
+        _enemy: Enemy,
+        _pl_dmg_mult: f64,
+        _engagement: &Engagement,
+    ) -> DpsResponse {
+        calc_dps_at_distance(self.clone(), _enemy, _pl_dmg_mult, _engagement)
+    }
 }
 impl Default for Weapon {
     fn default() -> Weapon {
@@ -232,10 +309,13 @@ impl Default for Weapon {
             ammo_formula: AmmoFormula::default(),
             handling_formula: HandlingFormula::default(),
             reload_formula: ReloadFormula::default(),
+            melee_formula: MeleeFormula::default(),
 
             weapon_type: WeaponType::UNKNOWN,
             damage_type: DamageType::UNKNOWN,
             ammo_type: AmmoType::UNKNOWN,
+
+            player: PlayerState::default(),
         }
     }
 }
@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    build_spec::PerkSpec,
+    enemies::Enemy,
+    perks::{enhanced_check, Perk},
+};
+
+use super::{Stat, Weapon};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Objective {
+    ///Average dps over the whole reserve from `complex_dps_calc`
+    SustainedDps,
+    ///Optimal ttk at the given resilience from `calc_ttk`, lower is better
+    OptimalTtk { resilience: i32, overshield: f64 },
+    AdsFalloff { pvp: bool },
+    Weighted(Vec<WeightedObjective>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeightedObjective {
+    pub objective: Objective,
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OptimizerInput {
+    ///Candidate perks for every column, a combination takes one perk from each column
+    pub columns: Vec<Vec<PerkSpec>>,
+    ///Stat bonuses of each masterwork option, no masterwork is used if empty
+    #[serde(default)]
+    pub masterworks: Vec<BTreeMap<u32, i32>>,
+    pub objective: Objective,
+    #[serde(default = "default_top_n")]
+    pub top_n: usize,
+}
+
+fn default_top_n() -> usize {
+    10
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RollScore {
+    pub perks: Vec<PerkSpec>,
+    pub masterwork: BTreeMap<u32, i32>,
+    ///Weighted sum of every objective normalized against all combinations, higher is better
+    pub score: f64,
+    ///Raw value of every objective in the order they were given
+    pub values: Vec<f64>,
+}
+
+impl Objective {
+    fn flatten(&self, _weight: f64, _out: &mut Vec<(Objective, f64)>) {
+        match self {
+            Objective::Weighted(objectives) => {
+                for entry in objectives {
+                    entry.objective.flatten(_weight * entry.weight, _out);
+                }
+            }
+            _ => _out.push((self.clone(), _weight)),
+        }
+    }
+
+    fn higher_is_better(&self) -> bool {
+        !matches!(self, Objective::OptimalTtk { .. })
+    }
+
+    fn evaluate(&self, _weapon: &Weapon, _enemy: &Enemy, _pl_dmg_mult: f64) -> f64 {
+        match self {
+            Objective::SustainedDps => {
+                let dps = _weapon.calc_dps(_enemy.clone(), _pl_dmg_mult);
+                if dps.total_time > 0.0 {
+                    dps.total_damage / dps.total_time
+                } else {
+                    0.0
+                }
+            }
+            Objective::OptimalTtk {
+                resilience,
+                overshield,
+            } => _weapon
+                .calc_ttk(*overshield)
+                .into_iter()
+                .find(|summary| summary.value == *resilience)
+                .map_or(f64::INFINITY, |summary| summary.optimal_ttk.time_taken),
+            Objective::AdsFalloff { pvp } => {
+                _weapon
+                    .calc_range_falloff(Some(_weapon.static_calc_input()), None, *pvp)
+                    .ads_falloff_start
+            }
+            Objective::Weighted(_) => unreachable!("weighted objectives are flattened"),
+        }
+    }
+}
+
+fn to_perk(_spec: &PerkSpec) -> Perk {
+    let data = enhanced_check(_spec.hash);
+    Perk {
+        stat_buffs: _spec.stat_buffs.clone().into_iter().collect(),
+        enhanced: _spec.enhanced || data.1,
+        value: _spec.value,
+        hash: data.0,
+        raw_hash: _spec.hash,
+    }
+}
+
+///Scores every perk and masterwork combination on top of `_weapon` and returns the best ones.
+///Perks already on the weapon are kept, so buffs and intrinsics can be set up ahead of time.
+pub fn optimize_rolls(
+    _weapon: &Weapon,
+    _enemy: &Enemy,
+    _pl_dmg_mult: f64,
+    _input: &OptimizerInput,
+) -> Vec<RollScore> {
+    let mut objectives = Vec::new();
+    _input.objective.flatten(1.0, &mut objectives);
+
+    let columns: Vec<Vec<(PerkSpec, Perk)>> = _input
+        .columns
+        .iter()
+        .filter(|column| !column.is_empty())
+        .map(|column| column.iter().map(|spec| (spec.clone(), to_perk(spec))).collect())
+        .collect();
+    let no_masterwork = [BTreeMap::new()];
+    let masterworks = if _input.masterworks.is_empty() {
+        &no_masterwork[..]
+    } else {
+        &_input.masterworks[..]
+    };
+
+    let mut results = Vec::new();
+    let mut indices = vec![0; columns.len()];
+    loop {
+        for masterwork in masterworks {
+            let mut weapon = _weapon.clone();
+            for (column, index) in columns.iter().zip(&indices) {
+                let perk = column[*index].1.clone();
+                weapon.perks.insert(perk.hash, perk);
+            }
+            for (hash, value) in masterwork {
+                weapon.stats.entry(*hash).or_insert_with(Stat::new).base_value += value;
+            }
+            weapon.update_stats();
+
+            results.push(RollScore {
+                perks: columns
+                    .iter()
+                    .zip(&indices)
+                    .map(|(column, index)| column[*index].0.clone())
+                    .collect(),
+                masterwork: masterwork.clone(),
+                score: 0.0,
+                values: objectives
+                    .iter()
+                    .map(|(objective, _)| objective.evaluate(&weapon, _enemy, _pl_dmg_mult))
+                    .collect(),
+            });
+        }
+
+        //advance to the next combination, last column first
+        let mut column = columns.len();
+        loop {
+            if column == 0 {
+                score_results(&mut results, &objectives);
+                results.truncate(_input.top_n);
+                return results;
+            }
+            column -= 1;
+            indices[column] += 1;
+            if indices[column] < columns[column].len() {
+                break;
+            }
+            indices[column] = 0;
+        }
+    }
+}
+
+fn score_results(_results: &mut [RollScore], _objectives: &[(Objective, f64)]) {
+    for (i, (objective, weight)) in _objectives.iter().enumerate() {
+        let finite = _results.iter().map(|r| r.values[i]).filter(|v| v.is_finite());
+        let min = finite.clone().fold(f64::INFINITY, f64::min);
+        let max = finite.fold(f64::NEG_INFINITY, f64::max);
+        for result in _results.iter_mut() {
+            let value = result.values[i];
+            let mut normalized = if !value.is_finite() {
+                0.0
+            } else if max > min {
+                (value - min) / (max - min)
+            } else {
+                1.0
+            };
+            if !objective.higher_is_better() && value.is_finite() && max > min {
+                normalized = 1.0 - normalized;
+            }
+            result.score += normalized * weight;
+        }
+    }
+    _results.sort_by(|a, b| b.score.total_cmp(&a.score));
+}
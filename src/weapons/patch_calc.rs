@@ -0,0 +1,142 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::formula_database::PatchHistory;
+
+use super::Weapon;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum PatchDiffCategory {
+    Range,
+    Handling,
+    Reload,
+    Ammo,
+    Firing,
+    PveScalars,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValueChange {
+    pub category: PatchDiffCategory,
+    ///Field of the matching response, e.g. `ads_falloff_start` or `boss`
+    pub field: String,
+    pub old: f64,
+    pub new: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PatchDiff {
+    pub weapon_hash: u32,
+    pub old_patch: String,
+    pub new_patch: String,
+    ///Only values that differ between the two patches
+    pub changes: Vec<ValueChange>,
+}
+impl PatchDiff {
+    pub fn changed(&self, _category: PatchDiffCategory) -> bool {
+        self.changes
+            .iter()
+            .any(|change| change.category == _category)
+    }
+}
+
+///The weapon evaluated under a single patch, perk effects included
+fn patch_values(_weapon: &Weapon, _pvp: bool) -> Vec<(PatchDiffCategory, Value)> {
+    let input = || Some(_weapon.static_calc_input());
+    vec![
+        (
+            PatchDiffCategory::Range,
+            to_value(_weapon.calc_range_falloff(input(), None, _pvp)),
+        ),
+        (
+            PatchDiffCategory::Handling,
+            to_value(_weapon.calc_handling_times(input(), None, _pvp)),
+        ),
+        (
+            PatchDiffCategory::Reload,
+            to_value(_weapon.calc_reload_time(input(), None, _pvp)),
+        ),
+        (
+            PatchDiffCategory::Ammo,
+            to_value(_weapon.calc_ammo_sizes(input(), None, _pvp)),
+        ),
+        (
+            PatchDiffCategory::Firing,
+            to_value(_weapon.calc_firing_data(input(), None, _pvp)),
+        ),
+        (
+            PatchDiffCategory::PveScalars,
+            to_value(&_weapon.damage_mods),
+        ),
+    ]
+}
+
+fn to_value<T: Serialize>(_response: T) -> Value {
+    serde_json::to_value(_response).unwrap_or_default()
+}
+
+//timestamps only say when a formula was first seen, they aren't balance values
+fn numeric_fields(_val: &Value) -> Vec<(String, f64)> {
+    let mut fields = Vec::new();
+    if let Some(map) = _val.as_object() {
+        for (key, val) in map {
+            if key == "timestamp" {
+                continue;
+            }
+            let num = match val {
+                Value::Number(num) => num.as_f64(),
+                Value::Bool(flag) => Some(*flag as u8 as f64),
+                _ => None,
+            };
+            if let Some(num) = num {
+                fields.push((key.clone(), num));
+            }
+        }
+    }
+    fields
+}
+
+///Rebuilds the weapon under both patches and reports every range, handling, reload,
+///ammo, firing and pve scalar value that changed
+pub fn diff_patches(
+    _weapon: &Weapon,
+    _history: &PatchHistory,
+    _old_patch: &str,
+    _new_patch: &str,
+    _pvp: bool,
+) -> Result<PatchDiff, String> {
+    let old_weapon = _weapon
+        .rebuild(_history.get_patch(_old_patch)?)
+        .map_err(|err| format!("{} in patch {}", err, _old_patch))?;
+    let new_weapon = _weapon
+        .rebuild(_history.get_patch(_new_patch)?)
+        .map_err(|err| format!("{} in patch {}", err, _new_patch))?;
+
+    let mut changes = Vec::new();
+    for ((category, old), (_, new)) in patch_values(&old_weapon, _pvp)
+        .into_iter()
+        .zip(patch_values(&new_weapon, _pvp))
+    {
+        let new_fields = numeric_fields(&new);
+        for (field, old_val) in numeric_fields(&old) {
+            let new_val = new_fields
+                .iter()
+                .find(|(new_field, _)| *new_field == field)
+                .map_or(old_val, |(_, val)| *val);
+            if (new_val - old_val).abs() > 1e-9 {
+                changes.push(ValueChange {
+                    category,
+                    field,
+                    old: old_val,
+                    new: new_val,
+                });
+            }
+        }
+    }
+    Ok(PatchDiff {
+        weapon_hash: _weapon.hash,
+        old_patch: _old_patch.to_string(),
+        new_patch: _new_patch.to_string(),
+        changes,
+    })
+}
@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{
+    activity::Activity,
+    d2_enums::{Seconds, WeaponType},
+    enemies::{champion::ChampionState, Enemy},
+    perks::{
+        get_dmg_modifier, get_firing_modifier,
+        perk_state::{PerkStateMachine, PerkTrigger},
+        simulation_state::SimulationState,
+    },
+};
+
+use super::{
+    sword_calc::{melee_dps_calc, SwingType},
+    Weapon,
+};
+
+///Share of hits landing on a precision spot for each reported kill
+const PRECISION_MIXES: [f64; 5] = [1.0, 0.75, 0.5, 0.25, 0.0];
+const MAX_HITS: i32 = 10000;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PveKillData {
+    pub precision_ratio: f64,
+    pub precision_shots: i32,
+    pub body_shots: i32,
+    ///Ammo taken from the magazine, one per burst for one ammo weapons
+    pub ammo_used: i32,
+    pub reloads: i32,
+    pub time_taken: Seconds,
+    ///False when the weapon runs out of ammo first
+    pub killed: bool,
+    ///When each resist phase of the enemy started, in the order the enemy lists them
+    pub phase_times: Vec<Option<Seconds>>,
+    ///Times a champion was stunned
+    pub stuns: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PveTtkResponse {
+    pub enemy: String,
+    ///Health as shown in game
+    pub health: f64,
+    ///In game health after power delta and base resistance
+    pub adjusted_health: f64,
+    pub kills: Vec<PveKillData>,
+    ///Kill with light swings only, for glaives and swords
+    pub melee: Option<PveKillData>,
+}
+
+fn mark_phases(_enemy: &Enemy, _health_left: f64, _time: Seconds, _kill: &mut PveKillData) {
+    let fraction = _health_left / _enemy.health;
+    for (phase, phase_time) in _enemy
+        .resist_phases
+        .iter()
+        .zip(_kill.phase_times.iter_mut())
+    {
+        if phase_time.is_none() && fraction <= phase.health_fraction {
+            *phase_time = Some(_time);
+        }
+    }
+}
+
+fn champion_state(_weapon: &Weapon, _enemy: &Enemy) -> Option<ChampionState> {
+    let champion_mods = _weapon.champion_mods();
+    _enemy
+        .champion
+        .map(|profile| ChampionState::new(profile, champion_mods.stuns(profile.champion_type)))
+}
+
+//resist phases and champion state are applied on top of the damage passed in
+fn apply_hit(
+    _enemy: &Enemy,
+    _champion: &mut Option<ChampionState>,
+    _health_left: &mut f64,
+    _time: Seconds,
+    _damage: f64,
+) {
+    let mut damage = _damage;
+    if let Some(champion) = _champion {
+        *_health_left = (*_health_left + champion.regen(_time, _enemy.health)).min(_enemy.health);
+        damage *= champion.damage_scale(_time);
+    }
+    damage *= 1.0 - _enemy.get_damage_resistance(*_health_left / _enemy.health);
+    if let Some(champion) = _champion {
+        damage = champion.absorb(_time, damage, *_health_left / _enemy.health);
+    }
+    *_health_left -= damage;
+}
+
+fn simulate_kill(
+    _weapon: &Weapon,
+    _enemy: &Enemy,
+    _pl_dmg_mult: f64,
+    _precision_ratio: f64,
+) -> PveKillData {
+    let mut kill = PveKillData {
+        precision_ratio: _precision_ratio,
+        phase_times: vec![None; _enemy.resist_phases.len()],
+        ..Default::default()
+    };
+    let mut pers_calc_data = SimulationState::default();
+    let mut champion = champion_state(_weapon, _enemy);
+    let mut weapon = _weapon.clone();
+    let mut perks = weapon.list_perks();
+    let mut perk_states = PerkStateMachine::new(&weapon);
+    perk_states.trigger(PerkTrigger::Swap, 0.0);
+    let firing_data = &_weapon.firing_data;
+
+    let (impact_dmg, explosion_dmg, crit_mult, _) = _weapon.get_damage_profile();
+    let combatant_mult = weapon.damage_mods.get_mod(&_enemy.type_) * weapon.damage_mods.pve;
+    //pellets of a shot land together
+    let pellets = if firing_data.inner_burst_delay == 0.0 {
+        firing_data.burst_size as f64
+    } else {
+        1.0
+    };
+
+    let mut reserve = weapon
+        .calc_ammo_sizes(
+            Some(weapon.static_calc_input()),
+            Some(&mut pers_calc_data),
+            false,
+        )
+        .reserve_size;
+    let mut mag = weapon
+        .calc_ammo_sizes(None, None, false)
+        .mag_size
+        .min(reserve);
+    reserve -= mag;
+
+    let mut health_left = _enemy.health;
+    let mut time = 0.0;
+    let mut mag_start = 0.0;
+    let mut shots_this_mag = 0;
+    let mut hits = 0;
+    let mut burst_hits = 0.0;
+    let mut reloaded = false;
+    while health_left > 0.0 && hits < MAX_HITS {
+        perk_states.advance(time);
+        if perk_states.apply(&mut weapon) {
+            perks = weapon.list_perks();
+        }
+        let mut calc_input = weapon.sparse_calc_input(kill.ammo_used, time);
+        calc_input.enemy_type = &_enemy.type_;
+        calc_input.curr_mag = mag as f64;
+        calc_input.shots_fired_this_mag = shots_this_mag as f64;
+        calc_input.total_shots_hit = hits as f64;
+        calc_input.reserves_left = reserve as f64;
+        calc_input.time_this_mag = time - mag_start;
+        calc_input.num_reloads = kill.reloads as f64;
+        //every shot gets the strongest buff, not only the first one
+        pers_calc_data.reset_buffs();
+        let dmg_mods = get_dmg_modifier(perks.clone(), &calc_input, false, &mut pers_calc_data);
+        let firing_mods =
+            get_firing_modifier(perks.clone(), &calc_input, false, &mut pers_calc_data);
+
+        let burst_size = if pellets > 1.0 {
+            1.0
+        } else {
+            firing_data.burst_size as f64 + firing_mods.burst_size_add
+        };
+        let new_burst = burst_hits == 0.0 || burst_hits >= burst_size;
+        if new_burst || !firing_data.one_ammo {
+            if mag == 0 {
+                if reserve <= 0 {
+                    break;
+                }
+                time += weapon
+                    .calc_reload_time(Some(calc_input.clone()), Some(&mut pers_calc_data), false)
+                    .reload_time;
+                mag = weapon
+                    .calc_ammo_sizes(Some(calc_input), Some(&mut pers_calc_data), false)
+                    .mag_size
+                    .min(reserve);
+                reserve -= mag;
+                kill.reloads += 1;
+                perk_states.trigger(PerkTrigger::Reload, time);
+                mag_start = time;
+                shots_this_mag = 0;
+                burst_hits = 0.0;
+                reloaded = true;
+                continue;
+            }
+            mag -= 1;
+            shots_this_mag += 1;
+            kill.ammo_used += 1;
+        }
+
+        if hits > 0 && !reloaded {
+            time += if new_burst {
+                (firing_data.burst_delay + firing_mods.burst_delay_add)
+                    * firing_mods.burst_delay_scale
+                    * weapon.charge_delay_scale()
+            } else {
+                firing_data.inner_burst_delay * firing_mods.inner_burst_scale
+            };
+        }
+        reloaded = false;
+        burst_hits = if new_burst { 1.0 } else { burst_hits + 1.0 };
+
+        //spreads precision hits evenly through the kill
+        let precision = (f64::from(hits + 1) * _precision_ratio).floor()
+            > (f64::from(hits) * _precision_ratio).floor();
+        let body_damage =
+            impact_dmg * dmg_mods.impact_dmg_scale + explosion_dmg * dmg_mods.explosive_dmg_scale;
+        let damage = if precision {
+            kill.precision_shots += 1;
+            perk_states.trigger(PerkTrigger::PrecisionHit, time);
+            impact_dmg * dmg_mods.impact_dmg_scale * crit_mult * dmg_mods.crit_scale
+                + explosion_dmg * dmg_mods.explosive_dmg_scale
+        } else {
+            kill.body_shots += 1;
+            body_damage
+        };
+        apply_hit(
+            _enemy,
+            &mut champion,
+            &mut health_left,
+            time,
+            damage * pellets * _pl_dmg_mult * combatant_mult,
+        );
+        hits += 1;
+        mark_phases(_enemy, health_left, time, &mut kill);
+        if champion.as_ref().is_some_and(|state| state.is_immune()) {
+            break;
+        }
+    }
+    kill.stuns = champion.map_or(0, |state| state.stuns);
+    kill.time_taken = time;
+    kill.killed = health_left <= 0.0;
+    kill
+}
+
+//melee can't crit so only a single kill is reported for each swing
+fn simulate_melee_kill(
+    _weapon: &Weapon,
+    _enemy: &Enemy,
+    _pl_dmg_mult: f64,
+    _swing: SwingType,
+) -> PveKillData {
+    let mut kill = PveKillData {
+        phase_times: vec![None; _enemy.resist_phases.len()],
+        ..Default::default()
+    };
+    let mut champion = champion_state(_weapon, _enemy);
+    let mut health_left = _enemy.health;
+    let ammo_cost = _weapon.calc_swing_profile(_swing).ammo_cost;
+    let swings = melee_dps_calc(_weapon, _enemy, _pl_dmg_mult, _swing, None).time_damage_data;
+    for (time, damage) in swings {
+        apply_hit(_enemy, &mut champion, &mut health_left, time, damage);
+        kill.body_shots += 1;
+        kill.ammo_used += ammo_cost;
+        kill.time_taken = time;
+        mark_phases(_enemy, health_left, time, &mut kill);
+        if health_left <= 0.0 {
+            kill.killed = true;
+            break;
+        }
+        if champion.as_ref().is_some_and(|state| state.is_immune()) {
+            break;
+        }
+    }
+    kill.stuns = champion.map_or(0, |state| state.stuns);
+    kill
+}
+
+///Shots and time needed to kill the enemy for a range of precision and body shot mixes,
+///perks are evaluated every hit and the enemies resist phases apply as its health drops.
+///Champions the weapon has no mod for can't be stunned and a barrier stops the kill.
+pub fn calc_pve_ttk(_weapon: &Weapon, _enemy: &Enemy, _activity: &Activity) -> PveTtkResponse {
+    let pl_dmg_mult = _activity.get_pl_delta();
+    let kills = if _weapon.weapon_type == WeaponType::SWORD {
+        vec![simulate_melee_kill(
+            _weapon,
+            _enemy,
+            pl_dmg_mult,
+            SwingType::Heavy,
+        )]
+    } else {
+        PRECISION_MIXES
+            .iter()
+            .map(|ratio| simulate_kill(_weapon, _enemy, pl_dmg_mult, *ratio))
+            .collect()
+    };
+    let melee = matches!(_weapon.weapon_type, WeaponType::GLAIVE | WeaponType::SWORD)
+        .then(|| simulate_melee_kill(_weapon, _enemy, pl_dmg_mult, SwingType::Light));
+    let rpl_mult = _activity.get_rpl_mult();
+    PveTtkResponse {
+        enemy: _enemy.name.clone(),
+        health: _enemy.health * rpl_mult,
+        adjusted_health: _enemy.get_adjusted_health(_activity) * rpl_mult,
+        kills,
+        melee,
+    }
+}
+
+impl Weapon {
+    pub fn calc_pve_ttk(&self, _enemy: &Enemy, _activity: &Activity) -> PveTtkResponse {
+        calc_pve_ttk(self, _enemy, _activity)
+    }
+}
@@ -0,0 +1,466 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    activity::PlayerState,
+    d2_enums::{Seconds, WeaponType},
+    perks::{
+        get_damage_resist_modifier, get_dmg_modifier, get_firing_modifier,
+        perk_state::{PerkStateMachine, PerkTrigger},
+        simulation_state::SimulationState,
+        Perk, Perks,
+    },
+};
+
+use super::{
+    accuracy_calc::{ShotOutcome, ShotRoller},
+    falloff_calc::Engagement,
+    sword_calc::SwingType,
+    ttk_calc::RESILIENCE_VALUES,
+    Weapon,
+};
+
+pub(crate) const MAX_SHOTS: i32 = 50;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OvershieldType {
+    #[default]
+    NONE,
+    ///Lasts until it is broken
+    PERSISTENT,
+    ///Loses strength over time whether it is shot or not
+    DECAYING,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PvpHealing {
+    #[default]
+    NONE,
+    RESTORATION,
+    ///Restoration x2
+    AMPLIFIED,
+    DEVOUR,
+}
+impl PvpHealing {
+    ///Health restored every second, unlike regeneration taking damage doesn't stop it
+    pub fn heal_per_second(&self) -> f64 {
+        match self {
+            PvpHealing::NONE => 0.0,
+            PvpHealing::RESTORATION => 10.0,
+            PvpHealing::AMPLIFIED => 16.0,
+            PvpHealing::DEVOUR => 20.0,
+        }
+    }
+}
+
+///A guardian being shot at, values are approximations from pvp testing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PvpTarget {
+    ///Resilience tier from 0 to 10
+    pub resilience: usize,
+    pub overshield_type: OvershieldType,
+    ///Overshield health, ignored when the type is NONE, a void overshield is a PERSISTENT one
+    pub overshield: f64,
+    ///Overshield health lost every second for a DECAYING overshield
+    pub overshield_decay: f64,
+    pub healing: PvpHealing,
+    ///Recovery tier from 0 to 10
+    pub recovery: usize,
+    ///Time without taking damage before health starts regenerating
+    pub recovery_delay: Seconds,
+    ///Frost armor stacks from 0 to 5
+    pub frost_armor: u32,
+    ///Cuts bodyshot damage, precision damage goes through
+    pub woven_mail: bool,
+    ///Chest mod that cuts damage from rockets and grenade launchers
+    pub concussive_dampener: bool,
+}
+impl Default for PvpTarget {
+    fn default() -> Self {
+        PvpTarget {
+            resilience: 0,
+            overshield_type: OvershieldType::NONE,
+            overshield: 0.0,
+            overshield_decay: 0.0,
+            healing: PvpHealing::NONE,
+            recovery: 0,
+            recovery_delay: 4.0,
+            frost_armor: 0,
+            woven_mail: false,
+            concussive_dampener: false,
+        }
+    }
+}
+impl PvpTarget {
+    ///A guardian with the same resilience and recovery as the player, for mirror matchups
+    pub fn from_player(_player: &PlayerState) -> Self {
+        PvpTarget {
+            resilience: _player.resilience_tier(),
+            recovery: _player.recovery_tier(),
+            ..Default::default()
+        }
+    }
+    pub fn health(&self) -> f64 {
+        RESILIENCE_VALUES[self.resilience.min(10)]
+    }
+    ///Resists on the target as perks for `get_damage_resist_modifier`
+    pub fn resist_perks(&self) -> Vec<Perk> {
+        let resist = |perk: Perks, value: u32| Perk {
+            stat_buffs: HashMap::new(),
+            enhanced: false,
+            value,
+            hash: perk as u32,
+            raw_hash: perk as u32,
+        };
+        let mut perks = Vec::new();
+        if self.frost_armor > 0 {
+            perks.push(resist(Perks::FrostArmor, self.frost_armor));
+        }
+        if self.woven_mail {
+            perks.push(resist(Perks::WovenMail, 1));
+        }
+        if self.concussive_dampener {
+            perks.push(resist(Perks::ConcussiveDampener, 1));
+        }
+        perks
+    }
+    pub fn overshield_health(&self) -> f64 {
+        if self.overshield_type == OvershieldType::NONE {
+            0.0
+        } else {
+            self.overshield
+        }
+    }
+    ///Health regained every second once regeneration has started
+    pub fn regen_per_second(&self) -> f64 {
+        let time_to_full = 9.0 - 0.35 * self.recovery.min(10) as f64;
+        self.health() / time_to_full
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PvpShotMix {
+    pub headshots: i32,
+    pub bodyshots: i32,
+    pub time_taken: Seconds,
+    pub reloads: i32,
+    ///False if healing outpaced the weapon
+    pub killed: bool,
+    ///True if the target started regenerating during a reload, undoing part of the kill
+    pub regen_reset: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PvpTtkResponse {
+    ///Health plus overshield at the start of the fight
+    pub health: f64,
+    ///Shots to kill for every mix, from all headshots to all bodyshots, empty for swords
+    pub mixes: Vec<PvpShotMix>,
+    ///Kill with melee swings only, heavy swings for swords and the melee for glaives
+    pub melee: Option<PvpShotMix>,
+}
+
+//bodyshots are fired first so the precision hits land on a weakened target
+fn simulate_mix(
+    _weapon: &Weapon,
+    _target: &PvpTarget,
+    _bodyshots: i32,
+    _engagement: Option<&Engagement>,
+) -> PvpShotMix {
+    simulate_shots(_weapon, _target, MAX_SHOTS, _engagement, |landed| {
+        if landed >= _bodyshots {
+            ShotOutcome::Precision
+        } else {
+            ShotOutcome::Body
+        }
+    })
+}
+
+///Fight where every shot is rolled, misses still use ammo and time
+pub(crate) fn simulate_rolled(
+    _weapon: &Weapon,
+    _target: &PvpTarget,
+    _max_shots: i32,
+    _roller: &mut ShotRoller,
+) -> PvpShotMix {
+    simulate_shots(_weapon, _target, _max_shots, None, |_| _roller.roll())
+}
+
+//the outcome of a shot is picked from the hits landed so far
+fn simulate_shots(
+    _weapon: &Weapon,
+    _target: &PvpTarget,
+    _max_shots: i32,
+    _engagement: Option<&Engagement>,
+    mut _outcome: impl FnMut(i32) -> ShotOutcome,
+) -> PvpShotMix {
+    let mut mix = PvpShotMix::default();
+    let mut persistent_data = SimulationState::default();
+    let mut weapon = _weapon.clone();
+    let mut perks = weapon.list_perks();
+    let mut perk_states = PerkStateMachine::new(&weapon);
+    perk_states.trigger(PerkTrigger::Swap, 0.0);
+    let firing_data = &_weapon.firing_data;
+
+    let (impact_dmg, explosion_dmg, mut crit_mult, _) = _weapon.get_damage_profile();
+    if _weapon.weapon_type == WeaponType::SHOTGUN && firing_data.burst_size == 12 {
+        crit_mult = 1.0; // shawty has no crits
+    }
+
+    let resist_perks = _target.resist_perks();
+    let max_health = _target.health();
+    let mut health = max_health;
+    let mut shield = _target.overshield_health();
+    let mut mag = weapon.calc_ammo_sizes(None, None, true).mag_size;
+    let mut time = 0.0;
+    let mut last_hit = 0.0;
+    let mut last_update = 0.0;
+    let mut bullets = 0;
+    let mut hits = 0;
+    let mut shots_fired = 0;
+    let mut burst_hits = 0.0;
+    let mut reloaded = false;
+    while bullets < _max_shots {
+        perk_states.advance(time);
+        if perk_states.apply(&mut weapon) {
+            perks = weapon.list_perks();
+        }
+        persistent_data.target_health = (health + shield) / 70.0;
+        persistent_data.reset_buffs();
+        let calc_input = weapon.pvp_calc_input(shots_fired as f64, hits as f64, time, shield > 0.0);
+        let dmg_mods = get_dmg_modifier(perks.clone(), &calc_input, true, &mut persistent_data);
+        let firing_mods =
+            get_firing_modifier(perks.clone(), &calc_input, true, &mut persistent_data);
+        let resist_mods = get_damage_resist_modifier(
+            resist_perks.clone(),
+            &calc_input,
+            true,
+            &mut persistent_data,
+        );
+
+        //falloff only scales impact damage
+        let falloff_mult = _engagement.map_or(1.0, |engagement| {
+            let range = weapon.calc_range_falloff(
+                Some(calc_input.clone()),
+                Some(&mut persistent_data),
+                true,
+            );
+            engagement.damage_mult(&range)
+        });
+
+        let burst_size = firing_data.burst_size as f64 + firing_mods.burst_size_add;
+        let new_burst = burst_hits == 0.0 || burst_hits >= burst_size;
+        if new_burst || !firing_data.one_ammo {
+            if mag <= 0 {
+                time += weapon
+                    .calc_reload_time(Some(calc_input.clone()), Some(&mut persistent_data), true)
+                    .reload_time;
+                mag = weapon
+                    .calc_ammo_sizes(Some(calc_input), Some(&mut persistent_data), true)
+                    .mag_size;
+                mix.reloads += 1;
+                perk_states.trigger(PerkTrigger::Reload, time);
+                burst_hits = 0.0;
+                reloaded = true;
+                continue;
+            }
+            mag -= 1;
+            shots_fired += 1;
+        }
+
+        if bullets > 0 && !reloaded {
+            time += if new_burst {
+                (firing_data.burst_delay + firing_mods.burst_delay_add)
+                    * firing_mods.burst_delay_scale
+                    * _weapon.charge_delay_scale()
+            } else {
+                firing_data.inner_burst_delay * firing_mods.inner_burst_scale
+            };
+        }
+        burst_hits = if new_burst { 1.0 } else { burst_hits + 1.0 };
+
+        //target recovers between hits
+        if bullets > 0 {
+            let gap = time - last_update;
+            let regen_start = last_update.max(last_hit + _target.recovery_delay);
+            let regen = (time - regen_start).max(0.0) * _target.regen_per_second();
+            if regen > 0.0 && reloaded && health < max_health {
+                mix.regen_reset = true;
+            }
+            health = (health + regen + gap * _target.healing.heal_per_second()).min(max_health);
+            if _target.overshield_type == OvershieldType::DECAYING {
+                shield = (shield - gap * _target.overshield_decay).max(0.0);
+            }
+        }
+        reloaded = false;
+        last_update = time;
+        bullets += 1;
+
+        let body_damage = (impact_dmg * dmg_mods.impact_dmg_scale * falloff_mult
+            + explosion_dmg * dmg_mods.explosive_dmg_scale)
+            * resist_mods.body_shot_resist;
+        let damage = match _outcome(hits) {
+            ShotOutcome::Miss => continue,
+            ShotOutcome::Precision => {
+                mix.headshots += 1;
+                perk_states.trigger(PerkTrigger::PrecisionHit, time);
+                impact_dmg
+                    * dmg_mods.impact_dmg_scale
+                    * falloff_mult
+                    * crit_mult
+                    * dmg_mods.crit_scale
+                    * resist_mods.head_shot_resist
+                    + explosion_dmg * dmg_mods.explosive_dmg_scale * resist_mods.body_shot_resist
+            }
+            ShotOutcome::Body => {
+                mix.bodyshots += 1;
+                body_damage
+            }
+        };
+        let absorbed = damage.min(shield);
+        shield -= absorbed;
+        health -= damage - absorbed;
+        hits += 1;
+        last_hit = time;
+        if health <= 0.0 {
+            mix.killed = true;
+            break;
+        }
+    }
+    mix.time_taken = time;
+    mix
+}
+
+///Swings until the target dies or the ammo the swing costs runs out,
+///swings come faster than the recovery delay so only healing applies between them
+pub(crate) fn simulate_melee(
+    _weapon: &Weapon,
+    _target: &PvpTarget,
+    _swing: SwingType,
+) -> PvpShotMix {
+    let mut mix = PvpShotMix::default();
+    let mut persistent_data = SimulationState::default();
+    let mut weapon = _weapon.clone();
+    let mut perks = weapon.list_perks();
+    let mut perk_states = PerkStateMachine::new(&weapon);
+    perk_states.trigger(PerkTrigger::Swap, 0.0);
+    let swing = weapon.calc_swing_profile(_swing);
+
+    let resist_perks = _target.resist_perks();
+    let max_health = _target.health();
+    let mut health = max_health;
+    let mut shield = _target.overshield_health();
+    let mut ammo = weapon.calc_ammo_sizes(None, None, true).mag_size;
+    let mut time = 0.0;
+    let mut swings = 0;
+    while swing.damage > 0.0 && ammo >= swing.ammo_cost && swings < MAX_SHOTS {
+        perk_states.advance(time);
+        if perk_states.apply(&mut weapon) {
+            perks = weapon.list_perks();
+        }
+        persistent_data.target_health = (health + shield) / 70.0;
+        persistent_data.reset_buffs();
+        let calc_input = weapon.pvp_calc_input(swings as f64, swings as f64, time, shield > 0.0);
+        let dmg_mods = get_dmg_modifier(perks.clone(), &calc_input, true, &mut persistent_data);
+        let firing_mods =
+            get_firing_modifier(perks.clone(), &calc_input, true, &mut persistent_data);
+        let resist_mods = get_damage_resist_modifier(
+            resist_perks.clone(),
+            &calc_input,
+            true,
+            &mut persistent_data,
+        );
+
+        let damage = swing.damage * dmg_mods.impact_dmg_scale * resist_mods.body_shot_resist;
+        let absorbed = damage.min(shield);
+        shield -= absorbed;
+        health -= damage - absorbed;
+        ammo -= swing.ammo_cost;
+        swings += 1;
+        mix.bodyshots += 1;
+        if health <= 0.0 {
+            mix.killed = true;
+            break;
+        }
+
+        let gap = swing.swing_time * firing_mods.burst_delay_scale;
+        time += gap;
+        health = (health + gap * _target.healing.heal_per_second()).min(max_health);
+        if _target.overshield_type == OvershieldType::DECAYING {
+            shield = (shield - gap * _target.overshield_decay).max(0.0);
+        }
+    }
+    mix.time_taken = time;
+    mix
+}
+
+///Shots and time needed to kill the target for every mix of headshots and bodyshots,
+///the target regenerates and heals between hits
+pub fn calc_pvp_ttk(_weapon: &Weapon, _target: &PvpTarget) -> PvpTtkResponse {
+    pvp_ttk(_weapon, _target, None)
+}
+
+///Same as `calc_pvp_ttk` with impact damage reduced by falloff at the engagement distance
+pub fn calc_pvp_ttk_at_distance(
+    _weapon: &Weapon,
+    _target: &PvpTarget,
+    _engagement: &Engagement,
+) -> PvpTtkResponse {
+    pvp_ttk(_weapon, _target, Some(_engagement))
+}
+
+fn pvp_ttk(
+    _weapon: &Weapon,
+    _target: &PvpTarget,
+    _engagement: Option<&Engagement>,
+) -> PvpTtkResponse {
+    let mut mixes = Vec::new();
+    let melee = match _weapon.weapon_type {
+        WeaponType::SWORD => Some(simulate_melee(_weapon, _target, SwingType::Heavy)),
+        WeaponType::GLAIVE => Some(simulate_melee(_weapon, _target, SwingType::Light)),
+        _ => None,
+    };
+    //without crits every mix is the same so only the all body mix is reported
+    let (_, _, crit_mult, _) = _weapon.get_damage_profile();
+    let no_crits = crit_mult <= 1.0
+        || (_weapon.weapon_type == WeaponType::SHOTGUN && _weapon.firing_data.burst_size == 12);
+    let first = if no_crits { MAX_SHOTS } else { 0 };
+    //swords have no projectile to mix
+    let last = if _weapon.weapon_type == WeaponType::SWORD {
+        -1
+    } else {
+        MAX_SHOTS
+    };
+    for bodyshots in first..=last {
+        let mix = simulate_mix(_weapon, _target, bodyshots, _engagement);
+        let done = mix.headshots == 0 || !mix.killed;
+        mixes.push(mix);
+        if done {
+            break;
+        }
+    }
+    PvpTtkResponse {
+        health: _target.health() + _target.overshield_health(),
+        mixes,
+        melee,
+    }
+}
+
+impl Weapon {
+    pub fn calc_pvp_ttk(&self, _target: &PvpTarget) -> PvpTtkResponse {
+        calc_pvp_ttk(self, _target)
+    }
+    ///Time to kill a guardian wearing the same armor stats as the player
+    pub fn calc_mirror_ttk(&self) -> PvpTtkResponse {
+        calc_pvp_ttk(self, &PvpTarget::from_player(&self.player))
+    }
+    pub fn calc_pvp_ttk_at_distance(
+        &self,
+        _target: &PvpTarget,
+        _engagement: &Engagement,
+    ) -> PvpTtkResponse {
+        calc_pvp_ttk_at_distance(self, _target, _engagement)
+    }
+}
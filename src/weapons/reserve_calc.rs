@@ -19,6 +19,7 @@ enum ReserveIDs {
     SniperRifles,
     Glaive,
     TraceRifles,
+    Swords,
 }
 impl From<u32> for ReserveIDs {
     fn from(id: u32) -> Self {
@@ -42,6 +43,7 @@ impl From<u32> for ReserveIDs {
             121 => ReserveIDs::SniperRifles,
             331 => ReserveIDs::Glaive,
             251 => ReserveIDs::TraceRifles,
+            181 => ReserveIDs::Swords,
             _ => ReserveIDs::Primary,
         }
     }
@@ -61,6 +63,8 @@ pub fn calc_reserves(_mag_size: f64, _mag_stat: i32, _inv_stat: i32, _id: u32, _
         ReserveIDs::ForeRunner => forerunner(_mag_size, _mag_stat, _inv_stat),
         ReserveIDs::ErianasVow => eriana_vow(_mag_size, _mag_stat, _inv_stat),
         ReserveIDs::RocketLaunchers => rockets(_mag_size, _mag_stat, _inv_stat),
+        //swords hold all of their ammo at once
+        ReserveIDs::Swords => _mag_size.ceil(),
 
         //placeholders
         ReserveIDs::LeviathansBreath => 8.0,
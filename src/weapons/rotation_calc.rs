@@ -0,0 +1,252 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{d2_enums::Seconds, enemies::Enemy, perks::Perks};
+
+use super::{dps_calc::complex_dps_calc_segments, Weapon};
+
+pub const MAX_LOADOUT_WEAPONS: usize = 3;
+//auto-loading holster reloads a weapon once it has been stowed this long
+const AUTO_LOAD_DELAY: Seconds = 3.0;
+
+#[derive(Debug, Clone, Default)]
+pub struct Loadout {
+    weapons: Vec<Weapon>,
+}
+impl Loadout {
+    pub fn new(_weapons: Vec<Weapon>) -> Result<Loadout, String> {
+        if _weapons.is_empty() || _weapons.len() > MAX_LOADOUT_WEAPONS {
+            return Err(format!(
+                "A loadout needs between 1 and {} weapons, got {}",
+                MAX_LOADOUT_WEAPONS,
+                _weapons.len()
+            ));
+        }
+        Ok(Loadout { weapons: _weapons })
+    }
+
+    pub fn weapons(&self) -> &[Weapon] {
+        &self.weapons
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FiringPlan {
+    ///Always fires the magazine with the most damage over the time it takes to get it out
+    Greedy,
+    ///Loadout indices, each entry fires one magazine.
+    ///Empty weapons are skipped and greedy takes over once the script runs out
+    Scripted(Vec<usize>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct RotationHit {
+    pub time: Seconds,
+    pub damage: f64,
+    ///Index of the weapon in the loadout
+    pub weapon: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct WeaponContribution {
+    pub hash: u32,
+    pub total_damage: f64,
+    pub hits: i32,
+    pub magazines: i32,
+    ///Time spent firing, swaps and reloads are not included
+    pub time_active: Seconds,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RotationResponse {
+    ///Hits of every weapon sorted by time
+    pub timeline: Vec<RotationHit>,
+    pub total_damage: f64,
+    pub total_time: Seconds,
+    pub weapons: Vec<WeaponContribution>,
+    pub swaps: i32,
+}
+
+struct MagazineTape {
+    damage: f64,
+    duration: Seconds,
+    reload_time: Seconds,
+    ///Hit times are relative to the first shot of the magazine
+    hits: Vec<(Seconds, f64)>,
+}
+
+struct WeaponState {
+    mags: Vec<MagazineTape>,
+    next_mag: usize,
+    loaded: bool,
+    stowed_at: Option<Seconds>,
+    ready_time: Seconds,
+    stow_time: Seconds,
+    auto_loading: bool,
+}
+impl WeaponState {
+    fn new(_weapon: &Weapon, _enemy: &Enemy, _pl_dmg_mult: f64) -> WeaponState {
+        let (dps, segments) =
+            complex_dps_calc_segments(_weapon.clone(), _enemy.clone(), _pl_dmg_mult);
+        let mut mags: Vec<MagazineTape> = segments
+            .iter()
+            .map(|segment| MagazineTape {
+                damage: 0.0,
+                duration: segment.end - segment.start,
+                reload_time: segment.reload_time,
+                hits: Vec::new(),
+            })
+            .collect();
+        for (time, damage) in dps.time_damage_data {
+            let index = segments
+                .iter()
+                .rposition(|segment| segment.start <= time)
+                .unwrap_or(0);
+            if let Some(mag) = mags.get_mut(index) {
+                mag.damage += damage;
+                mag.hits
+                    .push(((time - segments[index].start).max(0.0), damage));
+            }
+        }
+
+        //hot swap is triggered by the swap itself
+        let mut swap_weapon = _weapon.clone();
+        swap_weapon.change_perk_val(Perks::HotSwap as u32, 1);
+        let ready_time = swap_weapon
+            .calc_handling_times(Some(swap_weapon.static_calc_input()), None, false)
+            .ready_time;
+        let stow_time = _weapon
+            .calc_handling_times(Some(_weapon.static_calc_input()), None, false)
+            .stow_time;
+
+        WeaponState {
+            mags,
+            next_mag: 0,
+            loaded: true,
+            stowed_at: None,
+            ready_time,
+            stow_time,
+            auto_loading: _weapon
+                .perks
+                .contains_key(&(Perks::AutoLoadingHolster as u32)),
+        }
+    }
+
+    fn has_ammo(&self) -> bool {
+        self.next_mag < self.mags.len()
+    }
+
+    ///Time before the next magazine can be fired, swaps are not included
+    fn reload_cost(&self, _now: Seconds) -> Seconds {
+        let auto_loaded = self.auto_loading
+            && self
+                .stowed_at
+                .is_some_and(|stowed| _now - stowed >= AUTO_LOAD_DELAY);
+        if self.loaded || auto_loaded || self.next_mag == 0 {
+            0.0
+        } else {
+            self.mags[self.next_mag - 1].reload_time
+        }
+    }
+}
+
+fn swap_cost(_states: &[WeaponState], _current: Option<usize>, _next: usize) -> Seconds {
+    match _current {
+        Some(current) if current != _next => _states[current].stow_time + _states[_next].ready_time,
+        _ => 0.0,
+    }
+}
+
+fn greedy_pick(_states: &[WeaponState], _current: Option<usize>, _now: Seconds) -> Option<usize> {
+    _states
+        .iter()
+        .enumerate()
+        .filter(|(_, state)| state.has_ammo())
+        .map(|(index, state)| {
+            let swap = swap_cost(_states, _current, index);
+            let mag = &state.mags[state.next_mag];
+            let time = swap + state.reload_cost(_now + swap) + mag.duration;
+            (index, mag.damage / time.max(f64::EPSILON))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(index, _)| index)
+}
+
+///Fires the loadout one magazine at a time until the phase ends or every weapon is empty.
+///Stowed weapons keep an empty magazine unless they have auto-loading holster
+pub fn simulate_rotation(
+    _loadout: &Loadout,
+    _enemy: &Enemy,
+    _pl_dmg_mult: f64,
+    _plan: &FiringPlan,
+    _duration: Seconds,
+) -> RotationResponse {
+    let mut states: Vec<WeaponState> = _loadout
+        .weapons
+        .iter()
+        .map(|weapon| WeaponState::new(weapon, _enemy, _pl_dmg_mult))
+        .collect();
+    let mut response = RotationResponse {
+        weapons: _loadout
+            .weapons
+            .iter()
+            .map(|weapon| WeaponContribution {
+                hash: weapon.hash,
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    };
+    let mut script = match _plan {
+        FiringPlan::Greedy => Vec::new(),
+        FiringPlan::Scripted(order) => order.clone(),
+    }
+    .into_iter();
+
+    let mut now = 0.0_f64;
+    let mut current: Option<usize> = None;
+    while now < _duration {
+        let scripted = script
+            .by_ref()
+            .find(|index| states.get(*index).is_some_and(|state| state.has_ammo()));
+        let next = match scripted.or_else(|| greedy_pick(&states, current, now)) {
+            Some(next) => next,
+            None => break,
+        };
+
+        if let Some(prev) = current.filter(|prev| *prev != next) {
+            states[prev].stowed_at = Some(now);
+            now += swap_cost(&states, current, next);
+            response.swaps += 1;
+        }
+        now += states[next].reload_cost(now);
+
+        let state = &mut states[next];
+        let mag = &state.mags[state.next_mag];
+        let contribution = &mut response.weapons[next];
+        for (time, damage) in &mag.hits {
+            if now + time > _duration {
+                continue;
+            }
+            response.timeline.push(RotationHit {
+                time: now + time,
+                damage: *damage,
+                weapon: next,
+            });
+            contribution.total_damage += damage;
+            contribution.hits += 1;
+        }
+        contribution.magazines += 1;
+        contribution.time_active += mag.duration.min((_duration - now).max(0.0));
+        now += mag.duration;
+
+        state.next_mag += 1;
+        state.loaded = false;
+        state.stowed_at = None;
+        current = Some(next);
+    }
+
+    response.timeline.sort_by(|a, b| a.time.total_cmp(&b.time));
+    response.total_damage = response.weapons.iter().map(|w| w.total_damage).sum();
+    response.total_time = now.min(_duration);
+    response
+}
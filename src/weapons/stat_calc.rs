@@ -12,6 +12,7 @@ use crate::{
             HandlingModifierResponse, InventoryModifierResponse, MagazineModifierResponse,
             RangeModifierResponse, ReloadModifierResponse,
         },
+        simulation_state::SimulationState,
         Perks,
     },
     types::rs_types::{
@@ -35,7 +36,7 @@ impl Weapon {
     pub fn calc_reload_time(
         &self,
         _calc_input: Option<CalculationInput>,
-        _cached_data: Option<&mut HashMap<String, f64>>,
+        _sim_state: Option<&mut SimulationState>,
         _pvp: bool,
     ) -> ReloadResponse {
         let mut reload_stat = self
@@ -43,15 +44,15 @@ impl Weapon {
             .get(&StatHashes::RELOAD.into())
             .unwrap_or(&Stat::new())
             .perk_val();
-        let mut default_chd_dt = HashMap::new();
-        let cached_data = _cached_data.unwrap_or(&mut default_chd_dt);
+        let mut default_sim_state = SimulationState::default();
+        let sim_state = _sim_state.unwrap_or(&mut default_sim_state);
         if self.weapon_type == WeaponType::BOW {
             reload_stat = reload_stat.clamp(0, 80);
         }
         let mut out;
         if _calc_input.is_some() {
             let modifiers =
-                get_reload_modifier(self.list_perks(), &_calc_input.unwrap(), _pvp, cached_data);
+                get_reload_modifier(self.list_perks(), &_calc_input.unwrap(), _pvp, sim_state);
             out = self.reload_formula.calc_reload_time_formula(reload_stat);
             out.reload_time *= modifiers.reload_time_scale;
         } else {
@@ -105,7 +106,7 @@ impl Weapon {
     pub fn calc_range_falloff(
         &self,
         _calc_input: Option<CalculationInput>,
-        _cached_data: Option<&mut HashMap<String, f64>>,
+        _sim_state: Option<&mut SimulationState>,
         _pvp: bool,
     ) -> RangeResponse {
         let range_stat = self
@@ -118,11 +119,11 @@ impl Weapon {
             .get(&StatHashes::ZOOM.into())
             .unwrap_or(&Stat::new())
             .val();
-        let mut default_chd_dt = HashMap::new();
-        let cached_data = _cached_data.unwrap_or(&mut default_chd_dt);
+        let mut default_sim_state = SimulationState::default();
+        let sim_state = _sim_state.unwrap_or(&mut default_sim_state);
         if _calc_input.is_some() {
             let modifiers =
-                get_range_modifier(self.list_perks(), &_calc_input.unwrap(), _pvp, cached_data);
+                get_range_modifier(self.list_perks(), &_calc_input.unwrap(), _pvp, sim_state);
             self.range_formula.calc_range_falloff_formula(
                 range_stat,
                 zoom_stat,
@@ -167,7 +168,7 @@ impl Weapon {
     pub fn calc_handling_times(
         &self,
         _calc_input: Option<CalculationInput>,
-        _cached_data: Option<&mut HashMap<String, f64>>,
+        _sim_state: Option<&mut SimulationState>,
         _pvp: bool,
     ) -> HandlingResponse {
         let handling_stat = self
@@ -175,11 +176,11 @@ impl Weapon {
             .get(&StatHashes::HANDLING.into())
             .unwrap_or(&Stat::new())
             .val();
-        let mut default_chd_dt = HashMap::new();
-        let cached_data = _cached_data.unwrap_or(&mut default_chd_dt);
+        let mut default_sim_state = SimulationState::default();
+        let sim_state = _sim_state.unwrap_or(&mut default_sim_state);
         if _calc_input.is_some() {
             let modifiers =
-                get_handling_modifier(self.list_perks(), &_calc_input.unwrap(), _pvp, cached_data);
+                get_handling_modifier(self.list_perks(), &_calc_input.unwrap(), _pvp, sim_state);
             self.handling_formula
                 .calc_handling_times_formula(handling_stat, modifiers)
         } else {
@@ -234,12 +235,18 @@ impl Weapon {
     pub fn calc_ammo_sizes(
         &self,
         _calc_input: Option<CalculationInput>,
-        _cached_data: Option<&mut HashMap<String, f64>>,
+        _sim_state: Option<&mut SimulationState>,
         _pvp: bool,
     ) -> AmmoResponse {
+        //a swords magazine is its ammo capacity
+        let mag_hash = if self.weapon_type == WeaponType::SWORD {
+            StatHashes::AMMO_CAPACITY
+        } else {
+            StatHashes::MAGAZINE
+        };
         let mag_stat = self
             .stats
-            .get(&StatHashes::MAGAZINE.into())
+            .get(&mag_hash.into())
             .unwrap_or(&Stat::new())
             .val();
         let inv_stat = self
@@ -248,20 +255,20 @@ impl Weapon {
             .unwrap_or(&Stat::new())
             .val();
         let mut out;
-        let mut default_chd_dt = HashMap::new();
-        let cached_data = _cached_data.unwrap_or(&mut default_chd_dt);
+        let mut default_sim_state = SimulationState::default();
+        let sim_state = _sim_state.unwrap_or(&mut default_sim_state);
         if _calc_input.is_some() {
             let mag_modifiers = get_magazine_modifier(
                 self.list_perks(),
                 &_calc_input.clone().unwrap(),
                 _pvp,
-                cached_data,
+                sim_state,
             );
             let inv_modifiers = get_reserve_modifier(
                 self.list_perks(),
                 &_calc_input.clone().unwrap(),
                 _pvp,
-                cached_data,
+                sim_state,
             );
             out = self.ammo_formula.calc_ammo_size_formula(
                 mag_stat,
@@ -295,32 +302,32 @@ impl Weapon {
     pub fn calc_firing_data(
         &self,
         _calc_input: Option<CalculationInput>,
-        _cached_data: Option<&mut HashMap<String, f64>>,
+        _sim_state: Option<&mut SimulationState>,
         _pvp: bool,
     ) -> FiringResponse {
         let pve_damage_modifiers: DamageModifierResponse;
         let pvp_damage_modifiers: DamageModifierResponse;
         let firing_modifiers: FiringModifierResponse;
-        let mut default_cached_data = HashMap::new();
-        let cached_data = _cached_data.unwrap_or(&mut default_cached_data);
+        let mut default_sim_state = SimulationState::default();
+        let sim_state = _sim_state.unwrap_or(&mut default_sim_state);
         if _calc_input.is_some() {
             firing_modifiers = get_firing_modifier(
                 self.list_perks(),
                 &_calc_input.clone().unwrap(),
                 _pvp,
-                cached_data,
+                sim_state,
             );
             pvp_damage_modifiers = get_dmg_modifier(
                 self.list_perks(),
                 &_calc_input.clone().unwrap(),
                 true,
-                cached_data,
+                sim_state,
             );
             pve_damage_modifiers = get_dmg_modifier(
                 self.list_perks(),
                 &_calc_input.clone().unwrap(),
                 false,
-                cached_data,
+                sim_state,
             );
         } else {
             firing_modifiers = FiringModifierResponse::default();
@@ -409,15 +416,15 @@ impl Weapon {
         _calc_input: Option<CalculationInput>,
         _resillience: i32,
         _pvp: bool,
-        _cached_data: Option<&mut HashMap<String, f64>>,
+        _sim_state: Option<&mut SimulationState>,
     ) -> f64 {
         /*
         Todo:
         X3 Unflinching
         Perfect Float
          */
-        let mut default_cached_data = HashMap::new();
-        let cached_data = _cached_data.unwrap_or(&mut default_cached_data);
+        let mut default_sim_state = SimulationState::default();
+        let sim_state = _sim_state.unwrap_or(&mut default_sim_state);
         let mut total_scaler = 1.0;
 
         //resil
@@ -455,7 +462,7 @@ impl Weapon {
 
         if _calc_input.is_some() {
             total_scaler *=
-                get_flinch_modifier(self.list_perks(), &_calc_input.unwrap(), _pvp, cached_data)
+                get_flinch_modifier(self.list_perks(), &_calc_input.unwrap(), _pvp, sim_state)
                     .flinch_scale;
         }
 
@@ -469,10 +476,10 @@ impl Weapon {
         &self,
         _calc_input: Option<CalculationInput>,
         _pvp: bool,
-        _cached_data: Option<&mut HashMap<String, f64>>,
+        _sim_state: Option<&mut SimulationState>,
     ) -> MetersPerSecond {
-        let mut default_cached_data = HashMap::new();
-        let cached_data = _cached_data.unwrap_or(&mut default_cached_data);
+        let mut default_sim_state = SimulationState::default();
+        let sim_state = _sim_state.unwrap_or(&mut default_sim_state);
 
         //Range/Velocity stat to m/s
         let mut velocity = match self.weapon_type {
@@ -511,7 +518,7 @@ impl Weapon {
 
         if _calc_input.is_some() {
             velocity *=
-                get_velocity_modifier(self.list_perks(), &_calc_input.unwrap(), _pvp, cached_data)
+                get_velocity_modifier(self.list_perks(), &_calc_input.unwrap(), _pvp, sim_state)
                     .velocity_scaler;
         }
         velocity
@@ -570,7 +577,7 @@ impl Weapon {
         _pvp: bool,
     ) -> HashMap<String, f64> {
         let mut buffer: HashMap<String, f64> = HashMap::new();
-        let mut cached_data: HashMap<String, f64> = HashMap::new();
+        let mut sim_state = SimulationState::default();
 
         if matches!(
             self.weapon_type,
@@ -578,15 +585,24 @@ impl Weapon {
         ) {
             buffer.insert(
                 "velocity".to_string(),
-                self.calc_projectile_velocity(_calc_input, _pvp, Some(&mut cached_data)),
+                self.calc_projectile_velocity(_calc_input, _pvp, Some(&mut sim_state)),
             );
         };
 
-        if matches!(self.weapon_type, WeaponType::GLAIVE) {
-            // add "| WeaponType::SWORD" to matches when swords work
+        if matches!(self.weapon_type, WeaponType::GLAIVE | WeaponType::SWORD) {
             buffer.insert("shield_duration".to_string(), self.calc_shield_duration());
         }
 
+        if self.weapon_type == WeaponType::SWORD {
+            let guard = self.calc_guard_stats();
+            buffer.insert("guard_efficiency".to_string(), guard.efficiency);
+            buffer.insert("guard_resistance".to_string(), guard.resistance);
+            buffer.insert(
+                "ammo_per_heavy".to_string(),
+                self.melee_formula.ammo_per_heavy as f64,
+            );
+        }
+
         if self.weapon_type == WeaponType::BOW {
             buffer.insert("perfect_draw".to_string(), self.calc_perfect_draw());
         }
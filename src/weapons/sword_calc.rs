@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{
+    d2_enums::{Seconds, StatHashes, WeaponType},
+    enemies::Enemy,
+    perks::{
+        get_dmg_modifier, get_firing_modifier, get_refund_modifier,
+        lib::CalculationInput,
+        perk_state::{PerkStateMachine, PerkTrigger},
+        simulation_state::SimulationState,
+    },
+    types::rs_types::DpsResponse,
+};
+
+use super::{
+    accuracy_calc::{ShotOutcome, ShotRoller},
+    dps_calc::calc_refund,
+    falloff_calc::Engagement,
+    Stat, Weapon,
+};
+
+//stops runaway refunds and ammo free swings without a time limit
+const MAX_SWINGS: i32 = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SwingType {
+    Light,
+    Heavy,
+    ///The attack released out of guard
+    Guarding,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct SwingProfile {
+    pub swing_type: SwingType,
+    pub damage: f64,
+    pub swing_time: Seconds,
+    pub ammo_cost: i32,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct GuardResponse {
+    pub endurance: Seconds,
+    ///Portion of blocked damage given back as guard energy
+    pub efficiency: f64,
+    ///Portion of incoming damage blocked while guarding
+    pub resistance: f64,
+}
+
+impl Weapon {
+    fn sword_stat(&self, _stat: StatHashes) -> f64 {
+        self.stats
+            .get(&_stat.into())
+            .unwrap_or(&Stat::new())
+            .perk_val()
+            .clamp(0, 100)
+            .into()
+    }
+
+    ///Swing time multiplier from the swing speed stat
+    pub fn calc_swing_speed_scale(&self) -> f64 {
+        if self.weapon_type != WeaponType::SWORD {
+            return 1.0;
+        }
+        1.05 - self.sword_stat(StatHashes::SWING_SPEED) * 0.001
+    }
+
+    ///Returns a zeroed profile if the weapon has no such swing
+    pub fn calc_swing_profile(&self, _swing: SwingType) -> SwingProfile {
+        let melee = &self.melee_formula;
+        let (damage, delay, ammo_cost) = match _swing {
+            SwingType::Light => (melee.light_damage, melee.light_delay, 0),
+            SwingType::Heavy => (melee.heavy_damage, melee.heavy_delay, melee.ammo_per_heavy),
+            SwingType::Guarding => (melee.guard_damage, melee.guard_delay, melee.ammo_per_heavy),
+        };
+        SwingProfile {
+            swing_type: _swing,
+            damage,
+            swing_time: delay * self.calc_swing_speed_scale(),
+            ammo_cost,
+        }
+    }
+
+    pub fn calc_swing_profiles(&self) -> Vec<SwingProfile> {
+        [SwingType::Light, SwingType::Heavy, SwingType::Guarding]
+            .into_iter()
+            .map(|swing| self.calc_swing_profile(swing))
+            .filter(|profile| profile.damage > 0.0)
+            .collect()
+    }
+
+    ///Dps of repeating one swing for a fixed number of seconds
+    pub fn calc_melee_dps(
+        &self,
+        _enemy: &Enemy,
+        _pl_dmg_mult: f64,
+        _swing: SwingType,
+        _seconds: Seconds,
+    ) -> DpsResponse {
+        melee_dps_calc(self, _enemy, _pl_dmg_mult, _swing, Some(_seconds))
+    }
+
+    pub fn calc_guard_stats(&self) -> GuardResponse {
+        if self.weapon_type != WeaponType::SWORD {
+            return GuardResponse::default();
+        }
+        GuardResponse {
+            endurance: self.calc_shield_duration(),
+            efficiency: self.sword_stat(StatHashes::GUARD_EFFICIENCY) * 0.0035 + 0.15,
+            resistance: self.sword_stat(StatHashes::GUARD_RESISTANCE) * 0.0025 + 0.5,
+        }
+    }
+}
+
+//optional parts of a melee sim, the default spends the full ammo supply at point blank
+//with every swing landing
+#[derive(Default)]
+pub(crate) struct MeleeSimOptions<'a> {
+    pub starting_ammo: Option<i32>,
+    pub time_limit: Option<Seconds>,
+    pub roller: Option<&'a mut ShotRoller>,
+    pub engagement: Option<&'a Engagement>,
+}
+
+pub(crate) struct MeleeSimResult {
+    pub response: DpsResponse,
+    pub ammo_left: i32,
+}
+
+///Spends a swords ammo on heavy swings, refunds from perks are honoured,
+///starts with the full supply if no starting ammo is given
+pub fn sword_dps_calc(
+    _weapon: &Weapon,
+    _enemy: &Enemy,
+    _pl_dmg_mult: f64,
+    _starting_ammo: Option<i32>,
+) -> DpsResponse {
+    let options = MeleeSimOptions {
+        starting_ammo: _starting_ammo,
+        ..Default::default()
+    };
+    melee_sim(_weapon, _enemy, _pl_dmg_mult, SwingType::Heavy, options).response
+}
+
+///Repeats one swing until the ammo it costs runs out or the time limit is hit,
+///swings that cost no ammo like a glaives melee only stop at the time limit
+pub fn melee_dps_calc(
+    _weapon: &Weapon,
+    _enemy: &Enemy,
+    _pl_dmg_mult: f64,
+    _swing: SwingType,
+    _time_limit: Option<Seconds>,
+) -> DpsResponse {
+    let options = MeleeSimOptions {
+        time_limit: _time_limit,
+        ..Default::default()
+    };
+    melee_sim(_weapon, _enemy, _pl_dmg_mult, _swing, options).response
+}
+
+pub(crate) fn melee_sim(
+    _weapon: &Weapon,
+    _enemy: &Enemy,
+    _pl_dmg_mult: f64,
+    _swing: SwingType,
+    _options: MeleeSimOptions,
+) -> MeleeSimResult {
+    let MeleeSimOptions {
+        starting_ammo,
+        time_limit,
+        mut roller,
+        engagement,
+    } = _options;
+    let mut weapon = _weapon.clone();
+    let swing = weapon.calc_swing_profile(_swing);
+    let mut perks = weapon.list_perks();
+    let mut perk_states = PerkStateMachine::new(&weapon);
+    perk_states.trigger(PerkTrigger::Swap, 0.0);
+    let handling_data = weapon.calc_handling_times(None, None, false);
+    let mut pers_calc_data = SimulationState::default();
+    let time_limit = time_limit.unwrap_or(f64::INFINITY);
+
+    let base_ammo = weapon
+        .calc_ammo_sizes(
+            Some(weapon.static_calc_input()),
+            Some(&mut pers_calc_data),
+            false,
+        )
+        .mag_size;
+    let mut ammo = starting_ammo.unwrap_or(base_ammo);
+
+    let mut total_damage = 0.0_f64;
+    let mut total_time = 0.0_f64;
+    let mut total_swings = 0_i32;
+    let mut total_hits = 0_i32;
+    let mut time_damage_data: Vec<(f64, f64)> = Vec::new();
+
+    while swing.damage > 0.0
+        && ammo >= swing.ammo_cost
+        && total_swings < MAX_SWINGS
+        && total_time <= time_limit
+    {
+        perk_states.advance(total_time);
+        if perk_states.apply(&mut weapon) {
+            perks = weapon.list_perks();
+        }
+        let perk_value_map = weapon.perk_value_map_update();
+        let calc_input = CalculationInput {
+            intrinsic_hash: weapon.intrinsic_hash,
+            curr_firing_data: &weapon.firing_data,
+            base_crit_mult: 1.0,
+            base_mag: base_ammo as f64,
+            curr_mag: ammo as f64,
+            ammo_type: &weapon.ammo_type,
+            weapon_type: &weapon.weapon_type,
+            stats: &weapon.stats,
+            perk_value_map: &perk_value_map,
+            enemy_type: &_enemy.type_,
+            shots_fired_this_mag: total_swings as f64,
+            total_shots_fired: total_swings as f64,
+            total_shots_hit: total_hits as f64,
+            reserves_left: ammo as f64,
+            time_total: total_time,
+            time_this_mag: total_time,
+            damage_type: &weapon.damage_type,
+            handling_data,
+            num_reloads: 0.0,
+            has_overshield: false,
+            player: &weapon.player,
+        };
+        pers_calc_data.reset_buffs();
+        let dmg_mods = get_dmg_modifier(perks.clone(), &calc_input, false, &mut pers_calc_data);
+        let firing_mods =
+            get_firing_modifier(perks.clone(), &calc_input, false, &mut pers_calc_data);
+
+        //melee formulas have no falloff data unless a custom formula adds one
+        let falloff_mult = engagement.map_or(1.0, |engagement| {
+            let range = weapon.calc_range_falloff(
+                Some(calc_input.clone()),
+                Some(&mut pers_calc_data),
+                false,
+            );
+            engagement.damage_mult(&range)
+        });
+        //swings can't crit so a precision roll is only a hit
+        let landed = roller
+            .as_deref_mut()
+            .map_or(true, |roller| roller.roll() != ShotOutcome::Miss);
+        let dmg = if landed { swing.damage } else { 0.0 }
+            * falloff_mult
+            * dmg_mods.impact_dmg_scale
+            * _pl_dmg_mult
+            * weapon.damage_mods.get_mod(&_enemy.type_)
+            * weapon.damage_mods.pve;
+        time_damage_data.push((total_time, dmg));
+        total_damage += dmg;
+        total_time += swing.swing_time * firing_mods.burst_delay_scale;
+        total_swings += 1;
+        total_hits += landed as i32;
+        ammo -= swing.ammo_cost;
+
+        if swing.ammo_cost > 0 {
+            let mut refund_input = weapon.sparse_calc_input(total_swings, total_time);
+            refund_input.shots_fired_this_mag = total_swings as f64;
+            let refunds =
+                get_refund_modifier(perks.clone(), &refund_input, false, &mut pers_calc_data);
+            ammo += calc_refund(total_hits, 0, false, refunds).0;
+        }
+    }
+
+    let response = DpsResponse {
+        dps_per_mag: if total_time > 0.0 {
+            vec![total_damage / total_time]
+        } else {
+            Vec::new()
+        },
+        time_damage_data,
+        total_damage,
+        total_time,
+        total_shots: total_swings,
+    };
+    MeleeSimResult {
+        response,
+        ammo_left: ammo,
+    }
+}
@@ -5,17 +5,27 @@ use serde::Serialize;
 use crate::{
     d2_enums::WeaponType,
     logging::extern_log,
-    perks::{get_dmg_modifier, get_firing_modifier, lib::CalculationInput},
+    perks::{
+        get_dmg_modifier, get_firing_modifier,
+        lib::CalculationInput,
+        perk_state::{PerkStateMachine, PerkTrigger},
+        simulation_state::SimulationState,
+    },
 };
 
-use super::{FiringData, Weapon};
+use super::{
+    falloff_calc::Engagement,
+    pvp_ttk_calc::{simulate_melee, OvershieldType, PvpTarget},
+    sword_calc::SwingType,
+    FiringData, Weapon,
+};
 
 //just to make code cleaner for now
 fn ceil(x: f64) -> f64 {
     x.ceil()
 }
 
-const RESILIENCE_VALUES: [f64; 11] = [
+pub(crate) const RESILIENCE_VALUES: [f64; 11] = [
     185.001, 186.001, 187.001, 188.001, 189.001, 190.001, 192.001, 194.001, 196.001, 198.01, 200.00,
 ];
 
@@ -44,8 +54,59 @@ pub struct ResillienceSummary {
 }
 
 pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
+    resilience_ttk(_weapon, _overshield, None)
+}
+
+///Same as `calc_ttk` with impact damage reduced by falloff at the engagement distance
+pub fn calc_ttk_at_distance(
+    _weapon: &Weapon,
+    _overshield: f64,
+    _engagement: &Engagement,
+) -> Vec<ResillienceSummary> {
+    resilience_ttk(_weapon, _overshield, Some(_engagement))
+}
+
+//swords can't crit so both kills are the same heavy swings
+fn sword_resilience_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
+    (0..RESILIENCE_VALUES.len())
+        .map(|i| {
+            let target = PvpTarget {
+                resilience: i,
+                overshield_type: if _overshield > 0.0 {
+                    OvershieldType::PERSISTENT
+                } else {
+                    OvershieldType::NONE
+                },
+                overshield: _overshield,
+                ..Default::default()
+            };
+            let mix = simulate_melee(_weapon, &target, SwingType::Heavy);
+            ResillienceSummary {
+                value: i as i32,
+                body_ttk: BodyKillData {
+                    bodyshots: mix.bodyshots,
+                    time_taken: mix.time_taken,
+                },
+                optimal_ttk: OptimalKillData {
+                    headshots: 0,
+                    bodyshots: mix.bodyshots,
+                    time_taken: mix.time_taken,
+                },
+            }
+        })
+        .collect()
+}
+
+fn resilience_ttk(
+    _weapon: &Weapon,
+    _overshield: f64,
+    _engagement: Option<&Engagement>,
+) -> Vec<ResillienceSummary> {
+    if _weapon.weapon_type == WeaponType::SWORD {
+        return sword_resilience_ttk(_weapon, _overshield);
+    }
     let mut ttk_data: Vec<ResillienceSummary> = Vec::new();
-    let mut persistent_data: HashMap<String, f64> = HashMap::new();
+    let mut persistent_data = SimulationState::default();
 
     let tmp_dmg_prof = _weapon.get_damage_profile();
     let impact_dmg = tmp_dmg_prof.0;
@@ -67,46 +128,58 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
         let mut opt_headshots = 0;
         let mut opt_bullet_timeline: Vec<(f64, f64)> = Vec::new();
 
+        let mut weapon = _weapon.clone();
+        let mut perk_states = PerkStateMachine::new(&weapon);
+        perk_states.trigger(PerkTrigger::Swap, 0.0);
+
         //Optimal ttk
         while opt_bullets_hit < 50.0 {
             //PERK CALCULATIONS////////////
+            perk_states.advance(opt_time_taken);
+            perk_states.apply(&mut weapon);
 
-            persistent_data.insert("health%".to_string(), (health - opt_damage_dealt) / 70.0);
-            persistent_data.insert("empowering".to_string(), 1.0);
-            persistent_data.insert("surge".to_string(), 1.0);
-            persistent_data.insert("debuff".to_string(), 1.0);
-            let calc_input = _weapon.pvp_calc_input(
+            persistent_data.target_health = (health - opt_damage_dealt) / 70.0;
+            persistent_data.reset_buffs();
+            let calc_input = weapon.pvp_calc_input(
                 opt_bullets_fired,
                 opt_bullets_hit,
                 opt_time_taken,
                 (_overshield - opt_damage_dealt) > 0.0,
             );
             let dmg_mods = get_dmg_modifier(
-                _weapon.list_perks().clone(),
+                weapon.list_perks().clone(),
                 &calc_input,
                 true,
                 &mut persistent_data,
             );
             let firing_mods = get_firing_modifier(
-                _weapon.list_perks().clone(),
+                weapon.list_perks().clone(),
                 &calc_input,
                 true,
                 &mut persistent_data,
             );
+            let falloff_mult = _engagement.map_or(1.0, |engagement| {
+                let range = weapon.calc_range_falloff(
+                    Some(calc_input.clone()),
+                    Some(&mut persistent_data),
+                    true,
+                );
+                engagement.damage_mult(&range)
+            });
             ///////////////////////////////
 
-            let body_damage = (impact_dmg * dmg_mods.impact_dmg_scale)
+            let body_damage = (impact_dmg * dmg_mods.impact_dmg_scale * falloff_mult)
                 + (explosion_dmg * dmg_mods.explosive_dmg_scale);
             let critical_multiplier = crit_mult * dmg_mods.crit_scale;
-            let head_diff = ((impact_dmg * dmg_mods.impact_dmg_scale) * critical_multiplier)
-                - (impact_dmg * dmg_mods.impact_dmg_scale);
+            let head_diff = ((impact_dmg * dmg_mods.impact_dmg_scale * falloff_mult)
+                * critical_multiplier)
+                - (impact_dmg * dmg_mods.impact_dmg_scale * falloff_mult);
 
-            let shot_burst_delay = (_weapon.firing_data.burst_delay + firing_mods.burst_delay_add)
+            let shot_burst_delay = (weapon.firing_data.burst_delay + firing_mods.burst_delay_add)
                 * firing_mods.burst_delay_scale;
             let shot_inner_burst_delay =
-                _weapon.firing_data.inner_burst_delay * firing_mods.inner_burst_scale;
-            let shot_burst_size =
-                _weapon.firing_data.burst_size as f64 + firing_mods.burst_size_add;
+                weapon.firing_data.inner_burst_delay * firing_mods.inner_burst_scale;
+            let shot_burst_size = weapon.firing_data.burst_size as f64 + firing_mods.burst_size_add;
 
             let mut shot_delay = if opt_bullets_hit % shot_burst_size > 0.0 && opt_bullets_hit > 0.0
             {
@@ -117,15 +190,10 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
                 shot_burst_delay
             };
 
-            if _weapon.hash == 4289226715 { // vex mythoclast
-            } else if _weapon.weapon_type == WeaponType::LINEARFUSIONRIFLE {
-                shot_delay *= 1.95;
-            } else if _weapon.weapon_type == WeaponType::FUSIONRIFLE {
-                shot_delay *= 1.45;
-            }
+            shot_delay *= weapon.charge_delay_scale();
 
             let ammo_fired;
-            if _weapon.firing_data.one_ammo {
+            if weapon.firing_data.one_ammo {
                 ammo_fired = opt_bullets_fired/shot_burst_size;
             } else {
                 ammo_fired = opt_bullets_fired;
@@ -139,6 +207,7 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
                 shot_delay += _weapon
                     .calc_reload_time(Some(calc_input.clone()), Some(&mut persistent_data), true)
                     .reload_time;
+                perk_states.trigger(PerkTrigger::Reload, opt_time_taken + shot_delay);
             }
 
             if opt_bullets_hit % shot_burst_size == 0.0 {
@@ -149,6 +218,7 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
             };
 
             opt_time_taken += shot_delay;
+            perk_states.trigger(PerkTrigger::PrecisionHit, opt_time_taken);
 
             opt_bullet_timeline.push((body_damage, head_diff));
 
@@ -191,45 +261,55 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
         let mut bdy_bullets_fired = 0.0;
         let mut bdy_time_taken = 0.0;
         let mut bdy_damage_dealt = 0.0;
+        let mut weapon = _weapon.clone();
+        let mut perk_states = PerkStateMachine::new(&weapon);
+        perk_states.trigger(PerkTrigger::Swap, 0.0);
         while bdy_bullets_hit < 50.0 {
             //PERK CALCULATIONS////////////
-            persistent_data.insert("health%".to_string(), (health - bdy_damage_dealt) / 70.0);
-            persistent_data.insert("empowering".to_string(), 1.0);
-            persistent_data.insert("surge".to_string(), 1.0);
-            persistent_data.insert("debuff".to_string(), 1.0);
-            let calc_input = _weapon.pvp_calc_input(
+            perk_states.advance(bdy_time_taken);
+            perk_states.apply(&mut weapon);
+            persistent_data.target_health = (health - bdy_damage_dealt) / 70.0;
+            persistent_data.reset_buffs();
+            let calc_input = weapon.pvp_calc_input(
                 bdy_bullets_fired,
                 bdy_bullets_hit,
                 bdy_time_taken,
                 (_overshield - bdy_damage_dealt) > 0.0,
             );
             let dmg_mods = get_dmg_modifier(
-                _weapon.list_perks().clone(),
+                weapon.list_perks().clone(),
                 &calc_input,
                 true,
                 &mut persistent_data,
             );
             let firing_mods = get_firing_modifier(
-                _weapon.list_perks().clone(),
+                weapon.list_perks().clone(),
                 &calc_input,
                 true,
                 &mut persistent_data,
             );
+            let falloff_mult = _engagement.map_or(1.0, |engagement| {
+                let range = weapon.calc_range_falloff(
+                    Some(calc_input.clone()),
+                    Some(&mut persistent_data),
+                    true,
+                );
+                engagement.damage_mult(&range)
+            });
             ///////////////////////////////
 
-            let tmp_dmg_prof = _weapon.get_damage_profile();
+            let tmp_dmg_prof = weapon.get_damage_profile();
             let impact_dmg = tmp_dmg_prof.0;
             let explosion_dmg = tmp_dmg_prof.1;
 
-            let body_damage = (impact_dmg * dmg_mods.impact_dmg_scale)
+            let body_damage = (impact_dmg * dmg_mods.impact_dmg_scale * falloff_mult)
                 + (explosion_dmg * dmg_mods.explosive_dmg_scale);
 
-            let shot_burst_delay = (_weapon.firing_data.burst_delay + firing_mods.burst_delay_add)
+            let shot_burst_delay = (weapon.firing_data.burst_delay + firing_mods.burst_delay_add)
                 * firing_mods.burst_delay_scale;
             let shot_inner_burst_delay =
-                _weapon.firing_data.inner_burst_delay * firing_mods.inner_burst_scale;
-            let shot_burst_size =
-                _weapon.firing_data.burst_size as f64 + firing_mods.burst_size_add;
+                weapon.firing_data.inner_burst_delay * firing_mods.inner_burst_scale;
+            let shot_burst_size = weapon.firing_data.burst_size as f64 + firing_mods.burst_size_add;
 
             let mut shot_delay = if bdy_bullets_hit % shot_burst_size > 0.0 && bdy_bullets_hit > 0.0
             {
@@ -240,15 +320,10 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
                 shot_burst_delay
             };
 
-            if _weapon.hash == 4289226715 { //vex mythoclast
-            } else if _weapon.weapon_type == WeaponType::LINEARFUSIONRIFLE {
-                shot_delay *= 1.95;
-            } else if _weapon.weapon_type == WeaponType::FUSIONRIFLE {
-                shot_delay *= 1.45;
-            }
+            shot_delay *= weapon.charge_delay_scale();
 
             let ammo_fired;
-            if _weapon.firing_data.one_ammo {
+            if weapon.firing_data.one_ammo {
                 ammo_fired = opt_bullets_fired/shot_burst_size;
             } else {
                 ammo_fired = opt_bullets_fired;
@@ -262,6 +337,7 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
                 shot_delay += _weapon
                     .calc_reload_time(Some(calc_input.clone()), Some(&mut persistent_data), true)
                     .reload_time;
+                perk_states.trigger(PerkTrigger::Reload, bdy_time_taken + shot_delay);
             }
 
             bdy_time_taken += shot_delay;
@@ -292,7 +368,26 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
 }
 
 impl Weapon {
+    ///Fusions and linears charge before every burst, vex mythoclast fires without charging
+    pub(crate) fn charge_delay_scale(&self) -> f64 {
+        const VEX_MYTHOCLAST: u32 = 4289226715;
+        if self.hash == VEX_MYTHOCLAST {
+            return 1.0;
+        }
+        match self.weapon_type {
+            WeaponType::LINEARFUSIONRIFLE => 1.95,
+            WeaponType::FUSIONRIFLE => 1.45,
+            _ => 1.0,
+        }
+    }
     pub fn calc_ttk(&self, _overshield: f64) -> Vec<ResillienceSummary> {
         calc_ttk(self, _overshield)
     }
+    pub fn calc_ttk_at_distance(
+        &self,
+        _overshield: f64,
+        _engagement: &Engagement,
+    ) -> Vec<ResillienceSummary> {
+        calc_ttk_at_distance(self, _overshield, _engagement)
+    }
 }
@@ -1,20 +1,27 @@
 use std::collections::HashMap;
 
 use crate::{
+    activity::PlayerState,
+    build_spec::WeaponSpec,
     d2_enums::{AmmoType, DamageType, WeaponType},
-    database,
+    formula_database::FormulaDatabase,
     perks::{enhanced_check, Perk},
     types::rs_types::{
-        AmmoFormula, DamageMods, DataPointers, HandlingFormula, RangeFormula, ReloadFormula,
-        StatQuadraticFormula, WeaponPath,
+        AmmoFormula, DamageMods, DataPointers, HandlingFormula, MeleeFormula, RangeFormula,
+        ReloadFormula, StatQuadraticFormula, WeaponPath,
     },
 };
 
 use super::{FiringData, Weapon};
 
-fn get_data_pointers(_weapon_type_id: u8, _intrinsic_hash: u32) -> Result<DataPointers, String> {
-    let pointer_map: HashMap<WeaponPath, DataPointers> = HashMap::from(database::DATA_POINTERS);
-    let pointer_result = pointer_map.get(&WeaponPath(_weapon_type_id as u32, _intrinsic_hash));
+pub fn get_data_pointers(
+    _db: &FormulaDatabase,
+    _weapon_type_id: u8,
+    _intrinsic_hash: u32,
+) -> Result<DataPointers, String> {
+    let pointer_result = _db
+        .data_pointers
+        .get(&WeaponPath(_weapon_type_id as u32, _intrinsic_hash));
     if pointer_result.is_none() {
         return Err(format!(
             "No data pointers found for intrinsic hash: {}",
@@ -27,29 +34,32 @@ fn get_data_pointers(_weapon_type_id: u8, _intrinsic_hash: u32) -> Result<DataPo
 
 impl Weapon {
     pub fn generate_weapon(
+        _db: &FormulaDatabase,
         _hash: u32,
         _weapon_type_id: u8,
         _intrinsic_hash: u32,
         _ammo_type_id: u32,
         _damage_type_id: u32,
     ) -> Result<Weapon, String> {
-        let data_pointer_result = get_data_pointers(_weapon_type_id, _intrinsic_hash);
+        let data_pointer_result = get_data_pointers(_db, _weapon_type_id, _intrinsic_hash);
         if data_pointer_result.is_err() {
             return Err(data_pointer_result.unwrap_err());
         }
         let data_pointer = data_pointer_result.unwrap();
 
-        let range_formula: RangeFormula = database::RANGE_DATA[data_pointer.r].clone();
+        let range_formula: RangeFormula = _db.range_data[data_pointer.r].clone();
 
-        let handling_formula: HandlingFormula = database::HANDLING_DATA[data_pointer.h].clone();
+        let handling_formula: HandlingFormula = _db.handling_data[data_pointer.h].clone();
 
-        let reload_formula: ReloadFormula = database::RELOAD_DATA[data_pointer.rl].clone();
+        let reload_formula: ReloadFormula = _db.reload_data[data_pointer.rl].clone();
 
-        let damage_mods: DamageMods = database::SCALAR_DATA[data_pointer.s].clone();
+        let damage_mods: DamageMods = _db.scalar_data[data_pointer.s].clone();
 
-        let firing_data: FiringData = database::FIRING_DATA[data_pointer.f].clone();
+        let firing_data: FiringData = _db.firing_data[data_pointer.f].clone();
 
-        let ammo_formula: AmmoFormula = database::AMMO_DATA[data_pointer.a].clone();
+        let ammo_formula: AmmoFormula = _db.ammo_data[data_pointer.a].clone();
+
+        let melee_formula: MeleeFormula = _db.melee_data[data_pointer.m];
 
         let weapon_type = WeaponType::from(_weapon_type_id as u32);
         let ammo_type = AmmoType::from(_ammo_type_id);
@@ -87,10 +97,20 @@ impl Weapon {
             firing_data,
             handling_formula,
             reload_formula,
+            melee_formula,
             range_formula,
             ammo_type,
             damage_type,
             weapon_type,
+            player: PlayerState::default(),
         })
     }
+
+    ///Regenerates the weapon from another formula database, keeping its perks, stats
+    ///and player
+    pub fn rebuild(&self, _db: &FormulaDatabase) -> Result<Weapon, String> {
+        let mut weapon = WeaponSpec::from(self).to_weapon(_db)?;
+        weapon.set_player_state(self.player.clone());
+        Ok(weapon)
+    }
 }